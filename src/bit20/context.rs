@@ -46,7 +46,11 @@ impl Message {
             .unwrap_or(&Inscription {
               content_type: None,
               body: None,
+              content_encoding: None,
               delegate: None,
+              metadata: None,
+              metaprotocol: None,
+              parent: None,
             }),
           &op.action,
         ) {
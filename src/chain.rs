@@ -1,4 +1,6 @@
-use bitcoin::util::address;
+use bitcoin::util::address::{self, Payload};
+use bitcoin::util::base58;
+use bitcoin::{PubkeyHash, ScriptHash};
 use {super::*, clap::ValueEnum};
 
 #[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -68,6 +70,26 @@ impl Chain {
     bitcoin::consensus::deserialize(&genesis_buf).unwrap()
   }
 
+  // Every record in the node's blk*.dat files starts with the network's
+  // message start bytes.
+  pub(crate) fn block_file_magic(self) -> [u8; 4] {
+    match self {
+      Self::Mainnet => [0xc0, 0xc0, 0xc0, 0xc0],
+      Self::Testnet => [0xfc, 0xc1, 0xb7, 0xdc],
+      Self::Signet | Self::Regtest => [0xfa, 0xbf, 0xb5, 0xda],
+    }
+  }
+
+  // Version bytes of base58 pay-to-pubkey-hash and pay-to-script-hash
+  // addresses, which follow Dogecoin rather than Bitcoin.
+  fn base58_versions(self) -> (u8, u8) {
+    match self {
+      Self::Mainnet => (0x1e, 0x16),
+      Self::Testnet => (0x71, 0xc4),
+      Self::Signet | Self::Regtest => (0x6f, 0xc4),
+    }
+  }
+
   pub(crate) fn address_from_script(
     self,
     script: &Script,
@@ -75,6 +97,36 @@ impl Chain {
     Address::from_script(script, self.network())
   }
 
+  pub(crate) fn address_from_str(self, s: &str) -> Result<Address> {
+    if let Ok(data) = base58::from_check(s) {
+      let (pubkey_hash, script_hash) = self.base58_versions();
+
+      let payload = match data.split_first() {
+        Some((version, hash)) if *version == pubkey_hash => {
+          Payload::PubkeyHash(PubkeyHash::from_slice(hash)?)
+        }
+        Some((version, hash)) if *version == script_hash => {
+          Payload::ScriptHash(ScriptHash::from_slice(hash)?)
+        }
+        _ => bail!("address {s} is not valid on {self}"),
+      };
+
+      return Ok(Address {
+        payload,
+        network: self.network(),
+      });
+    }
+
+    let address = Address::from_str(s)?;
+
+    ensure!(
+      address.network == self.network(),
+      "address {s} is not valid on {self}"
+    );
+
+    Ok(address)
+  }
+
   pub(crate) fn join_with_data_dir(self, data_dir: &Path) -> PathBuf {
     match self {
       Self::Mainnet => data_dir.to_owned(),
@@ -99,3 +151,54 @@ impl Display for Chain {
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn base58_addresses_use_chain_version_bytes() {
+    assert_eq!(
+      Chain::Mainnet
+        .address_from_str("D597kHXGdkwkryF9oGhz9Bp1ypTpD1u99Z")
+        .unwrap()
+        .payload,
+      Payload::PubkeyHash(PubkeyHash::from_inner([
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19
+      ]))
+    );
+    assert_eq!(
+      Chain::Mainnet
+        .address_from_str("9rSHsR8xxKEkKW8Tbv3SGBdiwnQGWZ4bdM")
+        .unwrap()
+        .payload,
+      Payload::ScriptHash(ScriptHash::from_inner([
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19
+      ]))
+    );
+    assert!(Chain::Testnet
+      .address_from_str("nUCBUJGBZjQUjwpLq6MSPbQKDgr7DPLQiL")
+      .is_ok());
+    assert!(Chain::Regtest
+      .address_from_str("mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth")
+      .is_ok());
+    assert!(Chain::Regtest
+      .address_from_str("2MsFFCK16VhsCcvPXruztdzzcTZEQCbNKjJ")
+      .is_ok());
+  }
+
+  #[test]
+  fn addresses_from_other_chains_are_rejected() {
+    assert_eq!(
+      Chain::Regtest
+        .address_from_str("D597kHXGdkwkryF9oGhz9Bp1ypTpD1u99Z")
+        .unwrap_err()
+        .to_string(),
+      "address D597kHXGdkwkryF9oGhz9Bp1ypTpD1u99Z is not valid on regtest"
+    );
+    assert!(Chain::Mainnet
+      .address_from_str("bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw")
+      .is_err());
+    assert!(Chain::Mainnet.address_from_str("D597kHXGdkwk").is_err());
+  }
+}
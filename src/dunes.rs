@@ -3,9 +3,11 @@ use {
   super::*,
 };
 
-pub use {edict::Edict, dune::Dune, dune_id::DuneId, dunestone::Dunestone, terms::Terms};
+pub use {
+  dune::Dune, dune_id::DuneId, dunestone::Dunestone, edict::Edict, flaw::Flaw, terms::Terms,
+};
 
-pub(crate) use {etching::Etching, pile::Pile, spaced_dune::SpacedDune};
+pub(crate) use {dunestone::Decoded, etching::Etching, pile::Pile, spaced_dune::SpacedDune};
 
 pub(crate) const CLAIM_BIT: u128 = 1 << 48;
 pub const MAX_DIVISIBILITY: u8 = 38;
@@ -15,6 +17,7 @@ const RESERVED: u128 = 6402364363415443603228541259936211926;
 mod edict;
 mod etching;
 mod flag;
+mod flaw;
 mod terms;
 mod pile;
 mod dune;
@@ -3,7 +3,7 @@ use super::*;
 
 const MAX_SPACERS: u32 = 0b00000111_11111111_11111111_11111111;
 
-#[derive(Default, Serialize, Debug, PartialEq)]
+#[derive(Default, Serialize, Debug, PartialEq, Clone)]
 pub struct Dunestone {
   pub edicts: Vec<Edict>,
   pub etching: Option<Etching>,
@@ -13,7 +13,7 @@ pub struct Dunestone {
 
 
 struct Message {
-  cenotaph: bool,
+  flaws: Vec<Flaw>,
   fields: HashMap<u128, u128>,
   edicts: Vec<Edict>,
 }
@@ -22,7 +22,7 @@ impl Message {
   fn from_integers(tx: &Transaction, payload: &[u128]) -> Self {
     let mut edicts = Vec::new();
     let mut fields = HashMap::new();
-    let mut cenotaph = false;
+    let mut flaws = Vec::new();
 
     for i in (0..payload.len()).step_by(2) {
       let tag = payload[i];
@@ -31,10 +31,9 @@ impl Message {
         let mut id = 0u128;
         for chunk in payload[i + 1..].chunks_exact(3) {
           id = id.saturating_add(chunk[0]);
-          if let Some(edict) = Edict::from_integers(tx, id, chunk[1], chunk[2]) {
-            edicts.push(edict);
-          } else {
-            cenotaph = true;
+          match Edict::from_integers(tx, id, chunk[1], chunk[2]) {
+            Ok(edict) => edicts.push(edict),
+            Err(flaw) => flaws.push(flaw),
           }
         }
         break;
@@ -47,23 +46,37 @@ impl Message {
       fields.entry(tag).or_insert(value);
     }
 
-    Self { cenotaph, fields, edicts }
+    Self { flaws, fields, edicts }
   }
 }
 
+/// A deciphered dunestone together with the raw integers it was read from and
+/// every reason it is a cenotaph, for inspecting messages.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Decoded {
+  #[serde(flatten)]
+  pub(crate) dunestone: Dunestone,
+  pub(crate) flaws: Vec<Flaw>,
+  pub(crate) integers: Vec<u128>,
+}
+
 impl Dunestone {
   pub fn from_transaction(transaction: &Transaction) -> Option<Self> {
     Self::decipher(transaction).ok().flatten()
   }
 
   fn decipher(transaction: &Transaction) -> Result<Option<Self>, script::Error> {
+    Ok(Self::decode(transaction)?.map(|decoded| decoded.dunestone))
+  }
+
+  pub(crate) fn decode(transaction: &Transaction) -> Result<Option<Decoded>, script::Error> {
     let Some(payload) = Dunestone::payload(transaction)? else {
       return Ok(None);
     };
 
     let integers = Dunestone::integers(&payload);
 
-    let Message { cenotaph, mut fields, mut edicts } = Message::from_integers(transaction, &integers);
+    let Message { mut flaws, mut fields, mut edicts } = Message::from_integers(transaction, &integers);
 
     /* Ignore deadline
     let deadline = Tag::Deadline
@@ -160,11 +173,33 @@ impl Dunestone {
       None
     };
 
-    Ok(Some(Self {
-      cenotaph: cenotaph || overflow || flags != 0 || fields.keys().any(|tag| tag % 2 == 0),
-      pointer,
-      edicts,
-      etching,
+    if overflow {
+      flaws.push(Flaw::SupplyOverflow);
+    }
+
+    if flags != 0 {
+      flaws.push(Flaw::UnrecognizedFlag(flags));
+    }
+
+    let mut even_tags = fields
+      .keys()
+      .copied()
+      .filter(|tag| tag % 2 == 0)
+      .collect::<Vec<u128>>();
+
+    even_tags.sort();
+
+    flaws.extend(even_tags.into_iter().map(Flaw::UnrecognizedEvenTag));
+
+    Ok(Some(Decoded {
+      dunestone: Self {
+        cenotaph: !flaws.is_empty(),
+        pointer,
+        edicts,
+        etching,
+      },
+      flaws,
+      integers,
     }))
   }
 
@@ -176,7 +211,11 @@ impl Dunestone {
       Flag::Etching.set(&mut flags);
 
       if etching.terms.is_some() {
-        Flag::Etching.set(&mut flags);
+        Flag::Terms.set(&mut flags);
+      }
+
+      if etching.turbo {
+        Flag::Turbo.set(&mut flags);
       }
 
       Tag::Flags.encode(flags, &mut payload);
@@ -206,8 +245,20 @@ impl Dunestone {
           Tag::Limit.encode(limit, &mut payload);
         }
 
-        if let Some(term) = mint.height.1 {
-          Tag::HeightEnd.encode(term.into(), &mut payload);
+        if let Some(start) = mint.height.0 {
+          Tag::HeightStart.encode(start.into(), &mut payload);
+        }
+
+        if let Some(end) = mint.height.1 {
+          Tag::HeightEnd.encode(end.into(), &mut payload);
+        }
+
+        if let Some(start) = mint.offset.0 {
+          Tag::OffsetStart.encode(start.into(), &mut payload);
+        }
+
+        if let Some(end) = mint.offset.1 {
+          Tag::OffsetEnd.encode(end.into(), &mut payload);
         }
 
         if let Some(cap) = mint.cap {
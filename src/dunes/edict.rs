@@ -13,17 +13,17 @@ impl Edict {
     id: u128,
     amount: u128,
     output: u128,
-  ) -> Option<Self> {
-    let dune_id = DuneId::try_from(id).ok()?;
+  ) -> Result<Self, Flaw> {
+    let dune_id = DuneId::try_from(id).map_err(|_| Flaw::EdictDuneId(id))?;
 
     if dune_id.height == 0 && dune_id.index > 0 {
-      return None;
+      return Err(Flaw::EdictDuneId(id));
     }
 
-    if output > u128::try_from(tx.output.len()).ok()? {
-      return None;
+    if output > u128::try_from(tx.output.len()).map_err(|_| Flaw::EdictOutput(output))? {
+      return Err(Flaw::EdictOutput(output));
     }
 
-    Some(Self { id, amount, output })
+    Ok(Self { id, amount, output })
   }
 }
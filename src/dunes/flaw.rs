@@ -0,0 +1,59 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Flaw {
+  EdictDuneId(u128),
+  EdictOutput(u128),
+  SupplyOverflow,
+  UnrecognizedEvenTag(u128),
+  UnrecognizedFlag(u128),
+}
+
+impl Display for Flaw {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::EdictDuneId(id) => write!(f, "edict dune ID {id} is invalid"),
+      Self::EdictOutput(output) => write!(
+        f,
+        "edict output {output} is greater than the number of outputs"
+      ),
+      Self::SupplyOverflow => write!(f, "premine plus cap times limit overflows"),
+      Self::UnrecognizedEvenTag(tag) => write!(f, "unrecognized even tag {tag}"),
+      Self::UnrecognizedFlag(flags) => write!(f, "unrecognized flags {flags:#x}"),
+    }
+  }
+}
+
+impl Serialize for Flaw {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_eq!(
+      Flaw::EdictOutput(3).to_string(),
+      "edict output 3 is greater than the number of outputs"
+    );
+    assert_eq!(
+      Flaw::UnrecognizedFlag(1 << 4).to_string(),
+      "unrecognized flags 0x10"
+    );
+  }
+
+  #[test]
+  fn serialize() {
+    assert_eq!(
+      serde_json::to_string(&Flaw::UnrecognizedEvenTag(10)).unwrap(),
+      r#""unrecognized even tag 10""#
+    );
+  }
+}
@@ -1,9 +1,11 @@
 use {
   self::{
+    block_source::BlockFiles,
     dunes::{Dune, DuneId},
     entry::{
-      BlockHashValue, DuneEntryValue, DuneIdValue, Entry, InscriptionEntry, InscriptionEntryValue,
-      InscriptionIdValue, OutPointMapValue, OutPointValue, SatPointValue, SatRange, TxidValue,
+      AddressActivityValue, BlockHashValue, DuneEntryValue, DuneEventValue, DuneIdValue, Entry,
+      InscriptionEntry, InscriptionEntryValue, InscriptionIdValue, InscriptionTransferValue,
+      OutPointMapValue, OutPointValue, ReorgEntryValue, SatPointValue, SatRange, TxidValue,
     },
     reorg::*,
     updater::Updater,
@@ -17,7 +19,8 @@ use {
   log::log_enabled,
   redb::{
     Database, DatabaseError, MultimapTable, MultimapTableDefinition, ReadableMultimapTable,
-    ReadableTable, StorageError, Table, TableDefinition, WriteTransaction,
+    ReadableTable, StorageError, Table, TableDefinition, TableHandle, UntypedMultimapTableHandle,
+    UntypedTableHandle, WriteTransaction,
   },
   std::collections::HashMap,
   std::io::Cursor,
@@ -27,22 +30,32 @@ use {
 };
 
 use crate::bit20::{
-  Balance, max_script_tick_key, min_script_tick_key, script_tick_key, Tick, TokenInfo,
+  Balance, max_script_tick_key, min_script_tick_key, Receipt, script_tick_key, Tick, TokenInfo,
 };
 use crate::bit20::script_key::ScriptKey;
 use crate::sat::Sat;
 use crate::sat_point::SatPoint;
 use crate::templates::BlockHashAndConfirmations;
 
-pub(crate) use self::entry::DuneEntry;
+pub(crate) use self::entry::{
+  AddressActivity, Direction, DuneEntry, DuneEvent, DuneEventKind, InscriptionTransfer, ReorgEntry,
+};
+pub(crate) use self::migrate::migrate;
+pub(crate) use self::snapshot::import_snapshot;
+pub(crate) use self::text::TextSearchError;
 
+mod block_source;
 mod entry;
 mod reorg;
 mod fetcher;
+mod migrate;
 mod rtx;
+mod snapshot;
+mod text;
 mod updater;
+mod verify;
 
-const SCHEMA_VERSION: u64 = 6;
+const SCHEMA_VERSION: u64 = 18;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -59,22 +72,35 @@ macro_rules! define_multimap_table {
 
 define_table! { HEIGHT_TO_BLOCK_HASH, u32, &BlockHashValue }
 define_table! { INSCRIPTION_ID_TO_INSCRIPTION_ENTRY, &InscriptionIdValue, InscriptionEntryValue }
+define_multimap_table! { INSCRIPTION_ID_TO_CHILDREN, &InscriptionIdValue, u64 }
 define_table! { INSCRIPTION_ID_TO_DUNE, &InscriptionIdValue, u128 }
+define_table! { INSCRIPTION_ID_TO_PARENT, &InscriptionIdValue, &InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SATPOINT, &InscriptionIdValue, &SatPointValue }
+define_multimap_table! { INSCRIPTION_ID_TO_TRANSFER, &InscriptionIdValue, &InscriptionTransferValue }
 define_table! { INSCRIPTION_NUMBER_TO_INSCRIPTION_ID, u64, &InscriptionIdValue }
 define_table! { OUTPOINT_TO_DUNE_BALANCES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_DUNE_HOLDER, &OutPointValue, &[u8] }
 define_table! { INSCRIPTION_ID_TO_TXIDS, &InscriptionIdValue, &[u8] }
 define_table! { INSCRIPTION_TXID_TO_TX, &[u8], &[u8] }
 define_table! { PARTIAL_TXID_TO_INSCRIPTION_TXIDS, &[u8], &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
 define_multimap_table! { ADDRESS_TO_OUTPOINT, &[u8], &OutPointValue}
+define_multimap_table! { ADDRESS_TO_ACTIVITY, &[u8], &AddressActivityValue }
+define_multimap_table! { METAPROTOCOL_TO_INSCRIPTION_NUMBER, &str, u64 }
+define_table! { DUNE_BALANCE_TO_HOLDER, &[u8], u128 }
+define_table! { DUNE_HOLDER_TO_BALANCE, &[u8], u128 }
 define_table! { DUNE_ID_TO_DUNE_ENTRY, DuneIdValue, DuneEntryValue }
+define_multimap_table! { DUNE_ID_TO_EVENT, DuneIdValue, &DuneEventValue }
+define_table! { DUNE_ID_TO_HOLDER_COUNT, DuneIdValue, u64 }
 define_table! { DUNE_TO_DUNE_ID, u128, DuneIdValue }
+define_table! { REORG_NUMBER_TO_REORG_ENTRY, u64, &ReorgEntryValue }
 define_table! { SATPOINT_TO_INSCRIPTION_ID, &SatPointValue, &InscriptionIdValue }
+define_multimap_table! { SHA256_TO_INSCRIPTION_NUMBER, &[u8; 32], u64 }
 define_table! { SAT_TO_INSCRIPTION_ID, u64, &InscriptionIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
+define_multimap_table! { TERM_TO_INSCRIPTION_NUMBER, &str, u64 }
 define_table! { TRANSACTION_ID_TO_DUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
@@ -82,9 +108,12 @@ define_table! { BIT20_BALANCES, &str, &[u8] }
 define_table! { BIT20_TOKEN, &str, &[u8] }
 define_table! { BIT20_INSCRIBE_TRANSFER, &[u8; 36], &[u8] }
 define_table! { BIT20_TRANSFERABLELOG, &str, &[u8] }
+define_table! { BIT20_TXID_TO_RECEIPTS, &TxidValue, &[u8] }
+define_table! { BIT20_INSCRIPTION_ID_TO_RECEIPTS, &InscriptionIdValue, &[u8] }
 
 pub(crate) struct Index {
   auth: Auth,
+  block_files: Option<Arc<Mutex<BlockFiles>>>,
   client: Client,
   database: Database,
   path: PathBuf,
@@ -93,10 +122,16 @@ pub(crate) struct Index {
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
   index_bit20: bool,
   index_dunes: bool,
   index_sats: bool,
+  index_text: bool,
   index_transactions: bool,
+  max_savepoints: u32,
+  reorg_auto_rollback: bool,
+  savepoint_interval: u32,
+  savepoint_tip_distance: u32,
   unrecoverably_reorged: AtomicBool,
   rpc_url: String,
   nr_parallel_requests: usize,
@@ -123,6 +158,8 @@ pub(crate) enum Statistic {
   SatRanges,
   Schema,
   IndexTransactions,
+  IndexAddresses,
+  IndexText,
 }
 
 impl Statistic {
@@ -217,15 +254,13 @@ impl Index {
       bail!("failed to create data dir `{}`: {err}", data_dir.display());
     }
 
-    let path = if let Some(path) = &options.index {
-      path.clone()
-    } else {
-      data_dir.join("index.redb")
-    };
+    let path = options.index_path()?;
 
+    let index_addresses;
     let index_bit20;
     let index_dunes;
     let index_sats;
+    let index_text;
     let index_transactions;
 
     let database = match unsafe { Database::builder().open(&path) } {
@@ -239,6 +274,11 @@ impl Index {
             .unwrap_or(0);
 
           match schema_version.cmp(&SCHEMA_VERSION) {
+            cmp::Ordering::Less if migrate::can_migrate(schema_version) =>
+              bail!(
+              "index at `{}` was built with an older version of ord, run `ord index migrate` to upgrade it in place: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+              path.display()
+            ),
             cmp::Ordering::Less =>
               bail!(
               "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
@@ -274,6 +314,16 @@ impl Index {
             .unwrap()
             .value()
             != 0;
+          index_addresses = statistics
+            .get(&Statistic::IndexAddresses.key())?
+            .unwrap()
+            .value()
+            != 0;
+          index_text = statistics
+            .get(&Statistic::IndexText.key())?
+            .unwrap()
+            .value()
+            != 0;
         }
 
         database
@@ -303,20 +353,7 @@ impl Index {
           tx
         };
 
-        tx.open_table(HEIGHT_TO_BLOCK_HASH)?;
-        tx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?;
-        tx.open_table(INSCRIPTION_ID_TO_DUNE)?;
-        tx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
-        tx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
-        tx.open_table(INSCRIPTION_ID_TO_TXIDS)?;
-        tx.open_table(INSCRIPTION_TXID_TO_TX)?;
-        tx.open_table(PARTIAL_TXID_TO_INSCRIPTION_TXIDS)?;
-        tx.open_table(OUTPOINT_TO_VALUE)?;
-        tx.open_multimap_table(ADDRESS_TO_OUTPOINT)?;
-        tx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
-        tx.open_table(SAT_TO_INSCRIPTION_ID)?;
-        tx.open_table(SAT_TO_SATPOINT)?;
-        tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+        Self::create_tables(&tx)?;
 
         {
           let mut outpoint_to_sat_ranges = tx.open_table(OUTPOINT_TO_SAT_RANGES)?;
@@ -330,6 +367,8 @@ impl Index {
           index_dunes = options.index_dunes();
           index_sats = options.index_sats;
           index_transactions = options.index_transactions;
+          index_addresses = options.index_addresses;
+          index_text = options.index_text;
 
           statistics.insert(&Statistic::IndexBit20.key(), &u64::from(index_bit20))?;
 
@@ -342,6 +381,13 @@ impl Index {
             &u64::from(index_transactions),
           )?;
 
+          statistics.insert(
+            &Statistic::IndexAddresses.key(),
+            &u64::from(index_addresses),
+          )?;
+
+          statistics.insert(&Statistic::IndexText.key(), &u64::from(index_text))?;
+
           statistics.insert(&Statistic::Schema.key(), &SCHEMA_VERSION)?;
         }
 
@@ -358,6 +404,14 @@ impl Index {
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
       auth,
+      block_files: if options.read_block_files {
+        Some(Arc::new(Mutex::new(BlockFiles::new(
+          options.dogecoin_blocks_dir()?,
+          options.chain().block_file_magic(),
+        ))))
+      } else {
+        None
+      },
       client,
       database,
       path,
@@ -365,10 +419,16 @@ impl Index {
       first_dune_height: options.first_dune_height(),
       genesis_block_coinbase_transaction,
       height_limit: options.height_limit,
+      index_addresses,
       index_bit20,
       index_dunes,
       index_sats,
+      index_text,
       index_transactions,
+      max_savepoints: options.max_savepoints(),
+      reorg_auto_rollback: options.reorg_auto_rollback,
+      savepoint_interval: options.savepoint_interval(),
+      savepoint_tip_distance: options.savepoint_tip_distance(),
       unrecoverably_reorged: AtomicBool::new(false),
       rpc_url,
       nr_parallel_requests,
@@ -376,6 +436,40 @@ impl Index {
     })
   }
 
+  fn create_tables(tx: &WriteTransaction) -> Result {
+    tx.open_table(HEIGHT_TO_BLOCK_HASH)?;
+    tx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?;
+    tx.open_table(INSCRIPTION_ID_TO_DUNE)?;
+    tx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
+    tx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+    tx.open_table(INSCRIPTION_ID_TO_TXIDS)?;
+    tx.open_table(INSCRIPTION_TXID_TO_TX)?;
+    tx.open_table(PARTIAL_TXID_TO_INSCRIPTION_TXIDS)?;
+    tx.open_table(OUTPOINT_TO_VALUE)?;
+    tx.open_multimap_table(ADDRESS_TO_OUTPOINT)?;
+    tx.open_multimap_table(ADDRESS_TO_ACTIVITY)?;
+    tx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
+    tx.open_table(SAT_TO_INSCRIPTION_ID)?;
+    tx.open_table(SAT_TO_SATPOINT)?;
+    tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+    tx.open_table(BIT20_TXID_TO_RECEIPTS)?;
+    tx.open_table(BIT20_INSCRIPTION_ID_TO_RECEIPTS)?;
+    tx.open_table(DUNE_BALANCE_TO_HOLDER)?;
+    tx.open_table(DUNE_HOLDER_TO_BALANCE)?;
+    tx.open_table(DUNE_ID_TO_HOLDER_COUNT)?;
+    tx.open_multimap_table(DUNE_ID_TO_EVENT)?;
+    tx.open_table(OUTPOINT_TO_DUNE_HOLDER)?;
+    tx.open_multimap_table(INSCRIPTION_ID_TO_CHILDREN)?;
+    tx.open_table(INSCRIPTION_ID_TO_PARENT)?;
+    tx.open_multimap_table(INSCRIPTION_ID_TO_TRANSFER)?;
+    tx.open_multimap_table(METAPROTOCOL_TO_INSCRIPTION_NUMBER)?;
+    tx.open_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)?;
+    tx.open_multimap_table(TERM_TO_INSCRIPTION_NUMBER)?;
+    tx.open_table(REORG_NUMBER_TO_REORG_ENTRY)?;
+
+    Ok(())
+  }
+
   pub(crate) fn get_unspent_outputs(&self, _wallet: Wallet) -> Result<BTreeMap<OutPoint, Amount>> {
     let mut utxos = BTreeMap::new();
     utxos.extend(
@@ -434,6 +528,14 @@ impl Index {
       .collect()
   }
 
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
+  pub(crate) fn has_bit20_index(&self) -> bool {
+    self.index_bit20
+  }
+
   pub(crate) fn has_dune_index(&self) -> bool {
     self.index_dunes
   }
@@ -442,6 +544,10 @@ impl Index {
     self.index_sats
   }
 
+  pub(crate) fn has_text_index(&self) -> bool {
+    self.index_text
+  }
+
   pub(crate) fn info(&self) -> Result<Info> {
     let wtx = self.begin_write()?;
 
@@ -506,23 +612,60 @@ impl Index {
         Err(err) => {
           log::info!("{}", err.to_string());
 
-            match err.downcast_ref() {
-              Some(&ReorgError::Recoverable { height, depth }) => {
-                Reorg::handle_reorg(self, height, depth)?;
+          match err.downcast_ref() {
+            Some(reorg @ &ReorgError::Recoverable { height, depth, .. }) => {
+              Reorg::handle_reorg(self, height, depth)?;
+              self.record_reorg(reorg.entry(true))?;
 
-                updater = Updater::new(self)?;
-              }
-              Some(&ReorgError::Unrecoverable) => {
-                self
-                  .unrecoverably_reorged
-                  .store(true, atomic::Ordering::Relaxed);
-                return Err(anyhow!(ReorgError::Unrecoverable));
-              }
-              _ => return Err(err),
-            };
-          }
+              updater = Updater::new(self)?;
+            }
+            Some(reorg @ &ReorgError::Unrecoverable { height, .. }) if self.reorg_auto_rollback => {
+              Reorg::rollback_index(self, height)?;
+              self.record_reorg(reorg.entry(true))?;
+
+              updater = Updater::new(self)?;
+            }
+            Some(reorg @ &ReorgError::Unrecoverable { .. }) => {
+              self.record_reorg(reorg.entry(false))?;
+              self
+                .unrecoverably_reorged
+                .store(true, atomic::Ordering::Relaxed);
+              return Err(err);
+            }
+            _ => return Err(err),
+          };
         }
+      }
+    }
+  }
+
+  // Reorgs are recorded after any rollback, so the rollback doesn't erase them.
+  fn record_reorg(&self, entry: ReorgEntry) -> Result {
+    let wtx = self.begin_write()?;
+
+    {
+      let mut reorgs = wtx.open_table(REORG_NUMBER_TO_REORG_ENTRY)?;
+      let number = reorgs.len()?;
+      reorgs.insert(&number, &entry.store())?;
     }
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn get_reorgs(&self) -> Result<Vec<ReorgEntry>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(REORG_NUMBER_TO_REORG_ENTRY)?
+        .iter()?
+        .rev()
+        .map(|result| result.map(|(_number, entry)| ReorgEntry::load(*entry.value())))
+        .collect::<Result<Vec<ReorgEntry>, StorageError>>()?,
+    )
   }
 
   pub(crate) fn is_unrecoverably_reorged(&self) -> bool {
@@ -670,6 +813,87 @@ impl Index {
     Ok(entries)
   }
 
+  pub(crate) fn get_dune_holder_count(&self, id: DuneId) -> Result<u64> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(DUNE_ID_TO_HOLDER_COUNT)?
+        .get(&id.store())?
+        .map(|count| count.value())
+        .unwrap_or_default(),
+    )
+  }
+
+  // Keys of DUNE_BALANCE_TO_HOLDER, which sort a dune's holders by descending
+  // balance, and then by script.
+  fn ranked_dune_holder_key(id: u128, balance: u128, holder: &[u8]) -> Vec<u8> {
+    let mut key = id.to_be_bytes().to_vec();
+    key.extend_from_slice(&(u128::MAX - balance).to_be_bytes());
+    key.extend_from_slice(holder);
+    key
+  }
+
+  /// Returns a page of the holders of dune `id` and their balances, largest
+  /// first, and whether more pages follow.
+  pub(crate) fn get_dune_holders(
+    &self,
+    id: DuneId,
+    page_index: usize,
+    page_size: usize,
+  ) -> Result<(Vec<(Script, u128)>, bool)> {
+    let start = u128::from(id).to_be_bytes();
+    let end = (u128::from(id) + 1).to_be_bytes();
+
+    let mut holders = self
+      .database
+      .begin_read()?
+      .open_table(DUNE_BALANCE_TO_HOLDER)?
+      .range::<&[u8]>(start.as_slice()..end.as_slice())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result.map(|(key, balance)| (Script::from(key.value()[32..].to_vec()), balance.value()))
+      })
+      .collect::<Result<Vec<(Script, u128)>, StorageError>>()?;
+
+    let more = holders.len() > page_size;
+
+    if more {
+      holders.pop();
+    }
+
+    Ok((holders, more))
+  }
+
+  /// Returns a page of dune `id`'s events, newest first, and whether more
+  /// pages follow.
+  pub(crate) fn get_dune_events(
+    &self,
+    id: DuneId,
+    page_index: usize,
+    page_size: usize,
+  ) -> Result<(Vec<DuneEvent>, bool)> {
+    let mut events = self
+      .database
+      .begin_read()?
+      .open_multimap_table(DUNE_ID_TO_EVENT)?
+      .get(id.store())?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| result.map(|entry| DuneEvent::load(*entry.value())))
+      .collect::<Result<Vec<DuneEvent>, StorageError>>()?;
+
+    let more = events.len() > page_size;
+
+    if more {
+      events.pop();
+    }
+
+    Ok((events, more))
+  }
+
   pub(crate) fn get_dune_balance(&self, outpoint: OutPoint, id: DuneId) -> Result<u128> {
     let rtx = self.database.begin_read()?;
 
@@ -833,6 +1057,32 @@ impl Index {
     Ok(result)
   }
 
+  pub(crate) fn get_address_activity(
+    &self,
+    address: &str,
+    page_index: usize,
+    page_size: usize,
+  ) -> Result<(Vec<AddressActivity>, bool)> {
+    let mut activity = self
+      .database
+      .begin_read()?
+      .open_multimap_table(ADDRESS_TO_ACTIVITY)?
+      .get(address.as_bytes())?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| result.map(|entry| AddressActivity::load(*entry.value())))
+      .collect::<Result<Vec<AddressActivity>, StorageError>>()?;
+
+    let more = activity.len() > page_size;
+
+    if more {
+      activity.pop();
+    }
+
+    Ok((activity, more))
+  }
+
   pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<BlockHeader>> {
     self.client.get_block_header(&hash).into_option()
   }
@@ -954,6 +1204,33 @@ impl Index {
     }
   }
 
+  pub(crate) fn get_bit20_receipts_by_txid(&self, txid: Txid) -> Result<Vec<Receipt>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(BIT20_TXID_TO_RECEIPTS)?
+        .get(&txid.store())?
+        .map(|v| bincode::deserialize::<Vec<Receipt>>(v.value()).unwrap())
+        .unwrap_or_default(),
+    )
+  }
+
+  pub(crate) fn get_bit20_receipts_by_inscription_id(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Vec<Receipt>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(BIT20_INSCRIPTION_ID_TO_RECEIPTS)?
+        .get(&inscription_id.store())?
+        .map(|v| bincode::deserialize::<Vec<Receipt>>(v.value()).unwrap())
+        .unwrap_or_default(),
+    )
+  }
+
   pub(crate) fn get_etching(&self, txid: Txid) -> Result<Option<SpacedDune>> {
     if self.block_count().unwrap() >= self.first_dune_height {
       let rtx = self.database.begin_read()?;
@@ -1021,6 +1298,315 @@ impl Index {
     )
   }
 
+  pub(crate) fn get_parent(&self, inscription_id: InscriptionId) -> Result<Option<InscriptionId>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(INSCRIPTION_ID_TO_PARENT)?
+        .get(&inscription_id.store())?
+        .map(|parent| Entry::load(*parent.value())),
+    )
+  }
+
+  pub(crate) fn get_children(
+    &self,
+    inscription_id: InscriptionId,
+    page_index: usize,
+    page_size: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let number_to_id = rtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+
+    let mut children = rtx
+      .open_multimap_table(INSCRIPTION_ID_TO_CHILDREN)?
+      .get(&inscription_id.store())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let number = result?.value();
+        number_to_id
+          .get(&number)?
+          .map(|id| Entry::load(*id.value()))
+          .ok_or_else(|| anyhow!("child inscription {number} missing from index"))
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = children.len() > page_size;
+
+    if more {
+      children.pop();
+    }
+
+    Ok((children, more))
+  }
+
+  /// Returns a page of inscription `inscription_id`'s transfers, newest
+  /// first, and whether more pages follow.
+  pub(crate) fn get_inscription_history(
+    &self,
+    inscription_id: InscriptionId,
+    page_index: usize,
+    page_size: usize,
+  ) -> Result<(Vec<InscriptionTransfer>, bool)> {
+    let mut transfers = self
+      .database
+      .begin_read()?
+      .open_multimap_table(INSCRIPTION_ID_TO_TRANSFER)?
+      .get(&inscription_id.store())?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| result.map(|entry| InscriptionTransfer::load(*entry.value())))
+      .collect::<Result<Vec<InscriptionTransfer>, StorageError>>()?;
+
+    let more = transfers.len() > page_size;
+
+    if more {
+      transfers.pop();
+    }
+
+    Ok((transfers, more))
+  }
+
+  pub(crate) fn get_inscriptions_by_metaprotocol(
+    &self,
+    metaprotocol: &str,
+    page_index: usize,
+    page_size: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let number_to_id = rtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+
+    let mut inscriptions = rtx
+      .open_multimap_table(METAPROTOCOL_TO_INSCRIPTION_NUMBER)?
+      .get(metaprotocol)?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let number = result?.value();
+        number_to_id
+          .get(&number)?
+          .map(|id| Entry::load(*id.value()))
+          .ok_or_else(|| anyhow!("inscription {number} missing from index"))
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  pub(crate) fn get_inscriptions_by_sha256(
+    &self,
+    sha256: sha256::Hash,
+  ) -> Result<Vec<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let number_to_id = rtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+
+    rtx
+      .open_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)?
+      .get(&sha256.into_inner())?
+      .map(|result| {
+        let number = result?.value();
+        number_to_id
+          .get(&number)?
+          .map(|id| Entry::load(*id.value()))
+          .ok_or_else(|| anyhow!("inscription {number} missing from index"))
+      })
+      .collect()
+  }
+
+  pub(crate) fn get_original_by_sha256(
+    &self,
+    sha256: sha256::Hash,
+  ) -> Result<Option<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(number) = rtx
+      .open_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)?
+      .get(&sha256.into_inner())?
+      .next()
+      .transpose()?
+      .map(|number| number.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(
+      rtx
+        .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?
+        .get(&number)?
+        .map(|id| Entry::load(*id.value())),
+    )
+  }
+
+  pub(crate) fn get_duplicates(&self) -> Result<Vec<(sha256::Hash, Vec<InscriptionId>)>> {
+    let rtx = self.database.begin_read()?;
+
+    let number_to_id = rtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+
+    let mut duplicates = Vec::new();
+
+    for result in rtx
+      .open_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)?
+      .iter()?
+    {
+      let (sha256, numbers) = result?;
+
+      let ids = numbers
+        .map(|result| {
+          let number = result?.value();
+          number_to_id
+            .get(&number)?
+            .map(|id| Entry::load(*id.value()))
+            .ok_or_else(|| anyhow!("inscription {number} missing from index"))
+        })
+        .collect::<Result<Vec<InscriptionId>>>()?;
+
+      if ids.len() > 1 {
+        duplicates.push((sha256::Hash::from_inner(*sha256.value()), ids));
+      }
+    }
+
+    Ok(duplicates)
+  }
+
+  // The inscription numbers indexed under a term, or under every term it
+  // prefixes, newest first.
+  fn term_numbers<'a>(
+    term_to_number: &'a impl ReadableMultimapTable<&'static str, u64>,
+    term: &str,
+    prefix: bool,
+  ) -> Result<text::Descending<'a>> {
+    let mut values = Vec::new();
+
+    if prefix {
+      for result in term_to_number.range::<&str>(term..)? {
+        let (key, value) = result?;
+
+        if !key.value().starts_with(term) {
+          break;
+        }
+
+        values.push(value);
+      }
+    } else {
+      values.push(term_to_number.get(term)?);
+    }
+
+    text::Descending::new(values)
+  }
+
+  pub(crate) fn search_text(
+    &self,
+    query: &str,
+    page_index: usize,
+    page_size: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let query = text::TextQuery::parse(query);
+
+    query.check()?;
+
+    let rtx = self.database.begin_read()?;
+
+    let term_to_number = rtx.open_multimap_table(TERM_TO_INSCRIPTION_NUMBER)?;
+
+    let numbers: Box<dyn Iterator<Item = Result<u64, StorageError>> + '_> =
+      match query.clauses.as_slice() {
+        [clause] if clause.terms.len() == 1 => Box::new(Self::term_numbers(
+          &term_to_number,
+          &clause.terms[0],
+          clause.prefix,
+        )?),
+        clauses => {
+          let mut candidates: Option<BTreeSet<u64>> = None;
+
+          for clause in clauses {
+            for (i, term) in clause.terms.iter().enumerate() {
+              let mut numbers = BTreeSet::new();
+
+              let prefix = clause.prefix && i + 1 == clause.terms.len();
+
+              for number in Self::term_numbers(&term_to_number, term, prefix)? {
+                numbers.insert(number?);
+
+                if numbers.len() > text::MAX_CANDIDATES {
+                  return Err(TextSearchError::TooManyMatches.into());
+                }
+              }
+
+              candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&numbers).copied().collect(),
+                None => numbers,
+              });
+            }
+          }
+
+          let candidates = candidates.unwrap_or_default();
+
+          if query.needs_verification() && candidates.len() > text::MAX_VERIFIED_CANDIDATES {
+            return Err(TextSearchError::TooManyMatches.into());
+          }
+
+          Box::new(candidates.into_iter().rev().map(Ok))
+        }
+      };
+
+    let number_to_id = rtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+
+    let mut skip = page_index.saturating_mul(page_size);
+    let mut inscriptions = Vec::new();
+
+    for number in numbers {
+      let number = number?;
+
+      let inscription_id = number_to_id
+        .get(&number)?
+        .map(|id| Entry::load(*id.value()))
+        .ok_or_else(|| anyhow!("inscription {number} missing from index"))?;
+
+      if query.needs_verification() {
+        let matches = self
+          .get_inscription_by_id(inscription_id)?
+          .as_ref()
+          .and_then(text::inscription_text)
+          .map(|text| query.matches(&text))
+          .unwrap_or_default();
+
+        if !matches {
+          continue;
+        }
+      }
+
+      if skip > 0 {
+        skip -= 1;
+        continue;
+      }
+
+      inscriptions.push(inscription_id);
+
+      if inscriptions.len() > page_size {
+        break;
+      }
+    }
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
   pub(crate) fn get_inscription_satpoint_by_id(
     &self,
     inscription_id: InscriptionId,
@@ -1120,15 +1706,63 @@ impl Index {
     .collect()
   }
 
-  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
-    if txid == self.genesis_block_coinbase_txid {
-      return Ok(Some(self.genesis_block_coinbase_transaction.clone()));
-    }
-
-    if self.index_transactions {
-      if let Some(transaction) = self
-        .database
-        .begin_read()?
+  pub(crate) fn get_inscription_satpoints_on_output(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
+    Self::inscriptions_on_output(
+      &self
+        .database
+        .begin_read()?
+        .open_table(SATPOINT_TO_INSCRIPTION_ID)?,
+      outpoint,
+    )?
+    .map(|result| result.map_err(|e| e.into()))
+    .collect()
+  }
+
+  pub(crate) fn get_partial_inscription_transactions(
+    &self,
+    txid: Txid,
+  ) -> Result<Vec<Transaction>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(txids) = rtx
+      .open_table(PARTIAL_TXID_TO_INSCRIPTION_TXIDS)?
+      .get(txid.into_inner().as_slice())?
+      .map(|txids| txids.value().to_vec())
+    else {
+      return Ok(Vec::new());
+    };
+
+    let txid_to_tx = rtx.open_table(INSCRIPTION_TXID_TO_TX)?;
+
+    let mut txs = Vec::new();
+
+    for txid in txids.chunks_exact(32) {
+      let Some(tx) = txid_to_tx.get(txid)? else {
+        return Ok(Vec::new());
+      };
+
+      txs.push(consensus::encode::deserialize(tx.value())?);
+    }
+
+    Ok(txs)
+  }
+
+  pub(crate) fn get_raw_mempool(&self) -> Result<Vec<Txid>> {
+    Ok(self.client.get_raw_mempool()?)
+  }
+
+  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    if txid == self.genesis_block_coinbase_txid {
+      return Ok(Some(self.genesis_block_coinbase_transaction.clone()));
+    }
+
+    if self.index_transactions {
+      if let Some(transaction) = self
+        .database
+        .begin_read()?
         .open_table(TRANSACTION_ID_TO_TRANSACTION)?
         .get(&txid.store())?
       {
@@ -1405,153 +2039,564 @@ impl Index {
     let inscription_id_to_satpoint = rtx.open_table(INSCRIPTION_ID_TO_SATPOINT).unwrap();
 
     assert_eq!(
-      satpoint_to_inscription_id.len().unwrap(),
-      inscription_id_to_satpoint.len().unwrap(),
+      satpoint_to_inscription_id.len().unwrap(),
+      inscription_id_to_satpoint.len().unwrap(),
+    );
+
+    assert_eq!(
+      SatPoint::load(
+        *inscription_id_to_satpoint
+          .get(&inscription_id.store())
+          .unwrap()
+          .unwrap()
+          .value()
+      ),
+      satpoint,
+    );
+
+    assert_eq!(
+      InscriptionId::load(
+        *satpoint_to_inscription_id
+          .get(&satpoint.store())
+          .unwrap()
+          .unwrap()
+          .value()
+      ),
+      inscription_id,
+    );
+
+    if self.has_sat_index().unwrap() {
+      assert_eq!(
+        InscriptionId::load(
+          *rtx
+            .open_table(SAT_TO_INSCRIPTION_ID)
+            .unwrap()
+            .get(&sat)
+            .unwrap()
+            .unwrap()
+            .value()
+        ),
+        inscription_id,
+      );
+
+      assert_eq!(
+        SatPoint::load(
+          *rtx
+            .open_table(SAT_TO_SATPOINT)
+            .unwrap()
+            .get(&sat)
+            .unwrap()
+            .unwrap()
+            .value()
+        ),
+        satpoint,
+      );
+    }
+  }
+
+  #[cfg(test)]
+  fn assert_non_existence_of_inscription(&self, inscription_id: InscriptionId) {
+    let rtx = self.database.begin_read().unwrap();
+
+    let inscription_id_to_satpoint = rtx.open_table(INSCRIPTION_ID_TO_SATPOINT).unwrap();
+    assert!(inscription_id_to_satpoint
+      .get(&inscription_id.store())
+      .unwrap()
+      .is_none());
+
+    let inscription_id_to_entry = rtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY).unwrap();
+    assert!(inscription_id_to_entry
+      .get(&inscription_id.store())
+      .unwrap()
+      .is_none());
+
+    for range in rtx
+      .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)
+      .unwrap()
+      .iter()
+      .into_iter()
+    {
+      for entry in range.into_iter() {
+        let (_number, id) = entry.unwrap();
+        assert!(InscriptionId::load(*id.value()) != inscription_id);
+      }
+    }
+
+    for range in rtx
+      .open_table(SATPOINT_TO_INSCRIPTION_ID)
+      .unwrap()
+      .iter()
+      .into_iter()
+    {
+      for entry in range.into_iter() {
+        let (_satpoint, ids) = entry.unwrap();
+        assert!(!ids
+          .into_iter()
+          .any(|id| InscriptionId::load(*id.unwrap().value()) == inscription_id))
+      }
+    }
+
+    if self.has_sat_index().unwrap() {
+      for range in rtx
+        .open_table(SAT_TO_INSCRIPTION_ID)
+        .unwrap()
+        .iter()
+        .into_iter()
+      {
+        for entry in range.into_iter() {
+          let (_sat, ids) = entry.unwrap();
+          assert!(!ids
+            .into_iter()
+            .any(|id| InscriptionId::load(*id.unwrap().value()) == inscription_id))
+        }
+      }
+    }
+  }
+
+  fn inscriptions_on_output<'a: 'tx, 'tx>(
+    satpoint_to_id: &'a impl ReadableTable<&'static SatPointValue, &'static InscriptionIdValue>,
+    outpoint: OutPoint,
+  ) -> Result<impl Iterator<Item = Result<(SatPoint, InscriptionId), StorageError>> + 'tx> {
+    let start = SatPoint {
+      outpoint,
+      offset: 0,
+    }
+    .store();
+
+    let end = SatPoint {
+      outpoint,
+      offset: u64::MAX,
+    }
+    .store();
+
+    Ok(
+      satpoint_to_id
+        .range::<&[u8; 44]>(&start..=&end)?
+        .map(|result| {
+          result.map(|(satpoint, id)| (Entry::load(*satpoint.value()), Entry::load(*id.value())))
+        }),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    bitcoin::secp256k1::rand::{self, RngCore},
+    crate::index::testing::Context,
+    super::*,
+  };
+
+  #[test]
+  fn dev20_receipts_are_recorded() {
+    let context = Context::builder()
+      .args(["--index-bit20", "--index-transactions"])
+      .build();
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0)],
+      script_sig: inscription(
+        "text/plain",
+        r#"{"p":"dev-20","op":"deploy","tick":"doge","max":"21000000"}"#,
+      )
+      .to_script_sig(),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId::from(txid);
+
+    let receipts = context.index.get_bit20_receipts_by_txid(txid).unwrap();
+
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].inscription_id, inscription_id);
+    assert_eq!(receipts[0].op, crate::bit20::OperationType::Deploy);
+    assert!(matches!(
+      receipts[0].result,
+      Ok(crate::bit20::Event::Deploy(_))
+    ));
+
+    assert_eq!(
+      context
+        .index
+        .get_bit20_receipts_by_inscription_id(inscription_id)
+        .unwrap(),
+      receipts
+    );
+  }
+
+  #[test]
+  fn dev20_transfer_can_spend_reveal_in_same_block() {
+    let context = Context::builder().args(["--index-bit20"]).build();
+    context.mine_blocks(1);
+
+    let chain = context.index.chain;
+    let holder = chain
+      .address_from_str("mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth")
+      .unwrap();
+    let recipient = chain
+      .address_from_str("2MsFFCK16VhsCcvPXruztdzzcTZEQCbNKjJ")
+      .unwrap();
+
+    for (height, body) in [
+      (
+        1,
+        r#"{"p":"dev-20","op":"deploy","tick":"doge","max":"1000"}"#,
+      ),
+      (2, r#"{"p":"dev-20","op":"mint","tick":"doge","amt":"100"}"#),
+    ] {
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(height, 0, 0)],
+        script_sig: inscription("text/plain", body).to_script_sig(),
+        recipient: Some(holder.clone()),
+        ..Default::default()
+      });
+      context.mine_blocks(1);
+    }
+
+    let reveal = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 0, 0)],
+      script_sig: inscription(
+        "text/plain",
+        r#"{"p":"dev-20","op":"transfer","tick":"doge","amt":"40"}"#,
+      )
+      .to_script_sig(),
+      recipient: Some(holder.clone()),
+      ..Default::default()
+    });
+
+    let value = context
+      .index
+      .client
+      .get_raw_transaction(&reveal, None)
+      .unwrap()
+      .output[0]
+      .value;
+
+    context
+      .index
+      .client
+      .send_raw_transaction(&Transaction {
+        version: 1,
+        lock_time: bitcoin::PackedLockTime::ZERO,
+        input: vec![TxIn {
+          previous_output: OutPoint {
+            txid: reveal,
+            vout: 0,
+          },
+          ..Default::default()
+        }],
+        output: vec![TxOut {
+          value,
+          script_pubkey: recipient.script_pubkey(),
+        }],
+      })
+      .unwrap();
+
+    context.mine_blocks(1);
+
+    let tick = "doge".parse::<Tick>().unwrap();
+    let balance = |address: &Address| {
+      context
+        .index
+        .get_bit20_balance(
+          &ScriptKey::from_address(address.clone(), chain.network()),
+          &tick,
+        )
+        .unwrap()
+        .unwrap()
+    };
+
+    assert_eq!(balance(&holder).overall_balance, 60);
+    assert_eq!(balance(&holder).transferable_balance, 0);
+    assert_eq!(balance(&recipient).overall_balance, 40);
+  }
+
+  #[test]
+  fn dune_events_sort_chronologically() {
+    let event = DuneEvent {
+      height: 2,
+      index: 1,
+      kind: DuneEventKind::Transfer,
+      output: Some(3),
+      txid: txid(1),
+      amount: 1000,
+    };
+
+    assert_eq!(DuneEvent::load(event.store()), event);
+
+    let mint = DuneEvent {
+      height: 1,
+      index: 7,
+      kind: DuneEventKind::Mint,
+      output: None,
+      txid: txid(2),
+      amount: u128::MAX,
+    };
+
+    assert_eq!(DuneEvent::load(mint.store()), mint);
+    assert!(mint.store() < event.store());
+  }
+
+  #[test]
+  fn inscription_transfers_sort_chronologically() {
+    let inscribed = InscriptionTransfer {
+      height: 1,
+      sequence: 5,
+      txid: txid(1),
+      old_satpoint: None,
+      new_satpoint: satpoint(1, 0),
+      owner: Some("DTZSTXecLmSXpRGSfht4tAMyqra1wsL7xb".into()),
+    };
+
+    assert_eq!(
+      InscriptionTransfer::load(inscribed.clone().store()),
+      inscribed
+    );
+
+    let transferred = InscriptionTransfer {
+      height: 1,
+      sequence: 6,
+      txid: txid(2),
+      old_satpoint: Some(satpoint(1, 0)),
+      new_satpoint: SatPoint {
+        outpoint: OutPoint::null(),
+        offset: 10,
+      },
+      owner: None,
+    };
+
+    assert_eq!(
+      InscriptionTransfer::load(transferred.clone().store()),
+      transferred
+    );
+    assert!(inscribed.store() < transferred.store());
+  }
+
+  #[test]
+  fn inscriptions_with_identical_content_are_grouped_by_sha256() {
+    let context = Context::builder().build();
+
+    let hello = sha256::Hash::hash(b"hello");
+    let world = sha256::Hash::hash(b"world");
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+
+      {
+        let mut number_to_id = wtx
+          .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)
+          .unwrap();
+        let mut sha256_to_number = wtx
+          .open_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)
+          .unwrap();
+
+        for (number, sha256) in [(2, hello), (0, world), (1, hello)] {
+          number_to_id
+            .insert(&number, &inscription_id(number as u32 + 1).store())
+            .unwrap();
+          sha256_to_number
+            .insert(&sha256.into_inner(), &number)
+            .unwrap();
+        }
+      }
+
+      wtx.commit().unwrap();
+    }
+
+    assert_eq!(
+      context.index.get_inscriptions_by_sha256(hello).unwrap(),
+      [inscription_id(2), inscription_id(3)]
+    );
+    assert_eq!(
+      context.index.get_original_by_sha256(hello).unwrap(),
+      Some(inscription_id(2))
+    );
+    assert_eq!(
+      context.index.get_original_by_sha256(world).unwrap(),
+      Some(inscription_id(1))
+    );
+    assert_eq!(
+      context
+        .index
+        .get_original_by_sha256(sha256::Hash::hash(b"foo"))
+        .unwrap(),
+      None
+    );
+    assert_eq!(
+      context.index.get_duplicates().unwrap(),
+      [(hello, vec![inscription_id(2), inscription_id(3)])]
+    );
+  }
+
+  #[test]
+  fn search_text_intersects_terms_and_prefixes() {
+    let context = Context::builder().arg("--index-text").build();
+
+    assert!(context.index.has_text_index());
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+
+      {
+        let mut number_to_id = wtx
+          .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)
+          .unwrap();
+        let mut term_to_number = wtx.open_multimap_table(TERM_TO_INSCRIPTION_NUMBER).unwrap();
+
+        for (number, terms) in [
+          (0, ["such", "wow"].as_slice()),
+          (1, ["much", "doge"].as_slice()),
+          (2, ["such", "dogecoin"].as_slice()),
+        ] {
+          number_to_id
+            .insert(&number, &inscription_id(number as u32 + 1).store())
+            .unwrap();
+
+          for term in terms {
+            term_to_number.insert(*term, &number).unwrap();
+          }
+        }
+      }
+
+      wtx.commit().unwrap();
+    }
+
+    assert_eq!(
+      context.index.search_text("Such", 0, 10).unwrap(),
+      (vec![inscription_id(3), inscription_id(1)], false)
+    );
+    assert_eq!(
+      context.index.search_text("doge*", 0, 10).unwrap(),
+      (vec![inscription_id(3), inscription_id(2)], false)
+    );
+    assert_eq!(
+      context.index.search_text("such doge*", 0, 10).unwrap(),
+      (vec![inscription_id(3)], false)
+    );
+    assert_eq!(
+      context.index.search_text("such", 0, 1).unwrap(),
+      (vec![inscription_id(3)], true)
     );
-
     assert_eq!(
-      SatPoint::load(
-        *inscription_id_to_satpoint
-          .get(&inscription_id.store())
-          .unwrap()
-          .unwrap()
-          .value()
-      ),
-      satpoint,
+      context.index.search_text("such", 1, 1).unwrap(),
+      (vec![inscription_id(1)], false)
     );
-
     assert_eq!(
-      InscriptionId::load(
-        *satpoint_to_inscription_id
-          .get(&satpoint.store())
-          .unwrap()
-          .unwrap()
-          .value()
-      ),
-      inscription_id,
+      context.index.search_text("cat", 0, 10).unwrap(),
+      (Vec::new(), false)
     );
-
-    if self.has_sat_index().unwrap() {
-      assert_eq!(
-        InscriptionId::load(
-          *rtx
-            .open_table(SAT_TO_INSCRIPTION_ID)
-            .unwrap()
-            .get(&sat)
-            .unwrap()
-            .unwrap()
-            .value()
-        ),
-        inscription_id,
-      );
-
-      assert_eq!(
-        SatPoint::load(
-          *rtx
-            .open_table(SAT_TO_SATPOINT)
-            .unwrap()
-            .get(&sat)
-            .unwrap()
-            .unwrap()
-            .value()
-        ),
-        satpoint,
-      );
-    }
   }
 
-  #[cfg(test)]
-  fn assert_non_existence_of_inscription(&self, inscription_id: InscriptionId) {
-    let rtx = self.database.begin_read().unwrap();
+  #[test]
+  fn search_text_pages_through_common_terms() {
+    let context = Context::builder().arg("--index-text").build();
 
-    let inscription_id_to_satpoint = rtx.open_table(INSCRIPTION_ID_TO_SATPOINT).unwrap();
-    assert!(inscription_id_to_satpoint
-      .get(&inscription_id.store())
-      .unwrap()
-      .is_none());
+    let count = u64::try_from(text::MAX_CANDIDATES).unwrap() + 1;
 
-    let inscription_id_to_entry = rtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY).unwrap();
-    assert!(inscription_id_to_entry
-      .get(&inscription_id.store())
-      .unwrap()
-      .is_none());
+    let id = |number: u64| InscriptionId {
+      txid: Txid::all_zeros(),
+      index: u32::try_from(number).unwrap(),
+    };
 
-    for range in rtx
-      .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)
-      .unwrap()
-      .iter()
-      .into_iter()
     {
-      for entry in range.into_iter() {
-        let (_number, id) = entry.unwrap();
-        assert!(InscriptionId::load(*id.value()) != inscription_id);
+      let wtx = context.index.database.begin_write().unwrap();
+
+      {
+        let mut number_to_id = wtx
+          .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)
+          .unwrap();
+        let mut term_to_number = wtx.open_multimap_table(TERM_TO_INSCRIPTION_NUMBER).unwrap();
+
+        for number in 0..count {
+          number_to_id.insert(&number, &id(number).store()).unwrap();
+          term_to_number.insert("doge", &number).unwrap();
+
+          if number % 2 == 0 {
+            term_to_number.insert("dogecoin", &number).unwrap();
+          }
+        }
       }
+
+      wtx.commit().unwrap();
     }
 
-    for range in rtx
-      .open_table(SATPOINT_TO_INSCRIPTION_ID)
-      .unwrap()
-      .iter()
-      .into_iter()
+    let page = |number: u64| ((number - 9)..=number).rev().map(id).collect::<Vec<_>>();
+
+    assert_eq!(
+      context.index.search_text("doge", 0, 10).unwrap(),
+      (page(count - 1), true)
+    );
+    assert_eq!(
+      context.index.search_text("doge", 1, 10).unwrap(),
+      (page(count - 11), true)
+    );
+    assert_eq!(
+      context.index.search_text("dog*", 1, 10).unwrap(),
+      (page(count - 11), true)
+    );
+    assert_eq!(
+      context
+        .index
+        .search_text("doge", usize::try_from(count / 10).unwrap(), 10)
+        .unwrap(),
+      (vec![id(0)], false)
+    );
+
+    assert_eq!(
+      context
+        .index
+        .search_text("doge dogecoin", 0, 10)
+        .unwrap_err()
+        .downcast::<TextSearchError>()
+        .unwrap(),
+      TextSearchError::TooManyMatches
+    );
+  }
+
+  #[test]
+  fn search_text_limits_work_per_query() {
+    let context = Context::builder().arg("--index-text").build();
+
     {
-      for entry in range.into_iter() {
-        let (_satpoint, ids) = entry.unwrap();
-        assert!(!ids
-          .into_iter()
-          .any(|id| InscriptionId::load(*id.unwrap().value()) == inscription_id))
-      }
-    }
+      let wtx = context.index.database.begin_write().unwrap();
 
-    if self.has_sat_index().unwrap() {
-      for range in rtx
-        .open_table(SAT_TO_INSCRIPTION_ID)
-        .unwrap()
-        .iter()
-        .into_iter()
       {
-        for entry in range.into_iter() {
-          let (_sat, ids) = entry.unwrap();
-          assert!(!ids
-            .into_iter()
-            .any(|id| InscriptionId::load(*id.unwrap().value()) == inscription_id))
+        let mut term_to_number = wtx.open_multimap_table(TERM_TO_INSCRIPTION_NUMBER).unwrap();
+
+        for number in 0..=u64::try_from(text::MAX_VERIFIED_CANDIDATES).unwrap() {
+          term_to_number.insert("such", &number).unwrap();
+          term_to_number.insert("wow", &number).unwrap();
         }
       }
-    }
-  }
 
-  fn inscriptions_on_output<'a: 'tx, 'tx>(
-    satpoint_to_id: &'a impl ReadableTable<&'static SatPointValue, &'static InscriptionIdValue>,
-    outpoint: OutPoint,
-  ) -> Result<impl Iterator<Item = Result<(SatPoint, InscriptionId), StorageError>> + 'tx> {
-    let start = SatPoint {
-      outpoint,
-      offset: 0,
+      wtx.commit().unwrap();
     }
-    .store();
 
-    let end = SatPoint {
-      outpoint,
-      offset: u64::MAX,
-    }
-    .store();
+    assert_eq!(
+      context
+        .index
+        .search_text("s*", 0, 10)
+        .unwrap_err()
+        .downcast::<TextSearchError>()
+        .unwrap(),
+      TextSearchError::PrefixTooShort { prefix: "s".into() }
+    );
 
-    Ok(
-      satpoint_to_id
-        .range::<&[u8; 44]>(&start..=&end)?
-        .map(|result| {
-          result.map(|(satpoint, id)| (Entry::load(*satpoint.value()), Entry::load(*id.value())))
-        }),
-    )
-  }
-}
+    assert_eq!(
+      context
+        .index
+        .search_text(r#""such wow""#, 0, 10)
+        .unwrap_err()
+        .downcast::<TextSearchError>()
+        .unwrap(),
+      TextSearchError::TooManyMatches
+    );
 
-#[cfg(test)]
-mod tests {
-  use {
-    bitcoin::secp256k1::rand::{self, RngCore},
-    crate::index::testing::Context,
-    super::*,
-  };
+    assert!(context.index.search_text("suc*", 0, 10).is_ok());
+  }
 
   #[test]
   fn height_limit() {
@@ -1577,6 +2622,86 @@ mod tests {
     }
   }
 
+  #[test]
+  fn address_index_is_opt_in() {
+    assert!(!Context::builder().build().index.has_address_index());
+    assert!(Context::builder()
+      .arg("--index-addresses")
+      .build()
+      .index
+      .has_address_index());
+  }
+
+  #[test]
+  fn address_activity_is_stored_in_height_order() {
+    let earlier = AddressActivity {
+      height: 1,
+      txid: Txid::from_inner([0xff; 32]),
+      direction: Direction::Sent,
+      value: u64::MAX,
+    };
+
+    let later = AddressActivity {
+      height: 256,
+      txid: Txid::all_zeros(),
+      direction: Direction::Received,
+      value: 0,
+    };
+
+    assert!(earlier.store() < later.store());
+    assert_eq!(AddressActivity::load(earlier.store()), earlier);
+    assert_eq!(AddressActivity::load(later.store()), later);
+  }
+
+  #[test]
+  fn address_activity_is_indexed() {
+    let context = Context::builder().arg("--index-addresses").build();
+    context.mine_blocks(1);
+
+    let address = Address {
+      payload: bitcoin::util::address::Payload::PubkeyHash(bitcoin::PubkeyHash::all_zeros()),
+      network: Network::Regtest,
+    };
+
+    let received = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0)],
+      recipient: Some(address.clone()),
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    let sent = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0)],
+      fee: 1000,
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_address_activity(&address.to_string(), 0, 10)
+        .unwrap(),
+      (
+        vec![
+          AddressActivity {
+            height: 3,
+            txid: sent,
+            direction: Direction::Sent,
+            value: 50 * COIN_VALUE,
+          },
+          AddressActivity {
+            height: 2,
+            txid: received,
+            direction: Direction::Received,
+            value: 50 * COIN_VALUE,
+          },
+        ],
+        false
+      )
+    );
+  }
+
   #[test]
   fn inscriptions_below_first_inscription_height_are_skipped() {
     let inscription = inscription("text/plain;charset=utf-8", "hello");
@@ -2970,4 +4095,133 @@ mod tests {
           .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
     }
   }
+
+  #[test]
+  fn reorg_entry_round_trips() {
+    for entry in [
+      ReorgEntry {
+        height: 10,
+        depth: Some(2),
+        old_block_hash: BlockHash::from_inner([1; 32]),
+        new_block_hash: BlockHash::from_inner([2; 32]),
+        recovered: true,
+      },
+      ReorgEntry {
+        height: 10,
+        depth: None,
+        old_block_hash: BlockHash::from_inner([1; 32]),
+        new_block_hash: BlockHash::from_inner([2; 32]),
+        recovered: false,
+      },
+    ] {
+      assert_eq!(ReorgEntry::load(entry.store()), entry);
+    }
+  }
+
+  #[test]
+  fn recovered_reorgs_are_recorded() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(3);
+
+    let old_block_hash = context.index.block_hash(Some(3)).unwrap().unwrap();
+
+    context.rpc_server.invalidate_tip();
+    let blocks = context.mine_blocks(2);
+
+    assert_eq!(
+      context.index.get_reorgs().unwrap(),
+      [ReorgEntry {
+        height: 4,
+        depth: Some(2),
+        old_block_hash,
+        new_block_hash: blocks[0].block_hash(),
+        recovered: true,
+      }]
+    );
+  }
+
+  #[test]
+  fn unrecoverable_reorgs_are_recorded() {
+    let context = Context::builder()
+      .args(["--max-savepoints=1", "--savepoint-interval=1"])
+      .build();
+
+    context.mine_blocks(3);
+
+    context.rpc_server.invalidate_tip();
+    context.rpc_server.mine_blocks(2);
+
+    assert_eq!(
+      context.index.update().unwrap_err().to_string(),
+      "unrecoverable reorg detected"
+    );
+    assert!(context.index.is_unrecoverably_reorged());
+
+    let reorgs = context.index.get_reorgs().unwrap();
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(reorgs[0].depth, None);
+    assert!(!reorgs[0].recovered);
+  }
+
+  #[test]
+  fn unrecoverable_reorg_rolls_back_to_newest_savepoint_on_chain() {
+    let context = Context::builder()
+      .args(["--max-savepoints=3", "--savepoint-interval=1"])
+      .build();
+
+    for _ in 0..5 {
+      context.mine_blocks(1);
+    }
+
+    context.rpc_server.invalidate_tip();
+    context.rpc_server.invalidate_tip();
+    let blocks = context.rpc_server.mine_blocks(3);
+
+    assert_eq!(
+      context.index.update().unwrap_err().to_string(),
+      "unrecoverable reorg detected"
+    );
+
+    Reorg::rollback_index(&context.index, 6).unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 4);
+
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 7);
+    assert_eq!(
+      context.index.block_hash(Some(4)).unwrap(),
+      Some(blocks[0].block_hash())
+    );
+  }
+
+  #[test]
+  fn unrecoverable_reorg_rebuilds_index_with_auto_rollback() {
+    let context = Context::builder()
+      .args([
+        "--max-savepoints=1",
+        "--savepoint-interval=1",
+        "--reorg-auto-rollback",
+        "--index-sats",
+      ])
+      .build();
+
+    context.mine_blocks(3);
+
+    context.rpc_server.invalidate_tip();
+    let blocks = context.mine_blocks(2);
+
+    assert!(!context.index.is_unrecoverably_reorged());
+    assert_eq!(context.index.block_count().unwrap(), 5);
+    assert_eq!(
+      context.index.block_hash(Some(3)).unwrap(),
+      Some(blocks[0].block_hash())
+    );
+
+    let reorgs = context.index.get_reorgs().unwrap();
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(reorgs[0].depth, None);
+    assert!(reorgs[0].recovered);
+  }
 }
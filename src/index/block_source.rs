@@ -0,0 +1,492 @@
+use {
+  super::*,
+  std::{
+    collections::VecDeque,
+    io::{BufReader, Read, Seek, SeekFrom},
+  },
+};
+
+// Blocks this close to the node's tip are always fetched over RPC. They are the
+// ones most likely to be reorged, and the node may not have flushed them to its
+// block files yet.
+const TIP_DISTANCE: u32 = 100;
+
+pub(crate) trait BlockSource: Send {
+  fn get_block(&mut self, height: u32) -> Result<Option<Block>>;
+}
+
+impl Index {
+  pub(super) fn block_source(&self, height: u32) -> Result<Box<dyn BlockSource>> {
+    let rpc = RpcBlockSource::new(self)?;
+
+    let Some(block_files) = &self.block_files else {
+      return Ok(Box::new(rpc));
+    };
+
+    let tip = u32::try_from(self.client.get_block_count()?)?;
+
+    if tip.saturating_sub(height) <= TIP_DISTANCE {
+      return Ok(Box::new(rpc));
+    }
+
+    let tip = tip - TIP_DISTANCE;
+
+    log::info!(
+      "Reading blocks {height}..={tip} from `{}`",
+      block_files.lock().unwrap().blocks_dir.display()
+    );
+
+    Ok(Box::new(FileBlockSource::new(
+      self,
+      block_files.clone(),
+      tip,
+      Box::new(rpc),
+    )?))
+  }
+}
+
+pub(crate) struct RpcBlockSource {
+  client: Client,
+  first_inscription_height: u32,
+  index_sats: bool,
+}
+
+impl RpcBlockSource {
+  pub(crate) fn new(index: &Index) -> Result<Self> {
+    Ok(Self {
+      client: Client::new(&index.rpc_url, index.auth.clone())
+        .context("failed to connect to RPC URL")?,
+      first_inscription_height: index.first_inscription_height,
+      index_sats: index.index_sats,
+    })
+  }
+}
+
+impl BlockSource for RpcBlockSource {
+  fn get_block(&mut self, height: u32) -> Result<Option<Block>> {
+    let mut errors = 0;
+    loop {
+      match self
+        .client
+        .get_block_hash(height.into())
+        .into_option()
+        .and_then(|option| {
+          option
+            .map(|hash| {
+              if self.index_sats || height >= self.first_inscription_height {
+                Ok(self.client.get_block(&hash)?)
+              } else {
+                Ok(Block {
+                  header: self.client.get_block_header(&hash)?,
+                  txdata: Vec::new(),
+                })
+              }
+            })
+            .transpose()
+        }) {
+        Err(err) => {
+          if cfg!(test) {
+            return Err(err);
+          }
+
+          errors += 1;
+          let seconds = 1 << errors;
+          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
+
+          if seconds > 120 {
+            log::error!("would sleep for more than 120s, giving up");
+            return Err(err);
+          }
+
+          thread::sleep(Duration::from_secs(seconds));
+        }
+        Ok(result) => return Ok(result),
+      }
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+struct Location {
+  file: u32,
+  offset: u64,
+  size: u32,
+}
+
+// Where the node's blk*.dat files have been scanned up to, and the locations
+// of the most recently scanned blocks. The node appends blocks in the order it
+// receives them, which is not height order and includes blocks that were
+// later reorged out. It only downloads a bounded window of blocks ahead of its
+// tip though, so a block is found within a bounded number of records of its
+// parent, and only that many locations are remembered. Index updates share the
+// scan, so each picks up where the last one stopped.
+pub(crate) struct BlockFiles {
+  blocks_dir: PathBuf,
+  file: u32,
+  locations: HashMap<BlockHash, Location>,
+  magic: [u8; 4],
+  offset: u64,
+  order: VecDeque<BlockHash>,
+  reader: Option<BufReader<File>>,
+}
+
+impl BlockFiles {
+  // Far more than the node's download window, so blocks are only read over
+  // RPC if they are missing from the block files.
+  const WINDOW: usize = 10_000;
+
+  pub(crate) fn new(blocks_dir: PathBuf, magic: [u8; 4]) -> Self {
+    Self {
+      blocks_dir,
+      file: 0,
+      locations: HashMap::new(),
+      magic,
+      offset: 0,
+      order: VecDeque::new(),
+      reader: None,
+    }
+  }
+
+  fn path(blocks_dir: &Path, file: u32) -> PathBuf {
+    blocks_dir.join(format!("blk{file:05}.dat"))
+  }
+
+  // Scans up to `WINDOW` records for the block with `hash`.
+  fn locate(&mut self, hash: BlockHash) -> Result<Option<Location>> {
+    for _ in 0..Self::WINDOW {
+      if let Some(location) = self.locations.get(&hash) {
+        return Ok(Some(*location));
+      }
+
+      if !self.scan()? {
+        break;
+      }
+    }
+
+    Ok(self.locations.get(&hash).copied())
+  }
+
+  // Reads the next record, returning false at the end of the block files.
+  //
+  // Each record is the network magic, the block's size as a little-endian
+  // u32, and the serialized block. Files are preallocated and zero-filled, so
+  // a record without the magic marks the end of a file, or the point up to
+  // which the node has written the last one.
+  fn scan(&mut self) -> Result<bool> {
+    loop {
+      if self.reader.is_none() {
+        let path = Self::path(&self.blocks_dir, self.file);
+
+        if !path.exists() {
+          return Ok(false);
+        }
+
+        let mut reader = BufReader::new(
+          File::open(&path).with_context(|| format!("failed to open `{}`", path.display()))?,
+        );
+
+        reader.seek(SeekFrom::Start(self.offset))?;
+
+        self.reader = Some(reader);
+      }
+
+      let reader = self.reader.as_mut().unwrap();
+
+      let mut record = [0; 88];
+
+      let size = match reader.read_exact(&mut record) {
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => None,
+        result => {
+          result?;
+          Some(u32::from_le_bytes(record[4..8].try_into().unwrap()))
+            .filter(|size| record[..4] == self.magic && *size >= 80)
+        }
+      };
+
+      let Some(size) = size else {
+        self.reader = None;
+
+        if !Self::path(&self.blocks_dir, self.file + 1).exists() {
+          return Ok(false);
+        }
+
+        self.file += 1;
+        self.offset = 0;
+        continue;
+      };
+
+      reader.seek_relative(i64::from(size) - 80)?;
+
+      let header = &record[8..];
+
+      self.remember(
+        BlockHash::hash(header),
+        Location {
+          file: self.file,
+          offset: self.offset + 8,
+          size,
+        },
+      );
+
+      self.offset += 8 + u64::from(size);
+
+      return Ok(true);
+    }
+  }
+
+  fn remember(&mut self, hash: BlockHash, location: Location) {
+    if self.locations.insert(hash, location).is_some() {
+      return;
+    }
+
+    self.order.push_back(hash);
+
+    if self.order.len() > Self::WINDOW {
+      if let Some(oldest) = self.order.pop_front() {
+        self.locations.remove(&oldest);
+      }
+    }
+  }
+}
+
+// Reads blocks up to `tip` straight from the node's block files, looking up
+// each height's hash over RPC so that blocks which were reorged out are
+// skipped. Blocks past `tip`, or missing from the files, are fetched from the
+// fallback source.
+pub(crate) struct FileBlockSource {
+  client: Client,
+  fallback: Box<dyn BlockSource>,
+  files: Arc<Mutex<BlockFiles>>,
+  first_inscription_height: u32,
+  index_sats: bool,
+  open: Option<(u32, File)>,
+  tip: u32,
+}
+
+impl FileBlockSource {
+  pub(crate) fn new(
+    index: &Index,
+    files: Arc<Mutex<BlockFiles>>,
+    tip: u32,
+    fallback: Box<dyn BlockSource>,
+  ) -> Result<Self> {
+    Ok(Self {
+      client: Client::new(&index.rpc_url, index.auth.clone())
+        .context("failed to connect to RPC URL")?,
+      fallback,
+      files,
+      first_inscription_height: index.first_inscription_height,
+      index_sats: index.index_sats,
+      open: None,
+      tip,
+    })
+  }
+}
+
+impl BlockSource for FileBlockSource {
+  fn get_block(&mut self, height: u32) -> Result<Option<Block>> {
+    if height > self.tip {
+      return self.fallback.get_block(height);
+    }
+
+    let hash = self.client.get_block_hash(height.into())?;
+
+    let (blocks_dir, location) = {
+      let mut files = self.files.lock().unwrap();
+      (files.blocks_dir.clone(), files.locate(hash)?)
+    };
+
+    let Some(location) = location else {
+      log::debug!(
+        "block files in `{}` do not contain block {hash}, fetching it over RPC",
+        blocks_dir.display()
+      );
+      return self.fallback.get_block(height);
+    };
+
+    if self.open.as_ref().map(|(file, _)| *file) != Some(location.file) {
+      let path = BlockFiles::path(&blocks_dir, location.file);
+      self.open = Some((
+        location.file,
+        File::open(&path).with_context(|| format!("failed to open `{}`", path.display()))?,
+      ));
+    }
+
+    let (_, file) = self.open.as_mut().unwrap();
+
+    file.seek(SeekFrom::Start(location.offset))?;
+
+    let mut buffer = vec![0; usize::try_from(location.size).unwrap()];
+    file.read_exact(&mut buffer)?;
+
+    let mut block = Block::consensus_decode(&mut Cursor::new(buffer))?;
+
+    if !(self.index_sats || height >= self.first_inscription_height) {
+      block.txdata.clear();
+    }
+
+    Ok(Some(block))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  fn write_block_file(path: &Path, blocks: &[&Block]) {
+    let mut bytes = Vec::new();
+
+    for block in blocks {
+      let block = consensus::serialize(*block);
+      bytes.extend_from_slice(&Chain::Regtest.block_file_magic());
+      bytes.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+      bytes.extend_from_slice(&block);
+    }
+
+    bytes.extend_from_slice(&[0; 64]);
+
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, bytes).unwrap();
+  }
+
+  fn genesis_block(context: &Context) -> Block {
+    let client = &context.index.client;
+    client
+      .get_block(&client.get_block_hash(0).unwrap())
+      .unwrap()
+  }
+
+  fn file_block_source(context: &Context, blocks_dir: &Path, tip: u32) -> FileBlockSource {
+    FileBlockSource::new(
+      &context.index,
+      Arc::new(Mutex::new(BlockFiles::new(
+        blocks_dir.into(),
+        Chain::Regtest.block_file_magic(),
+      ))),
+      tip,
+      Box::new(RpcBlockSource::new(&context.index).unwrap()),
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn blocks_are_read_in_chain_order() {
+    let context = Context::builder().build();
+    let genesis = genesis_block(&context);
+    let blocks = context.rpc_server.mine_blocks(3);
+
+    let mut stale = blocks[2].clone();
+    stale.header.nonce += 1;
+
+    let blocks_dir = context.tempdir.path().join("blocks");
+
+    write_block_file(
+      &BlockFiles::path(&blocks_dir, 0),
+      &[&genesis, &blocks[1], &stale],
+    );
+    write_block_file(&BlockFiles::path(&blocks_dir, 1), &[&blocks[0], &blocks[2]]);
+
+    let mut source = file_block_source(&context, &blocks_dir, 3);
+
+    context.rpc_server.fail_get_block(true);
+
+    assert_eq!(source.get_block(0).unwrap().unwrap(), genesis);
+    assert_eq!(source.get_block(1).unwrap().unwrap(), blocks[0]);
+    assert_eq!(source.get_block(2).unwrap().unwrap(), blocks[1]);
+    assert_eq!(source.get_block(3).unwrap().unwrap(), blocks[2]);
+  }
+
+  #[test]
+  fn blocks_past_tip_or_missing_from_block_files_are_fetched_over_rpc() {
+    let context = Context::builder().build();
+    let blocks = context.rpc_server.mine_blocks(3);
+
+    let blocks_dir = context.tempdir.path().join("blocks");
+
+    write_block_file(&BlockFiles::path(&blocks_dir, 0), &[&blocks[0], &blocks[2]]);
+
+    let mut source = file_block_source(&context, &blocks_dir, 2);
+
+    assert_eq!(
+      source.get_block(0).unwrap().unwrap(),
+      genesis_block(&context)
+    );
+    assert_eq!(source.get_block(2).unwrap().unwrap(), blocks[1]);
+    assert_eq!(source.get_block(3).unwrap().unwrap(), blocks[2]);
+    assert_eq!(source.get_block(4).unwrap(), None);
+
+    context.rpc_server.fail_get_block(true);
+
+    assert_eq!(source.get_block(1).unwrap().unwrap(), blocks[0]);
+    assert!(source.get_block(3).is_err());
+  }
+
+  #[test]
+  fn scan_remembers_a_bounded_window_of_blocks() {
+    let context = Context::builder().build();
+    let blocks = context.rpc_server.mine_blocks(2);
+
+    let blocks_dir = context.tempdir.path().join("blocks");
+
+    write_block_file(&BlockFiles::path(&blocks_dir, 0), &[&blocks[0], &blocks[1]]);
+
+    let mut files = BlockFiles::new(blocks_dir.clone(), Chain::Regtest.block_file_magic());
+
+    for i in 0..BlockFiles::WINDOW {
+      files.remember(
+        BlockHash::hash(&i.to_le_bytes()),
+        Location {
+          file: 0,
+          offset: 0,
+          size: 0,
+        },
+      );
+    }
+
+    assert_eq!(files.locations.len(), BlockFiles::WINDOW);
+
+    assert!(files.locate(blocks[1].block_hash()).unwrap().is_some());
+    assert_eq!(files.locations.len(), BlockFiles::WINDOW);
+    assert_eq!(files.order.len(), BlockFiles::WINDOW);
+
+    assert!(files.locate(BlockHash::all_zeros()).unwrap().is_none());
+    assert!(!files.scan().unwrap());
+
+    write_block_file(&BlockFiles::path(&blocks_dir, 1), &[&blocks[0]]);
+
+    assert!(files.scan().unwrap());
+  }
+
+  #[test]
+  fn initial_sync_reads_block_files() {
+    let tempdir = TempDir::new().unwrap();
+
+    let context = Context::builder()
+      .arg("--read-block-files")
+      // stop before the blocks within `TIP_DISTANCE` of the tip, which are
+      // always fetched over RPC
+      .arg("--height-limit=11")
+      .arg(format!("--dogecoin-data-dir={}", tempdir.path().display()))
+      .build();
+
+    let genesis = genesis_block(&context);
+    let blocks = context.rpc_server.mine_blocks(110);
+
+    write_block_file(
+      &BlockFiles::path(&context.options.dogecoin_blocks_dir().unwrap(), 0),
+      &std::iter::once(&genesis)
+        .chain(&blocks)
+        .collect::<Vec<&Block>>(),
+    );
+
+    context.rpc_server.fail_get_block(true);
+
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 11);
+    assert_eq!(
+      context.index.block_hash(Some(10)).unwrap(),
+      Some(blocks[9].block_hash())
+    );
+  }
+}
@@ -11,6 +11,223 @@ pub(super) trait Entry: Sized {
   fn store(self) -> Self::Value;
 }
 
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Direction {
+  Received,
+  Sent,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub(crate) struct AddressActivity {
+  pub(crate) height: u32,
+  pub(crate) txid: Txid,
+  pub(crate) direction: Direction,
+  pub(crate) value: u64,
+}
+
+// Height is stored big-endian first, so an address's activity sorts by height.
+pub(super) type AddressActivityValue = [u8; 45];
+
+impl Entry for AddressActivity {
+  type Value = AddressActivityValue;
+
+  fn load(value: Self::Value) -> Self {
+    Self {
+      height: u32::from_be_bytes(value[0..4].try_into().unwrap()),
+      txid: Txid::from_inner(value[4..36].try_into().unwrap()),
+      direction: if value[36] == 0 {
+        Direction::Received
+      } else {
+        Direction::Sent
+      },
+      value: u64::from_be_bytes(value[37..45].try_into().unwrap()),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = [0; 45];
+    value[0..4].copy_from_slice(&self.height.to_be_bytes());
+    value[4..36].copy_from_slice(self.txid.as_inner());
+    value[36] = match self.direction {
+      Direction::Received => 0,
+      Direction::Sent => 1,
+    };
+    value[37..45].copy_from_slice(&self.value.to_be_bytes());
+    value
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DuneEventKind {
+  Etch,
+  Mint,
+  Transfer,
+  Burn,
+  CenotaphBurn,
+}
+
+impl Display for DuneEventKind {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Etch => write!(f, "etch"),
+      Self::Mint => write!(f, "mint"),
+      Self::Transfer => write!(f, "transfer"),
+      Self::Burn => write!(f, "burn"),
+      Self::CenotaphBurn => write!(f, "cenotaph burn"),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub(crate) struct DuneEvent {
+  pub(crate) height: u32,
+  pub(crate) index: u32,
+  pub(crate) kind: DuneEventKind,
+  pub(crate) output: Option<u32>,
+  pub(crate) txid: Txid,
+  pub(crate) amount: u128,
+}
+
+// Height and transaction index are stored big-endian first, so a dune's events
+// sort in the order they happened.
+pub(super) type DuneEventValue = [u8; 61];
+
+impl Entry for DuneEvent {
+  type Value = DuneEventValue;
+
+  fn load(value: Self::Value) -> Self {
+    let output = u32::from_be_bytes(value[9..13].try_into().unwrap());
+
+    Self {
+      height: u32::from_be_bytes(value[0..4].try_into().unwrap()),
+      index: u32::from_be_bytes(value[4..8].try_into().unwrap()),
+      kind: match value[8] {
+        0 => DuneEventKind::Etch,
+        1 => DuneEventKind::Mint,
+        2 => DuneEventKind::Transfer,
+        3 => DuneEventKind::Burn,
+        _ => DuneEventKind::CenotaphBurn,
+      },
+      output: (output != u32::MAX).then_some(output),
+      txid: Txid::from_inner(value[13..45].try_into().unwrap()),
+      amount: u128::from_be_bytes(value[45..61].try_into().unwrap()),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = [0; 61];
+    value[0..4].copy_from_slice(&self.height.to_be_bytes());
+    value[4..8].copy_from_slice(&self.index.to_be_bytes());
+    value[8] = match self.kind {
+      DuneEventKind::Etch => 0,
+      DuneEventKind::Mint => 1,
+      DuneEventKind::Transfer => 2,
+      DuneEventKind::Burn => 3,
+      DuneEventKind::CenotaphBurn => 4,
+    };
+    value[9..13].copy_from_slice(&self.output.unwrap_or(u32::MAX).to_be_bytes());
+    value[13..45].copy_from_slice(self.txid.as_inner());
+    value[45..61].copy_from_slice(&self.amount.to_be_bytes());
+    value
+  }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct InscriptionTransfer {
+  pub(crate) height: u32,
+  pub(crate) sequence: u32,
+  pub(crate) txid: Txid,
+  pub(crate) old_satpoint: Option<SatPoint>,
+  pub(crate) new_satpoint: SatPoint,
+  pub(crate) owner: Option<String>,
+}
+
+// Height and the order within the block are stored big-endian first, so an
+// inscription's transfers sort in the order they happened. A missing old
+// satpoint marks the transfer that created the inscription.
+pub(super) type InscriptionTransferValue = [u8; 162];
+
+impl Entry for InscriptionTransfer {
+  type Value = InscriptionTransferValue;
+
+  fn load(value: Self::Value) -> Self {
+    let old_satpoint: SatPointValue = value[40..84].try_into().unwrap();
+
+    Self {
+      height: u32::from_be_bytes(value[0..4].try_into().unwrap()),
+      sequence: u32::from_be_bytes(value[4..8].try_into().unwrap()),
+      txid: Txid::from_inner(value[8..40].try_into().unwrap()),
+      old_satpoint: (old_satpoint != [u8::MAX; 44]).then(|| SatPoint::load(old_satpoint)),
+      new_satpoint: SatPoint::load(value[84..128].try_into().unwrap()),
+      owner: (value[128..162] != [0; 34]).then(|| {
+        String::from_utf8_lossy(&value[128..162])
+          .trim_end_matches('\0')
+          .into()
+      }),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = [0; 162];
+    value[0..4].copy_from_slice(&self.height.to_be_bytes());
+    value[4..8].copy_from_slice(&self.sequence.to_be_bytes());
+    value[8..40].copy_from_slice(self.txid.as_inner());
+    value[40..84].copy_from_slice(
+      &self
+        .old_satpoint
+        .map(SatPoint::store)
+        .unwrap_or([u8::MAX; 44]),
+    );
+    value[84..128].copy_from_slice(&self.new_satpoint.store());
+    if let Some(owner) = self.owner {
+      let owner = &owner.as_bytes()[..owner.len().min(34)];
+      value[128..128 + owner.len()].copy_from_slice(owner);
+    }
+    value
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub(crate) struct ReorgEntry {
+  pub(crate) height: u32,
+  pub(crate) depth: Option<u32>,
+  pub(crate) old_block_hash: BlockHash,
+  pub(crate) new_block_hash: BlockHash,
+  pub(crate) recovered: bool,
+}
+
+// The hashes are the index's and the node's block below `height`. Reorgs too
+// deep to measure store a depth of `u32::MAX`.
+pub(super) type ReorgEntryValue = [u8; 73];
+
+impl Entry for ReorgEntry {
+  type Value = ReorgEntryValue;
+
+  fn load(value: Self::Value) -> Self {
+    let depth = u32::from_be_bytes(value[4..8].try_into().unwrap());
+
+    Self {
+      height: u32::from_be_bytes(value[0..4].try_into().unwrap()),
+      depth: (depth != u32::MAX).then_some(depth),
+      old_block_hash: BlockHash::from_inner(value[8..40].try_into().unwrap()),
+      new_block_hash: BlockHash::from_inner(value[40..72].try_into().unwrap()),
+      recovered: value[72] != 0,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = [0; 73];
+    value[0..4].copy_from_slice(&self.height.to_be_bytes());
+    value[4..8].copy_from_slice(&self.depth.unwrap_or(u32::MAX).to_be_bytes());
+    value[8..40].copy_from_slice(self.old_block_hash.as_inner());
+    value[40..72].copy_from_slice(self.new_block_hash.as_inner());
+    value[72] = self.recovered.into();
+    value
+  }
+}
+
 pub(super) type BlockHashValue = [u8; 32];
 
 impl Entry for BlockHash {
@@ -67,12 +284,12 @@ pub(super) type DuneEntryValue = (
   u64,                      // number
   (u128, u32),              // dune + spacers
   (u128, u128),             // supply + premine
-  u32,                      // symbol
+  Option<u32>,              // symbol
   u64,                      // timestamp
   bool                      // turbo
 );
 
-type TermsEntryValue = (
+pub(super) type TermsEntryValue = (
   Option<u128>,               // cap
   Option<u128>,               // limit
   (Option<u64>, Option<u64>), // height
@@ -240,7 +457,7 @@ impl Entry for DuneEntry {
       dune: Dune(dune),
       spacers,
       supply,
-      symbol: char::from_u32(symbol),
+      symbol: symbol.and_then(char::from_u32),
       timestamp,
       turbo,
     }
@@ -284,7 +501,7 @@ impl Entry for DuneEntry {
       self.number,
       (self.dune.0, self.spacers),
       (self.supply, self.premine),
-      self.symbol.map(u32::from).unwrap_or(u32::MAX),
+      self.symbol.map(u32::from),
       self.timestamp,
       self.turbo
     )
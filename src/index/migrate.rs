@@ -0,0 +1,922 @@
+use {super::entry::TermsEntryValue, super::*};
+
+// Each migration upgrades an index from schema `from` to schema `from + 1`.
+// Migrations are listed in order and end at `SCHEMA_VERSION`, so an index at
+// any schema from `MIGRATIONS[0].from` on can be brought up to date in place.
+pub(super) struct Migration {
+  pub(super) from: u64,
+  pub(super) description: &'static str,
+  run: fn(&WriteTransaction, Chain) -> Result<u64>,
+}
+
+pub(super) const MIGRATIONS: &[Migration] = &[
+  Migration {
+    from: 6,
+    description: "create ADDRESS_TO_ACTIVITY and record that address activity is not indexed",
+    run: add_address_activity_table,
+  },
+  Migration {
+    from: 7,
+    description: "create BIT20_TXID_TO_RECEIPTS and BIT20_INSCRIPTION_ID_TO_RECEIPTS; dev-20 receipts before the migration are not recorded",
+    run: add_receipt_tables,
+  },
+  Migration {
+    from: 8,
+    description: "create OUTPOINT_TO_DUNE_HOLDER, DUNE_HOLDER_TO_BALANCE and DUNE_ID_TO_HOLDER_COUNT and credit existing dune outputs to their holders",
+    run: add_dune_holder_tables,
+  },
+  Migration {
+    from: 9,
+    description: "create DUNE_ID_TO_EVENT; events before the migration are not recorded",
+    run: add_dune_event_table,
+  },
+  Migration {
+    from: 10,
+    description: "create INSCRIPTION_ID_TO_PARENT and INSCRIPTION_ID_TO_CHILDREN; parents of inscriptions revealed before the migration are not recorded",
+    run: add_parent_tables,
+  },
+  Migration {
+    from: 11,
+    description: "create METAPROTOCOL_TO_INSCRIPTION_NUMBER and record the metaprotocols of existing inscriptions",
+    run: add_metaprotocol_table,
+  },
+  Migration {
+    from: 12,
+    description:
+      "create INSCRIPTION_ID_TO_TRANSFER; transfers before the migration are not recorded",
+    run: add_transfer_table,
+  },
+  Migration {
+    from: 13,
+    description: "create SHA256_TO_INSCRIPTION_NUMBER and hash existing inscription bodies",
+    run: add_sha256_table,
+  },
+  Migration {
+    from: 14,
+    description: "create TERM_TO_INSCRIPTION_NUMBER and record that text is not indexed",
+    run: add_term_table,
+  },
+  Migration {
+    from: 15,
+    description: "create REORG_NUMBER_TO_REORG_ENTRY",
+    run: add_reorg_table,
+  },
+  Migration {
+    from: 16,
+    description: "create DUNE_BALANCE_TO_HOLDER and rank existing dune holders",
+    run: add_dune_balance_table,
+  },
+  Migration {
+    from: 17,
+    description: "re-encode DUNE_ID_TO_DUNE_ENTRY with optional symbols",
+    run: reencode_dune_entries,
+  },
+];
+
+// `DUNE_ID_TO_DUNE_ENTRY` values before schema 18, which stored a missing
+// symbol as `u32::MAX`.
+type LegacyDuneEntryValue = (
+  u64,                     // block
+  u128,                    // burned
+  u8,                      // divisibility
+  (u128, u128),            // etching
+  Option<TermsEntryValue>, // terms parameters
+  u128,                    // mints
+  u64,                     // number
+  (u128, u32),             // dune + spacers
+  (u128, u128),            // supply + premine
+  u32,                     // symbol
+  u64,                     // timestamp
+  bool,                    // turbo
+);
+
+const LEGACY_DUNE_ID_TO_DUNE_ENTRY: TableDefinition<DuneIdValue, LegacyDuneEntryValue> =
+  TableDefinition::new("DUNE_ID_TO_DUNE_ENTRY");
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+  pub from: u64,
+  pub to: u64,
+  pub description: String,
+  pub rows: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+  pub dry_run: bool,
+  pub from: u64,
+  pub to: u64,
+  pub steps: Vec<Step>,
+}
+
+pub(super) fn can_migrate(schema_version: u64) -> bool {
+  MIGRATIONS
+    .first()
+    .map(|migration| migration.from <= schema_version && schema_version < SCHEMA_VERSION)
+    .unwrap_or_default()
+}
+
+fn schema_version(database: &Database) -> Result<u64> {
+  Ok(
+    database
+      .begin_read()?
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .map(|x| x.value())
+      .unwrap_or(0),
+  )
+}
+
+// Every migration commits in its own transaction together with the new schema
+// version, so an interrupted migration resumes from the last completed step.
+// A dry run performs each step and then aborts it, reporting how many rows it
+// would have written. Steps don't depend on each other's tables, so aborting
+// one doesn't change what the next reports.
+pub(crate) fn migrate(options: &Options, dry_run: bool) -> Result<Report> {
+  let path = options.index_path()?;
+
+  let database = unsafe { Database::builder().open(&path) }
+    .with_context(|| format!("failed to open index at `{}`", path.display()))?;
+
+  let from = schema_version(&database)?;
+
+  ensure!(
+    from <= SCHEMA_VERSION,
+    "index at `{}` was built with a newer version of ord: index schema {from}, ord schema {SCHEMA_VERSION}",
+    path.display()
+  );
+
+  ensure!(
+    from == SCHEMA_VERSION || can_migrate(from),
+    "index at `{}` is too old to migrate, consider deleting and rebuilding the index: index schema {from}, ord schema {SCHEMA_VERSION}",
+    path.display()
+  );
+
+  let mut steps = Vec::new();
+
+  for migration in MIGRATIONS.iter().filter(|migration| migration.from >= from) {
+    let wtx = database.begin_write()?;
+
+    let rows = (migration.run)(&wtx, options.chain())?;
+
+    if dry_run {
+      wtx.abort()?;
+    } else {
+      wtx
+        .open_table(STATISTIC_TO_COUNT)?
+        .insert(&Statistic::Schema.key(), &(migration.from + 1))?;
+      wtx.commit()?;
+    }
+
+    log::info!(
+      "Migrated index from schema {} to {}, writing {rows} rows",
+      migration.from,
+      migration.from + 1
+    );
+
+    steps.push(Step {
+      from: migration.from,
+      to: migration.from + 1,
+      description: migration.description.into(),
+      rows,
+    });
+  }
+
+  Ok(Report {
+    dry_run,
+    from,
+    to: SCHEMA_VERSION,
+    steps,
+  })
+}
+
+fn add_address_activity_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  wtx.open_multimap_table(ADDRESS_TO_ACTIVITY)?;
+  wtx
+    .open_table(STATISTIC_TO_COUNT)?
+    .insert(&Statistic::IndexAddresses.key(), &0)?;
+  Ok(1)
+}
+
+fn add_receipt_tables(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  wtx.open_table(BIT20_TXID_TO_RECEIPTS)?;
+  wtx.open_table(BIT20_INSCRIPTION_ID_TO_RECEIPTS)?;
+  Ok(0)
+}
+
+// Holders are keyed by script pubkey, which is recovered from the address
+// index. Dune outputs paying to scripts without an address aren't credited.
+fn add_dune_holder_tables(wtx: &WriteTransaction, chain: Chain) -> Result<u64> {
+  let address_to_outpoint = wtx.open_multimap_table(ADDRESS_TO_OUTPOINT)?;
+  let outpoint_to_balances = wtx.open_table(OUTPOINT_TO_DUNE_BALANCES)?;
+  let mut outpoint_to_holder = wtx.open_table(OUTPOINT_TO_DUNE_HOLDER)?;
+  let mut holder_to_balance = wtx.open_table(DUNE_HOLDER_TO_BALANCE)?;
+  let mut id_to_holder_count = wtx.open_table(DUNE_ID_TO_HOLDER_COUNT)?;
+
+  let mut balances = BTreeMap::<(u128, Vec<u8>), u128>::new();
+
+  for result in address_to_outpoint.iter()? {
+    let (address, outpoints) = result?;
+
+    let Some(holder) = std::str::from_utf8(address.value())
+      .ok()
+      .and_then(|address| chain.address_from_str(address).ok())
+      .map(|address| address.script_pubkey())
+    else {
+      continue;
+    };
+
+    for outpoint in outpoints {
+      let outpoint = outpoint?;
+
+      let Some(buffer) = outpoint_to_balances.get(outpoint.value())? else {
+        continue;
+      };
+
+      let buffer = buffer.value();
+      let mut i = 0;
+      while i < buffer.len() {
+        let (id, length) = dunes::varint::decode(&buffer[i..]);
+        i += length;
+        let (amount, length) = dunes::varint::decode(&buffer[i..]);
+        i += length;
+
+        *balances
+          .entry((id, holder.as_bytes().to_vec()))
+          .or_default() += amount;
+      }
+
+      outpoint_to_holder.insert(outpoint.value(), holder.as_bytes())?;
+    }
+  }
+
+  let mut holder_counts = BTreeMap::<u128, u64>::new();
+
+  for ((id, holder), balance) in &balances {
+    let mut key = id.to_be_bytes().to_vec();
+    key.extend_from_slice(holder);
+    holder_to_balance.insert(key.as_slice(), balance)?;
+    *holder_counts.entry(*id).or_default() += 1;
+  }
+
+  for (id, count) in holder_counts {
+    id_to_holder_count.insert(DuneId::try_from(id)?.store(), count)?;
+  }
+
+  Ok(balances.len().try_into()?)
+}
+
+fn add_dune_event_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  wtx.open_multimap_table(DUNE_ID_TO_EVENT)?;
+  Ok(0)
+}
+
+fn add_parent_tables(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  wtx.open_table(INSCRIPTION_ID_TO_PARENT)?;
+  wtx.open_multimap_table(INSCRIPTION_ID_TO_CHILDREN)?;
+  Ok(0)
+}
+
+fn add_metaprotocol_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  let mut metaprotocol_to_number = wtx.open_multimap_table(METAPROTOCOL_TO_INSCRIPTION_NUMBER)?;
+
+  let mut rows = 0;
+
+  for_each_inscription(wtx, |number, inscription| {
+    if let Some(metaprotocol) = inscription.metaprotocol() {
+      metaprotocol_to_number.insert(metaprotocol, &number)?;
+      rows += 1;
+    }
+    Ok(())
+  })?;
+
+  Ok(rows)
+}
+
+fn add_transfer_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  wtx.open_multimap_table(INSCRIPTION_ID_TO_TRANSFER)?;
+  Ok(0)
+}
+
+fn add_sha256_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  let mut sha256_to_number = wtx.open_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)?;
+
+  let mut rows = 0;
+
+  for_each_inscription(wtx, |number, inscription| {
+    if let Some(sha256) = inscription.content_sha256() {
+      sha256_to_number.insert(&sha256.into_inner(), &number)?;
+      rows += 1;
+    }
+    Ok(())
+  })?;
+
+  Ok(rows)
+}
+
+// Reassembles every inscription from its stored reveal transactions.
+fn for_each_inscription(
+  wtx: &WriteTransaction,
+  mut f: impl FnMut(u64, Inscription) -> Result,
+) -> Result {
+  let number_to_id = wtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+  let id_to_txids = wtx.open_table(INSCRIPTION_ID_TO_TXIDS)?;
+  let txid_to_tx = wtx.open_table(INSCRIPTION_TXID_TO_TX)?;
+
+  for result in number_to_id.iter()? {
+    let (number, id) = result?;
+
+    let Some(txids) = id_to_txids.get(id.value())? else {
+      continue;
+    };
+
+    let mut txs = Vec::new();
+
+    for txid in txids.value().chunks(32) {
+      let tx = txid_to_tx
+        .get(txid)?
+        .ok_or_else(|| anyhow!("index is missing inscription transaction"))?;
+      txs.push(bitcoin::Transaction::consensus_decode(&mut Cursor::new(
+        tx.value(),
+      ))?);
+    }
+
+    if let ParsedInscription::Complete(inscription) = Inscription::from_transactions(txs) {
+      f(number.value(), inscription)?;
+    }
+  }
+
+  Ok(())
+}
+
+fn add_term_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  wtx.open_multimap_table(TERM_TO_INSCRIPTION_NUMBER)?;
+  wtx
+    .open_table(STATISTIC_TO_COUNT)?
+    .insert(&Statistic::IndexText.key(), &0)?;
+  Ok(1)
+}
+
+fn add_reorg_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  wtx.open_table(REORG_NUMBER_TO_REORG_ENTRY)?;
+  Ok(0)
+}
+
+fn add_dune_balance_table(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  let holder_to_balance = wtx.open_table(DUNE_HOLDER_TO_BALANCE)?;
+  let mut balance_to_holder = wtx.open_table(DUNE_BALANCE_TO_HOLDER)?;
+
+  let mut rows = 0;
+
+  for result in holder_to_balance.iter()? {
+    let (key, balance) = result?;
+    let (id, holder) = key.value().split_at(16);
+
+    balance_to_holder.insert(
+      Index::ranked_dune_holder_key(
+        u128::from_be_bytes(id.try_into().unwrap()),
+        balance.value(),
+        holder,
+      )
+      .as_slice(),
+      balance.value(),
+    )?;
+
+    rows += 1;
+  }
+
+  Ok(rows)
+}
+
+fn reencode_dune_entries(wtx: &WriteTransaction, _: Chain) -> Result<u64> {
+  let mut entries = Vec::new();
+
+  for result in wtx.open_table(LEGACY_DUNE_ID_TO_DUNE_ENTRY)?.iter()? {
+    let (id, entry) = result?;
+    entries.push((id.value(), entry.value()));
+  }
+
+  wtx.delete_table(LEGACY_DUNE_ID_TO_DUNE_ENTRY)?;
+
+  let mut id_to_entry = wtx.open_table(DUNE_ID_TO_DUNE_ENTRY)?;
+
+  for (
+    id,
+    (
+      block,
+      burned,
+      divisibility,
+      etching,
+      terms,
+      mints,
+      number,
+      dune,
+      supply,
+      symbol,
+      timestamp,
+      turbo,
+    ),
+  ) in &entries
+  {
+    id_to_entry.insert(
+      id,
+      (
+        *block,
+        *burned,
+        *divisibility,
+        *etching,
+        *terms,
+        *mints,
+        *number,
+        *dune,
+        *supply,
+        char::from_u32(*symbol).map(u32::from),
+        *timestamp,
+        *turbo,
+      ),
+    )?;
+  }
+
+  Ok(entries.len().try_into()?)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  // The migration opens the database itself, so only the context's options
+  // and data dir outlive the downgrade.
+  fn downgrade(context: Context, schema_version: u64) -> (Options, TempDir) {
+    let wtx = context.index.database.begin_write().unwrap();
+
+    if schema_version < 18 {
+      let mut entries = Vec::new();
+
+      for result in wtx
+        .open_table(DUNE_ID_TO_DUNE_ENTRY)
+        .unwrap()
+        .iter()
+        .unwrap()
+      {
+        let (id, entry) = result.unwrap();
+        entries.push((id.value(), entry.value()));
+      }
+
+      wtx.delete_table(DUNE_ID_TO_DUNE_ENTRY).unwrap();
+
+      let mut legacy = wtx.open_table(LEGACY_DUNE_ID_TO_DUNE_ENTRY).unwrap();
+
+      for (
+        id,
+        (
+          block,
+          burned,
+          divisibility,
+          etching,
+          terms,
+          mints,
+          number,
+          dune,
+          supply,
+          symbol,
+          timestamp,
+          turbo,
+        ),
+      ) in entries
+      {
+        legacy
+          .insert(
+            id,
+            (
+              block,
+              burned,
+              divisibility,
+              etching,
+              terms,
+              mints,
+              number,
+              dune,
+              supply,
+              symbol.unwrap_or(u32::MAX),
+              timestamp,
+              turbo,
+            ),
+          )
+          .unwrap();
+      }
+    }
+
+    if schema_version < 17 {
+      wtx.delete_table(DUNE_BALANCE_TO_HOLDER).unwrap();
+    }
+
+    if schema_version < 16 {
+      wtx.delete_table(REORG_NUMBER_TO_REORG_ENTRY).unwrap();
+    }
+
+    if schema_version < 15 {
+      wtx
+        .delete_multimap_table(TERM_TO_INSCRIPTION_NUMBER)
+        .unwrap();
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .remove(&Statistic::IndexText.key())
+        .unwrap();
+    }
+
+    if schema_version < 14 {
+      wtx
+        .delete_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)
+        .unwrap();
+    }
+
+    if schema_version < 13 {
+      wtx
+        .delete_multimap_table(INSCRIPTION_ID_TO_TRANSFER)
+        .unwrap();
+    }
+
+    if schema_version < 12 {
+      wtx
+        .delete_multimap_table(METAPROTOCOL_TO_INSCRIPTION_NUMBER)
+        .unwrap();
+    }
+
+    if schema_version < 11 {
+      wtx.delete_table(INSCRIPTION_ID_TO_PARENT).unwrap();
+      wtx
+        .delete_multimap_table(INSCRIPTION_ID_TO_CHILDREN)
+        .unwrap();
+    }
+
+    if schema_version < 10 {
+      wtx.delete_multimap_table(DUNE_ID_TO_EVENT).unwrap();
+    }
+
+    if schema_version < 9 {
+      wtx.delete_table(OUTPOINT_TO_DUNE_HOLDER).unwrap();
+      wtx.delete_table(DUNE_HOLDER_TO_BALANCE).unwrap();
+      wtx.delete_table(DUNE_ID_TO_HOLDER_COUNT).unwrap();
+    }
+
+    if schema_version < 8 {
+      wtx.delete_table(BIT20_TXID_TO_RECEIPTS).unwrap();
+      wtx.delete_table(BIT20_INSCRIPTION_ID_TO_RECEIPTS).unwrap();
+    }
+
+    if schema_version < 7 {
+      wtx.delete_multimap_table(ADDRESS_TO_ACTIVITY).unwrap();
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .remove(&Statistic::IndexAddresses.key())
+        .unwrap();
+    }
+
+    wtx
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .insert(&Statistic::Schema.key(), &schema_version)
+      .unwrap();
+
+    wtx.commit().unwrap();
+
+    (context.options, context.tempdir)
+  }
+
+  #[test]
+  fn migrations_are_contiguous_and_end_at_schema_version() {
+    for pair in MIGRATIONS.windows(2) {
+      assert_eq!(pair[0].from + 1, pair[1].from);
+    }
+    assert_eq!(MIGRATIONS.last().unwrap().from + 1, SCHEMA_VERSION);
+  }
+
+  #[test]
+  fn can_migrate_only_supported_schemas() {
+    assert!(!can_migrate(0));
+    assert!(!can_migrate(5));
+    assert!(can_migrate(6));
+    assert!(can_migrate(SCHEMA_VERSION - 1));
+    assert!(!can_migrate(SCHEMA_VERSION));
+  }
+
+  #[test]
+  fn migrated_index_opens() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    let (options, _tempdir) = downgrade(context, 14);
+
+    let report = migrate(&options, false).unwrap();
+
+    assert_eq!(report.from, 14);
+    assert_eq!(report.to, SCHEMA_VERSION);
+    assert_eq!(
+      report.steps,
+      [
+        Step {
+          from: 14,
+          to: 15,
+          description: MIGRATIONS[8].description.into(),
+          rows: 1,
+        },
+        Step {
+          from: 15,
+          to: 16,
+          description: MIGRATIONS[9].description.into(),
+          rows: 0,
+        },
+        Step {
+          from: 16,
+          to: 17,
+          description: MIGRATIONS[10].description.into(),
+          rows: 0,
+        },
+        Step {
+          from: 17,
+          to: 18,
+          description: MIGRATIONS[11].description.into(),
+          rows: 0,
+        },
+      ]
+    );
+
+    let index = Index::open(&options).unwrap();
+    assert!(!index.has_text_index());
+    assert_eq!(index.block_count().unwrap(), 2);
+  }
+
+  #[test]
+  fn open_suggests_migration() {
+    let (options, _tempdir) = downgrade(Context::builder().build(), 13);
+
+    assert!(Index::open(&options)
+      .err()
+      .unwrap()
+      .to_string()
+      .contains("run `ord index migrate` to upgrade it in place"));
+  }
+
+  #[test]
+  fn dry_run_changes_nothing() {
+    let (options, _tempdir) = downgrade(Context::builder().build(), 13);
+
+    let report = migrate(&options, true).unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(report.from, 13);
+    assert_eq!(report.steps.len(), 5);
+
+    assert_eq!(migrate(&options, true).unwrap(), report);
+
+    assert_eq!(migrate(&options, false).unwrap().steps.len(), 5);
+    assert!(migrate(&options, false).unwrap().steps.is_empty());
+  }
+
+  #[test]
+  fn old_index_is_not_migrated() {
+    let (options, _tempdir) = downgrade(Context::builder().build(), 5);
+
+    assert!(migrate(&options, false)
+      .unwrap_err()
+      .to_string()
+      .contains("is too old to migrate"));
+  }
+
+  #[test]
+  fn sha256_migration_hashes_existing_inscriptions() {
+    let context = Context::builder().build();
+
+    let tx = Transaction {
+      version: 1,
+      lock_time: bitcoin::PackedLockTime::ZERO,
+      input: vec![TxIn {
+        script_sig: script::Builder::new()
+          .push_slice(b"ord")
+          .push_int(1)
+          .push_slice(b"text/plain")
+          .push_int(0)
+          .push_slice(b"hello")
+          .into_script(),
+        ..Default::default()
+      }],
+      output: Vec::new(),
+    };
+
+    let inscription_id = InscriptionId::from(tx.txid());
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+
+      let mut tx_buf = Vec::new();
+      tx.consensus_encode(&mut tx_buf).unwrap();
+
+      wtx
+        .open_table(INSCRIPTION_TXID_TO_TX)
+        .unwrap()
+        .insert(tx.txid().into_inner().as_slice(), tx_buf.as_slice())
+        .unwrap();
+      wtx
+        .open_table(INSCRIPTION_ID_TO_TXIDS)
+        .unwrap()
+        .insert(&inscription_id.store(), tx.txid().into_inner().as_slice())
+        .unwrap();
+      wtx
+        .open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)
+        .unwrap()
+        .insert(&0, &inscription_id.store())
+        .unwrap();
+
+      wtx.commit().unwrap();
+    }
+
+    let (options, _tempdir) = downgrade(context, 13);
+
+    assert_eq!(migrate(&options, false).unwrap().steps[0].rows, 1);
+
+    assert_eq!(
+      Index::open(&options)
+        .unwrap()
+        .get_inscriptions_by_sha256(sha256::Hash::hash(b"hello"))
+        .unwrap(),
+      [inscription_id]
+    );
+  }
+
+  #[test]
+  fn dune_balance_migration_ranks_existing_holders() {
+    let context = Context::builder().build();
+
+    let id = DuneId {
+      height: 2,
+      index: 1,
+    };
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      let mut holder_to_balance = wtx.open_table(DUNE_HOLDER_TO_BALANCE).unwrap();
+
+      for (holder, balance) in [(b"a", 1), (b"b", 3), (b"c", 2)] {
+        let mut key = u128::from(id).to_be_bytes().to_vec();
+        key.extend_from_slice(holder);
+        holder_to_balance.insert(key.as_slice(), balance).unwrap();
+      }
+
+      drop(holder_to_balance);
+      wtx.commit().unwrap();
+    }
+
+    let (options, _tempdir) = downgrade(context, 16);
+
+    assert_eq!(migrate(&options, false).unwrap().steps[0].rows, 3);
+
+    assert_eq!(
+      Index::open(&options)
+        .unwrap()
+        .get_dune_holders(id, 0, 2)
+        .unwrap(),
+      (
+        vec![
+          (Script::from(b"b".to_vec()), 3),
+          (Script::from(b"c".to_vec()), 2),
+        ],
+        true
+      )
+    );
+  }
+
+  #[test]
+  fn released_index_migrates() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    let (options, _tempdir) = downgrade(context, 6);
+
+    let report = migrate(&options, false).unwrap();
+
+    assert_eq!(report.from, 6);
+    assert_eq!(
+      report
+        .steps
+        .iter()
+        .map(|step| step.from)
+        .collect::<Vec<u64>>(),
+      (6..SCHEMA_VERSION).collect::<Vec<u64>>()
+    );
+
+    let index = Index::open(&options).unwrap();
+    assert!(!index.has_address_index());
+    assert_eq!(index.block_count().unwrap(), 2);
+  }
+
+  #[test]
+  fn dune_holder_migration_credits_existing_outputs() {
+    let context = Context::builder().build();
+
+    let address = context
+      .options
+      .chain()
+      .address_from_str("mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth")
+      .unwrap();
+
+    let a = DuneId {
+      height: 2,
+      index: 1,
+    };
+
+    let b = DuneId {
+      height: 3,
+      index: 1,
+    };
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      let mut address_to_outpoint = wtx.open_multimap_table(ADDRESS_TO_OUTPOINT).unwrap();
+      let mut outpoint_to_balances = wtx.open_table(OUTPOINT_TO_DUNE_BALANCES).unwrap();
+
+      for (vout, balances) in [(0, vec![(a, 1)]), (1, vec![(a, 2), (b, 5)])] {
+        let outpoint = OutPoint {
+          txid: Txid::all_zeros(),
+          vout,
+        };
+
+        let mut buffer = Vec::new();
+        for (id, amount) in balances {
+          dunes::varint::encode_to_vec(id.into(), &mut buffer);
+          dunes::varint::encode_to_vec(amount, &mut buffer);
+        }
+
+        address_to_outpoint
+          .insert(address.to_string().as_bytes(), &outpoint.store())
+          .unwrap();
+        outpoint_to_balances
+          .insert(&outpoint.store(), buffer.as_slice())
+          .unwrap();
+      }
+
+      drop(address_to_outpoint);
+      drop(outpoint_to_balances);
+      wtx.commit().unwrap();
+    }
+
+    let (options, _tempdir) = downgrade(context, 8);
+
+    assert_eq!(migrate(&options, false).unwrap().steps[0].rows, 2);
+
+    let index = Index::open(&options).unwrap();
+
+    assert_eq!(
+      index.get_dune_holders(a, 0, 10).unwrap(),
+      (vec![(address.script_pubkey(), 3)], false)
+    );
+    assert_eq!(
+      index.get_dune_holders(b, 0, 10).unwrap(),
+      (vec![(address.script_pubkey(), 5)], false)
+    );
+    assert_eq!(index.get_dune_holder_count(a).unwrap(), 1);
+    assert_eq!(index.get_dune_holder_count(b).unwrap(), 1);
+  }
+
+  #[test]
+  fn dune_entry_migration_decodes_missing_symbols() {
+    let context = Context::builder().build();
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      let mut id_to_entry = wtx.open_table(DUNE_ID_TO_DUNE_ENTRY).unwrap();
+
+      for (index, symbol) in [(1, Some('x')), (2, None)] {
+        id_to_entry
+          .insert(
+            DuneId { height: 2, index }.store(),
+            DuneEntry {
+              symbol,
+              ..Default::default()
+            }
+            .store(),
+          )
+          .unwrap();
+      }
+
+      drop(id_to_entry);
+      wtx.commit().unwrap();
+    }
+
+    let (options, _tempdir) = downgrade(context, 17);
+
+    assert_eq!(migrate(&options, false).unwrap().steps[0].rows, 2);
+
+    assert_eq!(
+      Index::open(&options)
+        .unwrap()
+        .dunes()
+        .unwrap()
+        .into_iter()
+        .map(|(_, entry)| entry.symbol)
+        .collect::<Vec<Option<char>>>(),
+      [Some('x'), None]
+    );
+  }
+}
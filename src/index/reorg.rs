@@ -2,27 +2,62 @@ use {super::*, updater::BlockData};
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum ReorgError {
-  Recoverable { height: u32, depth: u32 },
-  Unrecoverable,
+  Recoverable {
+    height: u32,
+    depth: u32,
+    old_block_hash: BlockHash,
+    new_block_hash: BlockHash,
+  },
+  Unrecoverable {
+    height: u32,
+    old_block_hash: BlockHash,
+    new_block_hash: BlockHash,
+  },
+}
+
+impl ReorgError {
+  pub(crate) fn entry(&self, recovered: bool) -> ReorgEntry {
+    match *self {
+      ReorgError::Recoverable {
+        height,
+        depth,
+        old_block_hash,
+        new_block_hash,
+      } => ReorgEntry {
+        height,
+        depth: Some(depth),
+        old_block_hash,
+        new_block_hash,
+        recovered,
+      },
+      ReorgError::Unrecoverable {
+        height,
+        old_block_hash,
+        new_block_hash,
+      } => ReorgEntry {
+        height,
+        depth: None,
+        old_block_hash,
+        new_block_hash,
+        recovered,
+      },
+    }
+  }
 }
 
 impl fmt::Display for ReorgError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
-      ReorgError::Recoverable { height, depth } => {
+      ReorgError::Recoverable { height, depth, .. } => {
         write!(f, "{depth} block deep reorg detected at height {height}")
       }
-      ReorgError::Unrecoverable => write!(f, "unrecoverable reorg detected"),
+      ReorgError::Unrecoverable { .. } => write!(f, "unrecoverable reorg detected"),
     }
   }
 }
 
 impl std::error::Error for ReorgError {}
 
-const MAX_SAVEPOINTS: u32 = 5;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 25;
-
 pub(crate) struct Reorg {}
 
 impl Reorg {
@@ -33,7 +68,7 @@ impl Reorg {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
         let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
+          (index.max_savepoints - 1) * index.savepoint_interval + height % index.savepoint_interval;
 
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
@@ -43,11 +78,20 @@ impl Reorg {
             .into_option()?;
 
           if index_block_hash == bitcoind_block_hash {
-            return Err(anyhow!(ReorgError::Recoverable { height, depth }));
+            return Err(anyhow!(ReorgError::Recoverable {
+              height,
+              depth,
+              old_block_hash: index_prev_blockhash,
+              new_block_hash: bitcoind_prev_blockhash,
+            }));
           }
         }
 
-        Err(anyhow!(ReorgError::Unrecoverable))
+        Err(anyhow!(ReorgError::Unrecoverable {
+          height,
+          old_block_hash: index_prev_blockhash,
+          new_block_hash: bitcoind_prev_blockhash,
+        }))
       }
       _ => Ok(()),
     }
@@ -58,7 +102,8 @@ impl Reorg {
 
     let mut wtx = index.begin_write()?;
 
-    let oldest_savepoint = wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let oldest_savepoint =
+      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
 
     wtx.restore_savepoint(&oldest_savepoint)?;
 
@@ -73,22 +118,126 @@ impl Reorg {
     Ok(())
   }
 
+  // Detection only looks as deep as the savepoints are spaced for, but the
+  // savepoints that exist may still predate the fork. The newest one whose tip
+  // is on the node's chain is restored, and the index is only rebuilt from
+  // genesis if every savepoint is past the fork. Newer savepoints are from the
+  // abandoned chain and are dropped.
+  pub(crate) fn rollback_index(index: &Index, height: u32) -> Result {
+    let wtx = index.begin_write()?;
+    let mut savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+    wtx.abort()?;
+
+    savepoints.sort_unstable();
+
+    for (i, &savepoint) in savepoints.iter().enumerate().rev() {
+      let mut wtx = index.begin_write()?;
+
+      let persistent_savepoint = wtx.get_persistent_savepoint(savepoint)?;
+
+      wtx.restore_savepoint(&persistent_savepoint)?;
+
+      let tip = wtx
+        .open_table(HEIGHT_TO_BLOCK_HASH)?
+        .iter()?
+        .next_back()
+        .transpose()?
+        .map(|(tip_height, block_hash)| (tip_height.value(), BlockHash::load(*block_hash.value())));
+
+      let on_chain = match tip {
+        Some((tip_height, block_hash)) => {
+          index
+            .client
+            .get_block_hash(tip_height.into())
+            .into_option()?
+            == Some(block_hash)
+        }
+        None => true,
+      };
+
+      if !on_chain {
+        wtx.abort()?;
+        continue;
+      }
+
+      for &newer in &savepoints[i + 1..] {
+        wtx.delete_persistent_savepoint(newer)?;
+      }
+
+      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+      wtx.commit()?;
+
+      log::info!(
+        "rolled back database to height {} after unrecoverable reorg at height {height}",
+        index.block_count()?
+      );
+
+      return Ok(());
+    }
+
+    log::info!("rebuilding index from genesis after unrecoverable reorg at height {height}");
+
+    let wtx = index.begin_write()?;
+
+    for savepoint in wtx.list_persistent_savepoints()?.collect::<Vec<u64>>() {
+      wtx.delete_persistent_savepoint(savepoint)?;
+    }
+
+    for table in wtx.list_tables()?.collect::<Vec<UntypedTableHandle>>() {
+      if table.name() != STATISTIC_TO_COUNT.name()
+        && table.name() != REORG_NUMBER_TO_REORG_ENTRY.name()
+      {
+        wtx.delete_table(table)?;
+      }
+    }
+
+    for table in wtx
+      .list_multimap_tables()?
+      .collect::<Vec<UntypedMultimapTableHandle>>()
+    {
+      wtx.delete_multimap_table(table)?;
+    }
+
+    Index::create_tables(&wtx)?;
+
+    if index.index_sats {
+      wtx
+        .open_table(OUTPOINT_TO_SAT_RANGES)?
+        .insert(&OutPoint::null().store(), [].as_slice())?;
+    }
+
+    {
+      let mut statistics = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+      for statistic in [
+        Statistic::LostSats,
+        Statistic::OutputsTraversed,
+        Statistic::ReservedDunes,
+        Statistic::Dunes,
+        Statistic::SatRanges,
+      ] {
+        statistics.remove(&statistic.key())?;
+      }
+    }
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+
   pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
-      && u32::try_from(
-      index
-          .client
-          .get_block_count()?
-        )
+    if (height < index.savepoint_interval || height % index.savepoint_interval == 0)
+      && u32::try_from(index.client.get_block_count()?)
         .unwrap()
         .saturating_sub(height)
-      <= CHAIN_TIP_DISTANCE
+        <= index.savepoint_tip_distance
     {
       let wtx = index.begin_write()?;
 
       let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
 
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
+      if savepoints.len() >= usize::try_from(index.max_savepoints).unwrap() {
         wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
       }
 
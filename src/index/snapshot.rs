@@ -0,0 +1,334 @@
+use {
+  super::*,
+  bitcoin::hashes::HashEngine,
+  std::io::{BufRead, BufReader, BufWriter, Read, Write},
+};
+
+// A snapshot is this header as a line of JSON, followed by the raw bytes of the
+// index database.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Snapshot {
+  pub(crate) block_hash: BlockHash,
+  pub(crate) chain: Chain,
+  pub(crate) height: u32,
+  pub(crate) index_addresses: bool,
+  pub(crate) index_bit20: bool,
+  pub(crate) index_dunes: bool,
+  pub(crate) index_sats: bool,
+  pub(crate) index_text: bool,
+  pub(crate) index_transactions: bool,
+  pub(crate) schema_version: u64,
+  pub(crate) sha256: sha256::Hash,
+  pub(crate) size: u64,
+}
+
+impl Index {
+  pub(crate) fn export_snapshot(&self, height: u32, path: &Path) -> Result<Snapshot> {
+    let block_count = self.block_count()?;
+
+    ensure!(
+      block_count == height + 1,
+      "cannot export height {height} from an index with {block_count} blocks",
+    );
+
+    let block_hash = self
+      .block_hash(Some(height))?
+      .ok_or_else(|| anyhow!("index has no block hash at height {height}"))?;
+
+    // A durable commit flushes earlier non-durable commits, so the database
+    // file is complete before it is copied. Holding the database open keeps
+    // other processes from writing to it in the meantime.
+    self.database.begin_write()?.commit()?;
+
+    let mut engine = sha256::Hash::engine();
+    let size = io::copy(&mut File::open(&self.path)?, &mut engine)?;
+
+    let snapshot = Snapshot {
+      block_hash,
+      chain: self.chain,
+      height,
+      index_addresses: self.index_addresses,
+      index_bit20: self.index_bit20,
+      index_dunes: self.index_dunes,
+      index_sats: self.index_sats,
+      index_text: self.index_text,
+      index_transactions: self.index_transactions,
+      schema_version: SCHEMA_VERSION,
+      sha256: sha256::Hash::from_engine(engine),
+      size,
+    };
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut writer, &snapshot)?;
+    writer.write_all(b"\n")?;
+
+    let copied = io::copy(&mut File::open(&self.path)?, &mut writer)?;
+
+    ensure!(copied == size, "index changed size while being exported");
+
+    writer.flush()?;
+
+    Ok(snapshot)
+  }
+}
+
+pub(crate) fn import_snapshot(options: &Options, path: &Path) -> Result<Snapshot> {
+  let mut reader = BufReader::new(
+    File::open(path).with_context(|| format!("failed to open snapshot `{}`", path.display()))?,
+  );
+
+  let mut header = String::new();
+  reader.read_line(&mut header)?;
+
+  let snapshot = serde_json::from_str::<Snapshot>(&header)
+    .with_context(|| format!("`{}` does not start with a snapshot header", path.display()))?;
+
+  ensure!(
+    snapshot.schema_version == SCHEMA_VERSION,
+    "snapshot has index schema {}, but ord has schema {SCHEMA_VERSION}",
+    snapshot.schema_version,
+  );
+
+  ensure!(
+    snapshot.chain == options.chain(),
+    "snapshot is for {}, but ord is on {}",
+    snapshot.chain,
+    options.chain(),
+  );
+
+  // An index records its flags when it is created and ignores them after, so
+  // a mismatch would otherwise go unnoticed.
+  let with = |enabled: bool| if enabled { "with" } else { "without" };
+
+  for (flag, exported, enabled) in [
+    (
+      "--index-addresses",
+      snapshot.index_addresses,
+      options.index_addresses,
+    ),
+    ("--index-dunes", snapshot.index_dunes, options.index_dunes()),
+    ("--index-sats", snapshot.index_sats, options.index_sats),
+    ("--index-text", snapshot.index_text, options.index_text),
+    (
+      "--index-transactions",
+      snapshot.index_transactions,
+      options.index_transactions,
+    ),
+  ] {
+    ensure!(
+      exported == enabled,
+      "snapshot was exported {} `{flag}`, but ord is running {} it",
+      with(exported),
+      with(enabled),
+    );
+  }
+
+  let block_hash = options
+    .dogecoin_rpc_client()?
+    .get_block_hash(snapshot.height.into())?;
+
+  ensure!(
+    block_hash == snapshot.block_hash,
+    "snapshot block {} at height {} does not match node block {block_hash}",
+    snapshot.block_hash,
+    snapshot.height,
+  );
+
+  let index_path = options.index_path()?;
+
+  ensure!(
+    !index_path.exists(),
+    "index already exists at `{}`",
+    index_path.display()
+  );
+
+  if let Some(parent) = index_path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  let partial_path = index_path.with_extension("partial");
+
+  let mut engine = sha256::Hash::engine();
+  let mut size = 0;
+
+  {
+    let mut writer = BufWriter::new(File::create(&partial_path)?);
+    let mut buffer = vec![0; 1 << 20];
+
+    loop {
+      let n = reader.read(&mut buffer)?;
+
+      if n == 0 {
+        break;
+      }
+
+      engine.input(&buffer[..n]);
+      writer.write_all(&buffer[..n])?;
+      size += u64::try_from(n).unwrap();
+    }
+
+    writer.flush()?;
+  }
+
+  let sha256 = sha256::Hash::from_engine(engine);
+
+  if size != snapshot.size || sha256 != snapshot.sha256 {
+    fs::remove_file(&partial_path)?;
+    bail!(
+      "snapshot checksum mismatch: expected {} bytes with sha256 {}, got {size} bytes with sha256 {sha256}",
+      snapshot.size,
+      snapshot.sha256,
+    );
+  }
+
+  fs::rename(&partial_path, &index_path)?;
+
+  Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  fn import_options(context: &Context) -> Options {
+    Options {
+      index: Some(context.tempdir.path().join("imported.redb")),
+      ..context.options.clone()
+    }
+  }
+
+  #[test]
+  fn export_and_import_round_trip() {
+    let context = Context::builder().arg("--index-sats").build();
+    context.mine_blocks(2);
+
+    let path = context.tempdir.path().join("snapshot");
+
+    let exported = context.index.export_snapshot(2, &path).unwrap();
+
+    assert_eq!(exported.height, 2);
+    assert!(exported.index_sats);
+    assert_eq!(
+      Some(exported.block_hash),
+      context.index.block_hash(Some(2)).unwrap()
+    );
+
+    let options = import_options(&context);
+
+    assert_eq!(import_snapshot(&options, &path).unwrap(), exported);
+
+    let index = Index::open(&options).unwrap();
+    assert_eq!(index.block_count().unwrap(), 3);
+    assert!(index.has_sat_index());
+  }
+
+  #[test]
+  fn export_requires_index_at_height() {
+    let context = Context::builder().build();
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context
+        .index
+        .export_snapshot(1, &context.tempdir.path().join("snapshot"))
+        .unwrap_err()
+        .to_string(),
+      "cannot export height 1 from an index with 3 blocks"
+    );
+  }
+
+  #[test]
+  fn import_rejects_corrupted_snapshot() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    let path = context.tempdir.path().join("snapshot");
+
+    context.index.export_snapshot(1, &path).unwrap();
+
+    let mut bytes = fs::read(&path).unwrap();
+    *bytes.last_mut().unwrap() ^= 1;
+    fs::write(&path, bytes).unwrap();
+
+    let options = import_options(&context);
+
+    assert!(import_snapshot(&options, &path)
+      .unwrap_err()
+      .to_string()
+      .starts_with("snapshot checksum mismatch"));
+
+    assert!(!options.index_path().unwrap().exists());
+  }
+
+  #[test]
+  fn import_rejects_snapshot_from_another_chain() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    let path = context.tempdir.path().join("snapshot");
+
+    context.index.export_snapshot(1, &path).unwrap();
+
+    let options = Options {
+      chain_argument: Chain::Signet,
+      ..import_options(&context)
+    };
+
+    assert_eq!(
+      import_snapshot(&options, &path).unwrap_err().to_string(),
+      "snapshot is for regtest, but ord is on signet"
+    );
+
+    assert!(!options.index_path().unwrap().exists());
+  }
+
+  #[test]
+  fn import_rejects_snapshot_with_other_index_flags() {
+    let context = Context::builder().arg("--index-sats").build();
+    context.mine_blocks(1);
+
+    let path = context.tempdir.path().join("snapshot");
+
+    context.index.export_snapshot(1, &path).unwrap();
+
+    let options = Options {
+      index_sats: false,
+      ..import_options(&context)
+    };
+
+    assert_eq!(
+      import_snapshot(&options, &path).unwrap_err().to_string(),
+      "snapshot was exported with `--index-sats`, but ord is running without it"
+    );
+
+    let options = Options {
+      index_dunes: true,
+      ..import_options(&context)
+    };
+
+    assert_eq!(
+      import_snapshot(&options, &path).unwrap_err().to_string(),
+      "snapshot was exported without `--index-dunes`, but ord is running with it"
+    );
+
+    assert!(!options.index_path().unwrap().exists());
+  }
+
+  #[test]
+  fn import_rejects_snapshot_of_block_reorged_out_of_node_chain() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    let path = context.tempdir.path().join("snapshot");
+
+    context.index.export_snapshot(1, &path).unwrap();
+
+    context.rpc_server.invalidate_tip();
+    context.rpc_server.mine_blocks(2);
+
+    assert!(import_snapshot(&import_options(&context), &path)
+      .unwrap_err()
+      .to_string()
+      .contains("does not match node block"));
+  }
+}
@@ -134,6 +134,20 @@ impl Context {
         outstanding.get(id).copied().unwrap_or_default(),
         entry.supply - entry.burned
       );
+
+      let (holders, _more) = self.index.get_dune_holders(*id, 0, usize::MAX).unwrap();
+
+      assert!(holders.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+      assert_eq!(
+        holders.iter().map(|(_, balance)| balance).sum::<u128>(),
+        entry.supply - entry.burned
+      );
+
+      assert_eq!(
+        self.index.get_dune_holder_count(*id).unwrap(),
+        u64::try_from(holders.len()).unwrap()
+      );
     }
   }
 }
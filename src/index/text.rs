@@ -0,0 +1,262 @@
+use {super::*, redb::MultimapValue, std::collections::BinaryHeap};
+
+const MAX_TERM_LEN: usize = 64;
+
+// Queries with more than one term are answered by intersecting sets of
+// inscription numbers, so these bound the work such a query can do. Phrases
+// also decode every candidate. Single-term queries are paged straight from
+// the index and are not limited.
+const MIN_PREFIX_LEN: usize = 3;
+pub(super) const MAX_CANDIDATES: usize = 10_000;
+pub(super) const MAX_VERIFIED_CANDIDATES: usize = 1_000;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum TextSearchError {
+  PrefixTooShort { prefix: String },
+  TooManyMatches,
+}
+
+impl fmt::Display for TextSearchError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::PrefixTooShort { prefix } => write!(
+        f,
+        "prefix `{prefix}*` is too short, prefixes must be at least {MIN_PREFIX_LEN} characters"
+      ),
+      Self::TooManyMatches => write!(f, "query matches too many inscriptions, narrow it down"),
+    }
+  }
+}
+
+impl std::error::Error for TextSearchError {}
+
+// Content-encoded bodies are not indexed, since they would have to be
+// decompressed while indexing.
+pub(super) fn inscription_text(inscription: &Inscription) -> Option<String> {
+  if inscription.media() != Media::Text || inscription.content_encoding().is_some() {
+    return None;
+  }
+
+  Some(String::from_utf8_lossy(inscription.body()?).into_owned())
+}
+
+pub(super) fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+  text
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|term| !term.is_empty() && term.len() <= MAX_TERM_LEN)
+    .map(str::to_lowercase)
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Clause {
+  pub(super) terms: Vec<String>,
+  pub(super) prefix: bool,
+}
+
+impl Clause {
+  fn matches(&self, terms: &[String]) -> bool {
+    terms.windows(self.terms.len()).any(|window| {
+      window
+        .iter()
+        .zip(&self.terms)
+        .enumerate()
+        .all(|(i, (term, expected))| {
+          if self.prefix && i + 1 == self.terms.len() {
+            term.starts_with(expected.as_str())
+          } else {
+            term == expected
+          }
+        })
+    })
+  }
+}
+
+// Quoted text is a phrase, and a trailing `*` makes the last term of a word or
+// phrase a prefix. Every clause must match.
+#[derive(Debug, PartialEq)]
+pub(super) struct TextQuery {
+  pub(super) clauses: Vec<Clause>,
+}
+
+impl TextQuery {
+  pub(super) fn parse(query: &str) -> Self {
+    let mut clauses = Vec::new();
+
+    for (i, segment) in query.split('"').enumerate() {
+      let words = if i % 2 == 1 {
+        vec![segment]
+      } else {
+        segment.split_whitespace().collect()
+      };
+
+      for word in words {
+        let terms = terms(word).collect::<Vec<String>>();
+
+        if !terms.is_empty() {
+          clauses.push(Clause {
+            terms,
+            prefix: word.trim_end().ends_with('*'),
+          });
+        }
+      }
+    }
+
+    Self { clauses }
+  }
+
+  pub(super) fn check(&self) -> Result<(), TextSearchError> {
+    for clause in &self.clauses {
+      let last = clause.terms.last().unwrap();
+
+      if clause.prefix && last.chars().count() < MIN_PREFIX_LEN {
+        return Err(TextSearchError::PrefixTooShort {
+          prefix: last.clone(),
+        });
+      }
+    }
+
+    Ok(())
+  }
+
+  // Single-term clauses are answered by the index alone, while phrases have to
+  // be checked against the inscription text.
+  pub(super) fn needs_verification(&self) -> bool {
+    self.clauses.iter().any(|clause| clause.terms.len() > 1)
+  }
+
+  pub(super) fn matches(&self, text: &str) -> bool {
+    let terms = terms(text).collect::<Vec<String>>();
+    self.clauses.iter().all(|clause| clause.matches(&terms))
+  }
+}
+
+// Merges the inscription numbers of several terms, newest first, yielding
+// numbers that appear under more than one term only once.
+pub(super) struct Descending<'a> {
+  heads: BinaryHeap<(u64, usize)>,
+  last: Option<u64>,
+  values: Vec<MultimapValue<'a, u64>>,
+}
+
+impl<'a> Descending<'a> {
+  pub(super) fn new(mut values: Vec<MultimapValue<'a, u64>>) -> Result<Self> {
+    let mut heads = BinaryHeap::new();
+
+    for (i, value) in values.iter_mut().enumerate() {
+      if let Some(number) = value.next_back() {
+        heads.push((number?.value(), i));
+      }
+    }
+
+    Ok(Self {
+      heads,
+      last: None,
+      values,
+    })
+  }
+}
+
+impl Iterator for Descending<'_> {
+  type Item = Result<u64, StorageError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let (number, i) = self.heads.pop()?;
+
+      match self.values[i].next_back() {
+        Some(Ok(next)) => self.heads.push((next.value(), i)),
+        Some(Err(err)) => return Some(Err(err)),
+        None => {}
+      }
+
+      if self.last != Some(number) {
+        self.last = Some(number);
+        return Some(Ok(number));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn terms_are_lowercase_alphanumeric_runs() {
+    assert_eq!(
+      terms(r#"{"p":"dev-20","tick":"DOGE"} Such.wow"#).collect::<Vec<String>>(),
+      ["p", "dev", "20", "tick", "doge", "such", "wow"]
+    );
+  }
+
+  #[test]
+  fn long_terms_are_skipped() {
+    assert_eq!(
+      terms(&format!("a {} b", "x".repeat(MAX_TERM_LEN + 1))).collect::<Vec<String>>(),
+      ["a", "b"]
+    );
+  }
+
+  #[test]
+  fn only_plain_text_inscriptions_are_indexed() {
+    assert_eq!(
+      inscription_text(&inscription("text/plain;charset=utf-8", "hello")),
+      Some("hello".into())
+    );
+    assert_eq!(
+      inscription_text(&inscription("application/json", "{}")),
+      Some("{}".into())
+    );
+    assert_eq!(inscription_text(&inscription("image/png", "hello")), None);
+  }
+
+  #[test]
+  fn parse_words_phrases_and_prefixes() {
+    assert_eq!(
+      TextQuery::parse(r#"Much "such wow" dog* "very doge*" .."#),
+      TextQuery {
+        clauses: vec![
+          Clause {
+            terms: vec!["much".into()],
+            prefix: false,
+          },
+          Clause {
+            terms: vec!["such".into(), "wow".into()],
+            prefix: false,
+          },
+          Clause {
+            terms: vec!["dog".into()],
+            prefix: true,
+          },
+          Clause {
+            terms: vec!["very".into(), "doge".into()],
+            prefix: true,
+          },
+        ],
+      }
+    );
+  }
+
+  #[test]
+  fn phrases_must_be_contiguous() {
+    let query = TextQuery::parse(r#""such wow""#);
+    assert!(query.needs_verification());
+    assert!(query.matches("Such wow, much text"));
+    assert!(!query.matches("such text wow"));
+  }
+
+  #[test]
+  fn prefix_matches_last_term_of_phrase() {
+    let query = TextQuery::parse(r#""example.dog*""#);
+    assert!(query.matches("visit example.doge"));
+    assert!(!query.matches("visit example.cat"));
+  }
+
+  #[test]
+  fn words_match_in_any_order() {
+    let query = TextQuery::parse("wow such");
+    assert!(!query.needs_verification());
+    assert!(query.matches("such wow"));
+    assert!(!query.matches("such"));
+  }
+}
@@ -91,7 +91,7 @@ impl<'index> Updater<'_> {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(self.index, self.height, self.index.index_sats)?;
+    let rx = Self::fetch_blocks_from(self.index, self.height)?;
 
     let (mut outpoint_sender, mut value_receiver) = Self::spawn_fetcher(self.index)?;
 
@@ -165,19 +165,12 @@ impl<'index> Updater<'_> {
     Ok(())
   }
 
-  fn fetch_blocks_from(
-    index: &Index,
-    mut height: u32,
-    index_sats: bool,
-  ) -> Result<mpsc::Receiver<BlockData>> {
+  fn fetch_blocks_from(index: &Index, mut height: u32) -> Result<mpsc::Receiver<BlockData>> {
     let (tx, rx) = mpsc::sync_channel(32);
 
     let height_limit = index.height_limit;
 
-    let client =
-      Client::new(&index.rpc_url, index.auth.clone()).context("failed to connect to RPC URL")?;
-
-    let first_inscription_height = index.first_inscription_height;
+    let mut source = index.block_source(height)?;
 
     thread::spawn(move || loop {
       if let Some(height_limit) = height_limit {
@@ -186,7 +179,7 @@ impl<'index> Updater<'_> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      match source.get_block(height) {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -205,60 +198,15 @@ impl<'index> Updater<'_> {
     Ok(rx)
   }
 
-  fn get_block_with_retries(
-    client: &Client,
-    height: u32,
-    index_sats: bool,
-    first_inscription_height: u32,
-  ) -> Result<Option<Block>> {
-    let mut errors = 0;
-    loop {
-      match client
-        .get_block_hash(height.into())
-        .into_option()
-        .and_then(|option| {
-          option
-            .map(|hash| {
-              if index_sats || height >= first_inscription_height {
-                Ok(client.get_block(&hash)?)
-              } else {
-                Ok(Block {
-                  header: client.get_block_header(&hash)?,
-                  txdata: Vec::new(),
-                })
-              }
-            })
-            .transpose()
-        }) {
-        Err(err) => {
-          if cfg!(test) {
-            return Err(err);
-          }
-
-          errors += 1;
-          let seconds = 1 << errors;
-          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
-
-          if seconds > 120 {
-            log::error!("would sleep for more than 120s, giving up");
-            return Err(err);
-          }
-
-          thread::sleep(Duration::from_secs(seconds));
-        }
-        Ok(result) => return Ok(result),
-      }
-    }
-  }
-
-  fn spawn_fetcher(index: &Index) -> Result<(Sender<OutPoint>, Receiver<u64>)> {
+  fn spawn_fetcher(index: &Index) -> Result<(Sender<OutPoint>, Receiver<OutPointMapValue>)> {
     let fetcher = Fetcher::new(&index.rpc_url, index.auth.clone())?;
 
     // Not sure if any block has more than 20k inputs, but none so far after first inscription block
     const CHANNEL_BUFFER_SIZE: usize = 20_000;
     let (outpoint_sender, mut outpoint_receiver) =
       tokio::sync::mpsc::channel::<OutPoint>(CHANNEL_BUFFER_SIZE);
-    let (value_sender, value_receiver) = tokio::sync::mpsc::channel::<u64>(CHANNEL_BUFFER_SIZE);
+    let (value_sender, value_receiver) =
+      tokio::sync::mpsc::channel::<OutPointMapValue>(CHANNEL_BUFFER_SIZE);
 
     // Batch 2048 missing inputs at a time. Arbitrarily chosen for now, maybe higher or lower can be faster?
     // Did rudimentary benchmarks with 1024 and 4096 and time was roughly the same.
@@ -268,6 +216,9 @@ impl<'index> Updater<'_> {
     // else runs a request, we need to keep this a bit lower as configured.
     let parallel_requests = index.nr_parallel_requests;
 
+    let chain = index.chain;
+    let index_addresses = index.index_addresses;
+
     std::thread::spawn(move || {
       let rt = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -303,12 +254,21 @@ impl<'index> Updater<'_> {
               return;
             }
           };
-          // Send all tx output values back in order
+          // Send all tx output values, and addresses if needed, back in order
           for (i, tx) in txs.iter().flatten().enumerate() {
-            let Ok(_) = value_sender
-              .send(tx.output[usize::try_from(outpoints[i].vout).unwrap()].value)
-              .await
-            else {
+            let output = &tx.output[usize::try_from(outpoints[i].vout).unwrap()];
+
+            let address = if index_addresses {
+              chain
+                .address_from_script(&output.script_pubkey)
+                .ok()
+                .and_then(|address| address.to_string().as_bytes().try_into().ok())
+                .unwrap_or([0u8; 34])
+            } else {
+              [0u8; 34]
+            };
+
+            let Ok(_) = value_sender.send((output.value, address)).await else {
               log::error!("Value channel closed unexpectedly");
               return;
             };
@@ -324,7 +284,7 @@ impl<'index> Updater<'_> {
     &mut self,
     index: &Index,
     outpoint_sender: &mut Sender<OutPoint>,
-    value_receiver: &mut Receiver<u64>,
+    value_receiver: &mut Receiver<OutPointMapValue>,
     wtx: &mut WriteTransaction,
     block: BlockData,
     value_cache: &mut HashMap<OutPoint, OutPointMapValue>,
@@ -350,6 +310,8 @@ impl<'index> Updater<'_> {
 
     let mut outpoint_to_value = wtx.open_table(OUTPOINT_TO_VALUE)?;
     let mut address_to_outpoint = wtx.open_multimap_table(ADDRESS_TO_OUTPOINT)?;
+    let mut address_to_activity = wtx.open_multimap_table(ADDRESS_TO_ACTIVITY)?;
+    let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
     let index_inscriptions = self.height >= index.first_inscription_height;
 
@@ -377,8 +339,13 @@ impl<'index> Updater<'_> {
             continue;
           }
           // We don't need input values we already have in our outpoint_to_value table from earlier blocks that
-          // were committed to db already
-          if outpoint_to_value.get(&prev_output.store())?.is_some() {
+          // were committed to db already, unless we're tracking addresses and can't look up the output's script
+          if outpoint_to_value.get(&prev_output.store())?.is_some()
+            && (!index.index_addresses
+              || transaction_id_to_transaction
+                .get(&prev_output.txid.store())?
+                .is_some())
+          {
             continue;
           }
           // We don't know the value of this tx input. Send this outpoint to background thread to be fetched
@@ -391,22 +358,30 @@ impl<'index> Updater<'_> {
 
     let mut inscription_id_to_inscription_entry =
       wtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?;
+    let mut inscription_id_to_children = wtx.open_multimap_table(INSCRIPTION_ID_TO_CHILDREN)?;
+    let mut inscription_id_to_parent = wtx.open_table(INSCRIPTION_ID_TO_PARENT)?;
     let mut inscription_id_to_satpoint = wtx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
+    let mut inscription_id_to_transfer = wtx.open_multimap_table(INSCRIPTION_ID_TO_TRANSFER)?;
     let mut inscription_id_to_txids = wtx.open_table(INSCRIPTION_ID_TO_TXIDS)?;
     let mut inscription_txid_to_tx = wtx.open_table(INSCRIPTION_TXID_TO_TX)?;
     let mut partial_txid_to_inscription_txids =
       wtx.open_table(PARTIAL_TXID_TO_INSCRIPTION_TXIDS)?;
     let mut inscription_number_to_inscription_id =
       wtx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
+    let mut metaprotocol_to_inscription_number =
+      wtx.open_multimap_table(METAPROTOCOL_TO_INSCRIPTION_NUMBER)?;
     let mut sat_to_inscription_id = wtx.open_table(SAT_TO_INSCRIPTION_ID)?;
     let mut satpoint_to_inscription_id = wtx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
+    let mut sha256_to_inscription_number = wtx.open_multimap_table(SHA256_TO_INSCRIPTION_NUMBER)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
-    let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
+    let mut term_to_inscription_number = wtx.open_multimap_table(TERM_TO_INSCRIPTION_NUMBER)?;
 
     let mut bit20_token_info = wtx.open_table(BIT20_TOKEN)?;
     let mut bit20_token_balance = wtx.open_table(BIT20_BALANCES)?;
     let mut bit20_inscribe_transfer = wtx.open_table(BIT20_INSCRIBE_TRANSFER)?;
     let mut bit20_transferable_log = wtx.open_table(BIT20_TRANSFERABLELOG)?;
+    let mut bit20_txid_to_receipts = wtx.open_table(BIT20_TXID_TO_RECEIPTS)?;
+    let mut bit20_inscription_id_to_receipts = wtx.open_table(BIT20_INSCRIPTION_ID_TO_RECEIPTS)?;
 
     let mut lost_sats = statistic_to_count
       .get(&Statistic::LostSats.key())?
@@ -416,21 +391,30 @@ impl<'index> Updater<'_> {
     {
       let mut inscription_updater = InscriptionUpdater::new(
         self.height,
+        &mut address_to_activity,
         &mut inscription_id_to_satpoint,
         &mut inscription_id_to_txids,
         &mut inscription_txid_to_tx,
         &mut partial_txid_to_inscription_txids,
         value_receiver,
+        self.index.index_addresses,
+        self.index.index_text,
         self.index.index_transactions,
         Vec::new(),
         &mut transaction_id_to_transaction,
         &mut inscription_id_to_inscription_entry,
+        &mut inscription_id_to_children,
+        &mut inscription_id_to_parent,
+        &mut inscription_id_to_transfer,
         lost_sats,
+        &mut metaprotocol_to_inscription_number,
         &mut inscription_number_to_inscription_id,
         &mut outpoint_to_value,
         &mut address_to_outpoint,
         &mut sat_to_inscription_id,
         &mut satpoint_to_inscription_id,
+        &mut sha256_to_inscription_number,
+        &mut term_to_inscription_number,
         block.header.time,
         value_cache,
         index.chain,
@@ -543,6 +527,8 @@ impl<'index> Updater<'_> {
           &mut bit20_token_balance,
           &mut bit20_inscribe_transfer,
           &mut bit20_transferable_log,
+          &mut bit20_txid_to_receipts,
+          &mut bit20_inscription_id_to_receipts,
           &inscription_id_to_inscription_entry,
           &mut transaction_id_to_transaction,
         )?
@@ -565,10 +551,20 @@ impl<'index> Updater<'_> {
       let mut dune_id_to_dune_entry = wtx.open_table(DUNE_ID_TO_DUNE_ENTRY)?;
       let mut dune_to_dune_id = wtx.open_table(DUNE_TO_DUNE_ID)?;
       let mut inscription_id_to_dune = wtx.open_table(INSCRIPTION_ID_TO_DUNE)?;
+      let mut dune_holder_to_balance = wtx.open_table(DUNE_HOLDER_TO_BALANCE)?;
+      let mut dune_balance_to_holder = wtx.open_table(DUNE_BALANCE_TO_HOLDER)?;
+      let mut dune_id_to_holder_count = wtx.open_table(DUNE_ID_TO_HOLDER_COUNT)?;
+      let mut dune_id_to_event = wtx.open_multimap_table(DUNE_ID_TO_EVENT)?;
+      let mut outpoint_to_dune_holder = wtx.open_table(OUTPOINT_TO_DUNE_HOLDER)?;
       let mut dune_updater = DuneUpdater::new(
         self.height,
         &mut outpoint_to_dune_balances,
+        &mut outpoint_to_dune_holder,
+        &mut dune_holder_to_balance,
+        &mut dune_balance_to_holder,
+        &mut dune_id_to_holder_count,
         &mut dune_id_to_dune_entry,
+        &mut dune_id_to_event,
         &inscription_id_to_inscription_entry,
         &mut inscription_id_to_dune,
         &mut dune_to_dune_id,
@@ -9,7 +9,7 @@ use {
     super::*,
 };
 
-use crate::bit20::{Balance, BlockContext, Deploy, DeployEvent, BIT20Error, Event, InscripbeTransferEvent, max_script_tick_key, Message, min_script_tick_key, Mint, MintEvent, Num, script_tick_key, Tick, TokenInfo, Transfer, TransferableLog, TransferEvent, TransferInfo};
+use crate::bit20::{Balance, BlockContext, Deploy, DeployEvent, BIT20Error, Event, InscripbeTransferEvent, max_script_tick_key, Message, min_script_tick_key, Mint, MintEvent, Num, Receipt, script_tick_key, Tick, TokenInfo, Transfer, TransferableLog, TransferEvent, TransferInfo};
 use crate::bit20::errors::Error::LedgerError;
 use crate::bit20::operation::{InscriptionOp, Operation};
 use crate::bit20::script_key::ScriptKey;
@@ -31,6 +31,8 @@ pub(super) struct Bit20Updater<'a, 'db, 'tx> {
     bit20_token_balance: &'a mut Table<'db, 'tx, &'static str, &'static [u8]>,
     bit20_inscribe_transfer: &'a mut Table<'db, 'tx, &'static [u8; 36], &'static [u8]>,
     bit20_transferable_log: &'a mut Table<'db, 'tx, &'static str, &'static [u8]>,
+    bit20_txid_to_receipts: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
+    bit20_inscription_id_to_receipts: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, &'static [u8]>,
     inscription_id_to_inscription_entry: &'a Table<'db, 'tx, &'static InscriptionIdValue, InscriptionEntryValue>,
     transaction_id_to_transaction: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
 }
@@ -41,6 +43,8 @@ impl<'a, 'db, 'tx> Bit20Updater<'a, 'db, 'tx> {
         bit20_token_balance: &'a mut Table<'db, 'tx, &'static str, &'static [u8]>,
         bit20_inscribe_transfer: &'a mut Table<'db, 'tx, &'static [u8; 36], &'static [u8]>,
         bit20_transferable_log: &'a mut Table<'db, 'tx, &'static str, &'static [u8]>,
+        bit20_txid_to_receipts: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
+        bit20_inscription_id_to_receipts: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, &'static [u8]>,
         inscription_id_to_inscription_entry: &'a Table<'db, 'tx, &'static InscriptionIdValue, InscriptionEntryValue>,
         transaction_id_to_transaction: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
     ) -> Result<Self> {
@@ -49,6 +53,8 @@ impl<'a, 'db, 'tx> Bit20Updater<'a, 'db, 'tx> {
             bit20_token_balance,
             bit20_inscribe_transfer,
             bit20_transferable_log,
+            bit20_txid_to_receipts,
+            bit20_inscription_id_to_receipts,
             inscription_id_to_inscription_entry,
             transaction_id_to_transaction,
         })
@@ -129,7 +135,7 @@ impl<'a, 'db, 'tx> Bit20Updater<'a, 'db, 'tx> {
 
     pub fn execute_message(&mut self, context: BlockContext, msg: &Message) -> Result {
         let exec_msg = self.create_execution_message(msg, context.network)?;
-        let _ = match &exec_msg.op {
+        let event = match &exec_msg.op {
             Operation::Deploy(deploy) => {
                 Self::process_deploy(self, context.clone(), &exec_msg, deploy.clone())
             }
@@ -139,6 +145,54 @@ impl<'a, 'db, 'tx> Bit20Updater<'a, 'db, 'tx> {
             }
             Operation::Transfer(_) => Self::process_transfer(self, context.clone(), &exec_msg.clone()),
         };
+
+        let receipt = Receipt {
+            inscription_id: exec_msg.inscription_id,
+            inscription_number: exec_msg.inscription_number as i64,
+            old_satpoint: exec_msg.old_satpoint,
+            new_satpoint: exec_msg.new_satpoint,
+            op: exec_msg.op.op_type(),
+            from: exec_msg.from.clone(),
+            // redirect receiver to sender if transfer to coinbase.
+            to: exec_msg.to.clone().unwrap_or_else(|| exec_msg.from.clone()),
+            result: match event {
+                Ok(event) => Ok(event),
+                Err(errors::Error::BIT20Error(e)) => Err(e),
+                // ledger errors are ignored, as they always have been, and leave no receipt.
+                Err(errors::Error::LedgerError(e)) => {
+                    log::warn!("failed to execute dev-20 message! error: {e}");
+                    return Ok(());
+                }
+            },
+        };
+
+        Self::insert_receipt(self, exec_msg.txid, receipt)
+            .map_err(|e| anyhow!("failed to store dev-20 receipt! error: {e}"))?;
+
+        Ok(())
+    }
+
+    fn insert_receipt(&mut self, txid: Txid, receipt: Receipt) -> Result<(), redb::Error> {
+        let mut tx_receipts = self
+            .bit20_txid_to_receipts
+            .get(&txid.store())?
+            .map_or(Vec::new(), |v| bincode::deserialize::<Vec<Receipt>>(v.value()).unwrap());
+        tx_receipts.push(receipt.clone());
+        self.bit20_txid_to_receipts.insert(
+            &txid.store(),
+            bincode::serialize(&tx_receipts).unwrap().as_slice(),
+        )?;
+
+        let inscription_id = receipt.inscription_id.store();
+        let mut inscription_receipts = self
+            .bit20_inscription_id_to_receipts
+            .get(&inscription_id)?
+            .map_or(Vec::new(), |v| bincode::deserialize::<Vec<Receipt>>(v.value()).unwrap());
+        inscription_receipts.push(receipt);
+        self.bit20_inscription_id_to_receipts.insert(
+            &inscription_id,
+            bincode::serialize(&inscription_receipts).unwrap().as_slice(),
+        )?;
         Ok(())
     }
 
@@ -21,13 +21,18 @@ struct Allocation {
 }
 
 pub(super) struct DuneUpdater<'a, 'db, 'tx> {
+  balance_to_holder: &'a mut Table<'db, 'tx, &'static [u8], u128>,
   height: u32,
+  holder_to_balance: &'a mut Table<'db, 'tx, &'static [u8], u128>,
   id_to_entry: &'a mut Table<'db, 'tx, DuneIdValue, DuneEntryValue>,
+  id_to_event: &'a mut MultimapTable<'db, 'tx, DuneIdValue, &'static DuneEventValue>,
+  id_to_holder_count: &'a mut Table<'db, 'tx, DuneIdValue, u64>,
   inscription_id_to_inscription_entry:
   &'a Table<'db, 'tx, &'static InscriptionIdValue, InscriptionEntryValue>,
   inscription_id_to_dune: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, u128>,
   minimum: Dune,
   outpoint_to_balances: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
+  outpoint_to_holder: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
   dune_to_id: &'a mut Table<'db, 'tx, u128, DuneIdValue>,
   dunes: u64,
   statistic_to_count: &'a mut Table<'db, 'tx, u64, u64>,
@@ -38,7 +43,12 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
   pub(super) fn new(
     height: u32,
     outpoint_to_balances: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
+    outpoint_to_holder: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
+    holder_to_balance: &'a mut Table<'db, 'tx, &'static [u8], u128>,
+    balance_to_holder: &'a mut Table<'db, 'tx, &'static [u8], u128>,
+    id_to_holder_count: &'a mut Table<'db, 'tx, DuneIdValue, u64>,
     id_to_entry: &'a mut Table<'db, 'tx, DuneIdValue, DuneEntryValue>,
+    id_to_event: &'a mut MultimapTable<'db, 'tx, DuneIdValue, &'static DuneEventValue>,
     inscription_id_to_inscription_entry: &'a Table<
       'db,
       'tx,
@@ -56,10 +66,15 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
         .map(|x| x.value())
         .unwrap_or(0);
     Ok(Self {
+      balance_to_holder,
       height,
+      holder_to_balance,
       id_to_entry,
+      id_to_event,
+      id_to_holder_count,
       minimum,
       outpoint_to_balances,
+      outpoint_to_holder,
       inscription_id_to_inscription_entry,
       inscription_id_to_dune,
       dune_to_id,
@@ -72,11 +87,24 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
   pub(super) fn index_dunes(&mut self, index: usize, tx: &Transaction, txid: Txid) -> Result<()> {
     let dunestone = Dunestone::from_transaction(tx);
 
+    let height = self.height;
+    let tx_index = u32::try_from(index).unwrap();
+    let event = |kind, output, amount| DuneEvent {
+      height,
+      index: tx_index,
+      kind,
+      output,
+      txid,
+      amount,
+    };
+
     // A mapping of dune ID to un-allocated balance of that dune
     let mut unallocated: HashMap<u128, u128> = HashMap::new();
 
     // Increment unallocated dunes with the dunes in this transaction's inputs
     for input in &tx.input {
+      let mut spent = Vec::new();
+
       if let Some(guard) = self
         .outpoint_to_balances
         .remove(&input.previous_output.store())?
@@ -89,10 +117,26 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
           let (balance, len) = varint::decode(&buffer[i..]);
           i += len;
           *unallocated.entry(id).or_default() += balance;
+          spent.push((id, balance));
+        }
+      }
+
+      let holder = self
+        .outpoint_to_holder
+        .remove(&input.previous_output.store())?
+        .map(|holder| holder.value().to_vec());
+
+      if let Some(holder) = holder {
+        for (id, balance) in spent {
+          self.debit_holder(id, &holder, balance)?;
         }
       }
     }
 
+    // Dunes received by outputs are only transfers if they were spent by an
+    // input, rather than etched or minted by this transaction
+    let spent = unallocated.keys().copied().collect::<HashSet<u128>>();
+
     let cenotaph = dunestone
         .as_ref()
         .map(|dunestone| dunestone.cenotaph)
@@ -290,6 +334,9 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
             entry.supply += minted;
             entry.mints += 1;
             self.id_to_entry.insert(id, entry.store())?;
+            self
+              .id_to_event
+              .insert(id, &event(DuneEventKind::Mint, None, minted).store())?;
           }
         }
       }
@@ -308,6 +355,10 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
       {
         let id = DuneId::try_from(id).unwrap();
         self.dune_to_id.insert(dune.0, id.store())?;
+        self.id_to_event.insert(
+          id.store(),
+          &event(DuneEventKind::Etch, None, premine_amount).store(),
+        )?;
         let number = self.dunes;
         self.dunes += 1;
 
@@ -406,19 +457,40 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
       // Sort balances by id so tests can assert balances in a fixed order
       balances.sort();
 
-      for (id, balance) in balances {
-        varint::encode_to_vec(id, &mut buffer);
-        varint::encode_to_vec(balance, &mut buffer);
+      for (id, balance) in &balances {
+        varint::encode_to_vec(*id, &mut buffer);
+        varint::encode_to_vec(*balance, &mut buffer);
       }
 
-      self.outpoint_to_balances.insert(
-        &OutPoint {
-          txid,
-          vout: vout.try_into().unwrap(),
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      }
+      .store();
+
+      self
+        .outpoint_to_balances
+        .insert(&outpoint, buffer.as_slice())?;
+
+      let holder = tx.output[vout].script_pubkey.as_bytes();
+
+      self.outpoint_to_holder.insert(&outpoint, holder)?;
+
+      for (id, balance) in balances {
+        self.credit_holder(id, holder, balance)?;
+
+        if spent.contains(&id) {
+          self.id_to_event.insert(
+            DuneId::try_from(id).unwrap().store(),
+            &event(
+              DuneEventKind::Transfer,
+              Some(vout.try_into().unwrap()),
+              balance,
+            )
+            .store(),
+          )?;
         }
-        .store(),
-        buffer.as_slice(),
-      )?;
+      }
     }
 
     // increment entries with burned dunes
@@ -427,10 +499,121 @@ impl<'a, 'db, 'tx> DuneUpdater<'a, 'db, 'tx> {
       let mut entry = DuneEntry::load(self.id_to_entry.get(id)?.unwrap().value());
       entry.burned += amount;
       self.id_to_entry.insert(id, entry.store())?;
+
+      let kind = if cenotaph {
+        DuneEventKind::CenotaphBurn
+      } else {
+        DuneEventKind::Burn
+      };
+
+      self
+        .id_to_event
+        .insert(id, &event(kind, None, amount).store())?;
     }
 
     Ok(())
   }
+
+  fn holder_key(id: u128, holder: &[u8]) -> Vec<u8> {
+    let mut key = id.to_be_bytes().to_vec();
+    key.extend_from_slice(holder);
+    key
+  }
+
+  fn credit_holder(&mut self, id: u128, holder: &[u8], amount: u128) -> Result {
+    let key = Self::holder_key(id, holder);
+
+    let balance = self
+      .holder_to_balance
+      .get(key.as_slice())?
+      .map(|balance| balance.value());
+
+    if balance.is_none() {
+      self.adjust_holder_count(id, true)?;
+    }
+
+    let credited = balance.unwrap_or_default() + amount;
+
+    self.holder_to_balance.insert(key.as_slice(), credited)?;
+
+    self.rank_holder(id, holder, balance, Some(credited))
+  }
+
+  fn debit_holder(&mut self, id: u128, holder: &[u8], amount: u128) -> Result {
+    let key = Self::holder_key(id, holder);
+
+    // holders are credited with every balance they are later debited, so a
+    // missing or short balance means the holder tables are corrupt
+    let balance = self
+      .holder_to_balance
+      .get(key.as_slice())?
+      .map(|balance| balance.value())
+      .ok_or_else(|| {
+        anyhow!(
+          "dune {id} holder {} has no balance to debit",
+          hex::encode(holder)
+        )
+      })?;
+
+    let debited = balance.checked_sub(amount).ok_or_else(|| {
+      anyhow!(
+        "debiting {amount} from dune {id} holder {} underflows its balance of {balance}",
+        hex::encode(holder)
+      )
+    })?;
+
+    if debited == 0 {
+      self.holder_to_balance.remove(key.as_slice())?;
+      self.adjust_holder_count(id, false)?;
+    } else {
+      self.holder_to_balance.insert(key.as_slice(), debited)?;
+    }
+
+    self.rank_holder(id, holder, Some(balance), (debited > 0).then_some(debited))
+  }
+
+  fn rank_holder(
+    &mut self,
+    id: u128,
+    holder: &[u8],
+    old: Option<u128>,
+    new: Option<u128>,
+  ) -> Result {
+    if let Some(old) = old {
+      self
+        .balance_to_holder
+        .remove(Index::ranked_dune_holder_key(id, old, holder).as_slice())?;
+    }
+
+    if let Some(new) = new {
+      self.balance_to_holder.insert(
+        Index::ranked_dune_holder_key(id, new, holder).as_slice(),
+        new,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  fn adjust_holder_count(&mut self, id: u128, increment: bool) -> Result {
+    let id = DuneId::try_from(id).unwrap().store();
+
+    let count = self
+      .id_to_holder_count
+      .get(id)?
+      .map(|count| count.value())
+      .unwrap_or_default();
+
+    let count = if increment {
+      count + 1
+    } else {
+      count.saturating_sub(1)
+    };
+
+    self.id_to_holder_count.insert(id, count)?;
+
+    Ok(())
+  }
 }
 
 #[cfg(test)]
@@ -442,4 +625,62 @@ mod tests {
     assert_eq!(claim(1), None);
     assert_eq!(claim(1 | CLAIM_BIT), Some(1));
   }
+
+  #[test]
+  fn debiting_holder_requires_sufficient_balance() {
+    let tempdir = TempDir::new().unwrap();
+    let database = Database::create(tempdir.path().join("index.redb")).unwrap();
+    let wtx = database.begin_write().unwrap();
+
+    let mut outpoint_to_balances = wtx.open_table(OUTPOINT_TO_DUNE_BALANCES).unwrap();
+    let mut outpoint_to_holder = wtx.open_table(OUTPOINT_TO_DUNE_HOLDER).unwrap();
+    let mut holder_to_balance = wtx.open_table(DUNE_HOLDER_TO_BALANCE).unwrap();
+    let mut balance_to_holder = wtx.open_table(DUNE_BALANCE_TO_HOLDER).unwrap();
+    let mut id_to_holder_count = wtx.open_table(DUNE_ID_TO_HOLDER_COUNT).unwrap();
+    let mut id_to_entry = wtx.open_table(DUNE_ID_TO_DUNE_ENTRY).unwrap();
+    let mut id_to_event = wtx.open_multimap_table(DUNE_ID_TO_EVENT).unwrap();
+    let inscription_id_to_inscription_entry =
+      wtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY).unwrap();
+    let mut inscription_id_to_dune = wtx.open_table(INSCRIPTION_ID_TO_DUNE).unwrap();
+    let mut dune_to_id = wtx.open_table(DUNE_TO_DUNE_ID).unwrap();
+    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT).unwrap();
+
+    let mut updater = DuneUpdater::new(
+      0,
+      &mut outpoint_to_balances,
+      &mut outpoint_to_holder,
+      &mut holder_to_balance,
+      &mut balance_to_holder,
+      &mut id_to_holder_count,
+      &mut id_to_entry,
+      &mut id_to_event,
+      &inscription_id_to_inscription_entry,
+      &mut inscription_id_to_dune,
+      &mut dune_to_id,
+      &mut statistic_to_count,
+      0,
+      Dune(0),
+    )
+    .unwrap();
+
+    assert_eq!(
+      updater.debit_holder(1, b"a", 1).unwrap_err().to_string(),
+      "dune 1 holder 61 has no balance to debit"
+    );
+
+    updater.credit_holder(1, b"a", 5).unwrap();
+
+    assert_eq!(
+      updater.debit_holder(1, b"a", 6).unwrap_err().to_string(),
+      "debiting 6 from dune 1 holder 61 underflows its balance of 5"
+    );
+
+    updater.debit_holder(1, b"a", 5).unwrap();
+
+    assert!(updater
+      .holder_to_balance
+      .get(DuneUpdater::holder_key(1, b"a").as_slice())
+      .unwrap()
+      .is_none());
+  }
 }
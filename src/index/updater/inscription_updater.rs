@@ -15,11 +15,18 @@ pub(super) struct Flotsam {
 
 #[derive(Debug, Clone)]
 enum Origin {
-  New { fee: u64, inscription: Inscription },
+  New {
+    fee: u64,
+    inscription: Inscription,
+    parent: Option<InscriptionId>,
+  },
   Old(SatPoint),
 }
 
 pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
+  address_activity: HashMap<([u8; 34], Direction), u64>,
+  address_to_activity:
+    &'a mut MultimapTable<'db, 'tx, &'static [u8], &'static AddressActivityValue>,
   flotsam: Vec<Flotsam>,
   pub(super) operations: HashMap<Txid, Vec<InscriptionOp>>,
   height: u32,
@@ -27,12 +34,19 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   id_to_txids: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, &'static [u8]>,
   txid_to_tx: &'a mut Table<'db, 'tx, &'static [u8], &'static [u8]>,
   partial_txid_to_txids: &'a mut Table<'db, 'tx, &'static [u8], &'static [u8]>,
-  value_receiver: &'a mut Receiver<u64>,
+  value_receiver: &'a mut Receiver<OutPointMapValue>,
+  index_addresses: bool,
+  index_text: bool,
   index_transactions: bool,
   transaction_buffer: Vec<u8>,
   transaction_id_to_transaction: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
   id_to_entry: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, InscriptionEntryValue>,
+  id_to_children: &'a mut MultimapTable<'db, 'tx, &'static InscriptionIdValue, u64>,
+  id_to_parent: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, &'static InscriptionIdValue>,
+  id_to_transfer:
+    &'a mut MultimapTable<'db, 'tx, &'static InscriptionIdValue, &'static InscriptionTransferValue>,
   lost_sats: u64,
+  metaprotocol_to_number: &'a mut MultimapTable<'db, 'tx, &'static str, u64>,
   next_number: u64,
   number_to_id: &'a mut Table<'db, 'tx, u64, &'static InscriptionIdValue>,
   outpoint_to_value: &'a mut Table<'db, 'tx, &'static OutPointValue, u64>,
@@ -40,7 +54,10 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   reward: u64,
   sat_to_inscription_id: &'a mut Table<'db, 'tx, u64, &'static InscriptionIdValue>,
   satpoint_to_id: &'a mut Table<'db, 'tx, &'static SatPointValue, &'static InscriptionIdValue>,
+  sha256_to_number: &'a mut MultimapTable<'db, 'tx, &'static [u8; 32], u64>,
+  term_to_number: &'a mut MultimapTable<'db, 'tx, &'static str, u64>,
   timestamp: u32,
+  transfers: u32,
   value_cache: &'a mut HashMap<OutPoint, OutPointMapValue>,
   chain: Chain,
 }
@@ -48,21 +65,40 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
 impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) fn new(
     height: u32,
+    address_to_activity: &'a mut MultimapTable<
+      'db,
+      'tx,
+      &'static [u8],
+      &'static AddressActivityValue,
+    >,
     id_to_satpoint: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, &'static SatPointValue>,
     id_to_txids: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, &'static [u8]>,
     txid_to_tx: &'a mut Table<'db, 'tx, &'static [u8], &'static [u8]>,
     partial_txid_to_txids: &'a mut Table<'db, 'tx, &'static [u8], &'static [u8]>,
-    value_receiver: &'a mut Receiver<u64>,
+    value_receiver: &'a mut Receiver<OutPointMapValue>,
+    index_addresses: bool,
+    index_text: bool,
     index_transactions: bool,
     transaction_buffer: Vec<u8>,
     transaction_id_to_transaction: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
     id_to_entry: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, InscriptionEntryValue>,
+    id_to_children: &'a mut MultimapTable<'db, 'tx, &'static InscriptionIdValue, u64>,
+    id_to_parent: &'a mut Table<'db, 'tx, &'static InscriptionIdValue, &'static InscriptionIdValue>,
+    id_to_transfer: &'a mut MultimapTable<
+      'db,
+      'tx,
+      &'static InscriptionIdValue,
+      &'static InscriptionTransferValue,
+    >,
     lost_sats: u64,
+    metaprotocol_to_number: &'a mut MultimapTable<'db, 'tx, &'static str, u64>,
     number_to_id: &'a mut Table<'db, 'tx, u64, &'static InscriptionIdValue>,
     outpoint_to_value: &'a mut Table<'db, 'tx, &'static OutPointValue, u64>,
     address_to_outpoint: &'a mut MultimapTable<'db, 'tx, &'static [u8], &'static OutPointValue>,
     sat_to_inscription_id: &'a mut Table<'db, 'tx, u64, &'static InscriptionIdValue>,
     satpoint_to_id: &'a mut Table<'db, 'tx, &'static SatPointValue, &'static InscriptionIdValue>,
+    sha256_to_number: &'a mut MultimapTable<'db, 'tx, &'static [u8; 32], u64>,
+    term_to_number: &'a mut MultimapTable<'db, 'tx, &'static str, u64>,
     timestamp: u32,
     value_cache: &'a mut HashMap<OutPoint, OutPointMapValue>,
     chain: Chain,
@@ -76,6 +112,8 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       .unwrap_or(0);
 
     Ok(Self {
+      address_activity: HashMap::new(),
+      address_to_activity,
       flotsam: Vec::new(),
       operations: HashMap::new(),
       height,
@@ -84,11 +122,17 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       txid_to_tx,
       partial_txid_to_txids,
       value_receiver,
+      index_addresses,
+      index_text,
       index_transactions,
       transaction_buffer,
       transaction_id_to_transaction,
       id_to_entry,
+      id_to_children,
+      id_to_parent,
+      id_to_transfer,
       lost_sats,
+      metaprotocol_to_number,
       next_number,
       number_to_id,
       outpoint_to_value,
@@ -96,7 +140,10 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       reward: Height(height).subsidy(),
       sat_to_inscription_id,
       satpoint_to_id,
+      sha256_to_number,
+      term_to_number,
       timestamp,
+      transfers: 0,
       value_cache,
       chain,
     })
@@ -144,36 +191,37 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           return Err(e);
         }
 
-        input_value += if let Some(map) = self.value_cache.remove(&tx_in.previous_output) {
-          map.0
-        } else if let Some(map) = self
-          .outpoint_to_value
-          .remove(&tx_in.previous_output.store())?
-        {
-          if let Some(transaction) = self
-            .transaction_id_to_transaction
-            .get(&tx_in.previous_output.txid.store())?
-          {
-            let tx: Transaction = consensus::encode::deserialize(transaction.value())?;
-            let output = tx.output[tx_in.previous_output.vout as usize].clone();
-            if let Some(address_from_script) =
-              self.chain.address_from_script(&output.script_pubkey).ok()
-            {
-              self.address_to_outpoint.remove(
-                address_from_script.to_string().as_bytes(),
-                &tx_in.previous_output.store(),
-              )?;
+        let (value, address) = if let Some(map) = self.value_cache.remove(&tx_in.previous_output) {
+          map
+        } else if let Some(value) = self.remove_outpoint_value(tx_in.previous_output)? {
+          let address = match self.get_transaction(tx_in.previous_output.txid)? {
+            Some(tx) => {
+              self.address_bytes(&tx.output[tx_in.previous_output.vout as usize].script_pubkey)
             }
+            // Without the stored transaction, the fetcher was asked for the output instead
+            None if self.index_addresses => self.receive_value(tx_in.previous_output)?.1,
+            None => [0u8; 34],
+          };
+
+          if address != [0u8; 34] {
+            self
+              .address_to_outpoint
+              .remove(address.as_slice(), &tx_in.previous_output.store())?;
           }
-          map.value()
+
+          (value, address)
         } else {
-          self.value_receiver.blocking_recv().ok_or_else(|| {
-            anyhow!(
-              "failed to get transaction for {}",
-              tx_in.previous_output.txid
-            )
-          })?
+          self.receive_value(tx_in.previous_output)?
+        };
+
+        if self.index_addresses && address != [0u8; 34] {
+          *self
+            .address_activity
+            .entry((address, Direction::Sent))
+            .or_default() += value;
         }
+
+        input_value += value;
       }
     }
 
@@ -257,6 +305,13 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
             index: 0,
           };
 
+          // a parent only counts if the revealing transaction spends it
+          let parent = _inscription.parent().filter(|parent| {
+            inscriptions
+              .iter()
+              .any(|flotsam| flotsam.inscription_id == *parent)
+          });
+
           inscriptions.push(Flotsam {
             txid,
             inscription_id: og_inscription_id,
@@ -271,6 +326,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
             origin: Origin::New {
               fee: input_value - tx.output.iter().map(|txout| txout.value).sum::<u64>(),
               inscription: _inscription.clone(),
+              parent,
             },
           });
         }
@@ -294,6 +350,8 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     for (vout, tx_out) in tx.output.iter().enumerate() {
       let end = output_value + tx_out.value;
 
+      let address = self.address_bytes(&tx_out.script_pubkey);
+
       while let Some(flotsam) = inscriptions.peek() {
         if flotsam.offset >= end {
           break;
@@ -311,25 +369,18 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           input_sat_ranges,
           inscriptions.next().unwrap(),
           new_satpoint,
+          address,
         )?;
       }
 
       output_value = end;
 
-      let address_from_script = self
-        .chain
-        .address_from_script(&tx_out.clone().script_pubkey);
-
-      let address = if address_from_script.is_err() {
-        [0u8; 34]
-      } else {
-        address_from_script
-          .unwrap()
-          .to_string()
-          .as_bytes()
-          .try_into()
-          .unwrap_or([0u8; 34])
-      };
+      if self.index_addresses && address != [0u8; 34] {
+        *self
+          .address_activity
+          .entry((address, Direction::Received))
+          .or_default() += tx_out.value;
+      }
 
       self.value_cache.insert(
         OutPoint {
@@ -340,13 +391,26 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       );
     }
 
+    for ((address, direction), value) in self.address_activity.drain() {
+      self.address_to_activity.insert(
+        address.as_slice(),
+        &AddressActivity {
+          height: self.height,
+          txid,
+          direction,
+          value,
+        }
+        .store(),
+      )?;
+    }
+
     if is_coinbase {
       for flotsam in inscriptions {
         let new_satpoint = SatPoint {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
-        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint, [0; 34])?;
       }
 
       Ok(self.reward - output_value)
@@ -360,26 +424,109 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     }
   }
 
+  fn address_bytes(&self, script_pubkey: &Script) -> [u8; 34] {
+    self
+      .chain
+      .address_from_script(script_pubkey)
+      .ok()
+      .and_then(|address| address.to_string().as_bytes().try_into().ok())
+      .unwrap_or([0u8; 34])
+  }
+
+  fn remove_outpoint_value(&mut self, outpoint: OutPoint) -> Result<Option<u64>> {
+    Ok(
+      self
+        .outpoint_to_value
+        .remove(&outpoint.store())?
+        .map(|value| value.value()),
+    )
+  }
+
+  fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    Ok(
+      self
+        .transaction_id_to_transaction
+        .get(&txid.store())?
+        .map(|transaction| consensus::encode::deserialize(transaction.value()))
+        .transpose()?,
+    )
+  }
+
+  fn receive_value(&mut self, outpoint: OutPoint) -> Result<OutPointMapValue> {
+    self
+      .value_receiver
+      .blocking_recv()
+      .ok_or_else(|| anyhow!("failed to get transaction for {}", outpoint.txid))
+  }
+
   fn update_inscription_location(
     &mut self,
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
+    owner: [u8; 34],
   ) -> Result {
     let inscription_id = flotsam.inscription_id.store();
 
+    self.id_to_transfer.insert(
+      &inscription_id,
+      &InscriptionTransfer {
+        height: self.height,
+        sequence: self.transfers,
+        txid: flotsam.txid,
+        old_satpoint: match flotsam.origin {
+          Origin::Old(old_satpoint) => Some(old_satpoint),
+          Origin::New { .. } => None,
+        },
+        new_satpoint,
+        owner: (owner != [0; 34]).then(|| String::from_utf8_lossy(&owner).into_owned()),
+      }
+      .store(),
+    )?;
+
+    self.transfers += 1;
+
     match flotsam.origin {
       Origin::Old(old_satpoint) => {
         self.satpoint_to_id.remove(&old_satpoint.store())?;
       }
       Origin::New {
         fee,
-        inscription: _,
+        ref inscription,
+        parent,
       } => {
         self
           .number_to_id
           .insert(&self.next_number, &inscription_id)?;
 
+        if let Some(metaprotocol) = inscription.metaprotocol() {
+          self
+            .metaprotocol_to_number
+            .insert(metaprotocol, &self.next_number)?;
+        }
+
+        if let Some(sha256) = inscription.content_sha256() {
+          self
+            .sha256_to_number
+            .insert(&sha256.into_inner(), &self.next_number)?;
+        }
+
+        if self.index_text {
+          if let Some(text) = text::inscription_text(inscription) {
+            for term in text::terms(&text).collect::<BTreeSet<String>>() {
+              self
+                .term_to_number
+                .insert(term.as_str(), &self.next_number)?;
+            }
+          }
+        }
+
+        if let Some(parent) = parent {
+          let parent = parent.store();
+          self.id_to_parent.insert(&inscription_id, &parent)?;
+          self.id_to_children.insert(&parent, &self.next_number)?;
+        }
+
         let mut sat = None;
         if let Some(input_sat_ranges) = input_sat_ranges {
           let mut offset = 0;
@@ -427,10 +574,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           inscription_id: flotsam.inscription_id,
           action: match flotsam.origin {
             Origin::Old(_) => Action::Transfer,
-            Origin::New {
-              fee: _,
-              inscription,
-            } => Action::New {
+            Origin::New { inscription, .. } => Action::New {
               inscription,
             },
           },
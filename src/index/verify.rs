@@ -0,0 +1,460 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Problem {
+  BlockHash {
+    height: u32,
+    index: BlockHash,
+    node: Option<BlockHash>,
+  },
+  Dev20Minted {
+    tick: String,
+    minted: u128,
+    balances: u128,
+  },
+  DuneSupply {
+    dune: DuneId,
+    outstanding: u128,
+    balances: u128,
+  },
+  InscriptionSatpoint {
+    inscription_id: InscriptionId,
+    satpoint: SatPoint,
+    indexed: Option<InscriptionId>,
+  },
+  StaleSatpoint {
+    satpoint: SatPoint,
+    inscription_id: InscriptionId,
+  },
+}
+
+impl Problem {
+  // Problems in tables that are derived from other tables, and so can be
+  // repaired without reindexing.
+  fn repairable(&self) -> bool {
+    matches!(
+      self,
+      Self::InscriptionSatpoint { .. } | Self::StaleSatpoint { .. }
+    )
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+  pub blocks: u64,
+  pub dunes: u64,
+  pub dev20_tokens: u64,
+  pub inscriptions: u64,
+  pub problems: Vec<Problem>,
+  pub repaired: Vec<String>,
+  pub ok: bool,
+}
+
+impl Index {
+  pub(crate) fn verify(&self, repair: bool) -> Result<Report> {
+    let rtx = self.database.begin_read()?;
+
+    let mut problems = Vec::new();
+
+    let mut blocks = 0;
+
+    for result in rtx.open_table(HEIGHT_TO_BLOCK_HASH)?.iter()? {
+      let (height, hash) = result?;
+      let height = height.value();
+      let index = BlockHash::load(*hash.value());
+      let node = self.client.get_block_hash(height.into()).into_option()?;
+
+      if node != Some(index) {
+        problems.push(Problem::BlockHash {
+          height,
+          index,
+          node,
+        });
+      }
+
+      blocks += 1;
+    }
+
+    let mut dunes = 0;
+
+    if self.index_dunes && self.block_count()? > self.first_dune_height {
+      let mut balances = BTreeMap::<DuneId, u128>::new();
+
+      for result in rtx.open_table(OUTPOINT_TO_DUNE_BALANCES)?.iter()? {
+        let (_outpoint, buffer) = result?;
+        let buffer = buffer.value();
+
+        let mut i = 0;
+        while i < buffer.len() {
+          let (id, length) = dunes::varint::decode(&buffer[i..]);
+          i += length;
+          let (balance, length) = dunes::varint::decode(&buffer[i..]);
+          i += length;
+          *balances.entry(DuneId::try_from(id)?).or_default() += balance;
+        }
+      }
+
+      for result in rtx.open_table(DUNE_ID_TO_DUNE_ENTRY)?.iter()? {
+        let (id, entry) = result?;
+        let id = DuneId::load(id.value());
+        let entry = DuneEntry::load(entry.value());
+
+        let outstanding = entry.supply.saturating_sub(entry.burned);
+        let balance = balances.remove(&id).unwrap_or_default();
+
+        if balance != outstanding {
+          problems.push(Problem::DuneSupply {
+            dune: id,
+            outstanding,
+            balances: balance,
+          });
+        }
+
+        dunes += 1;
+      }
+
+      for (id, balance) in balances {
+        problems.push(Problem::DuneSupply {
+          dune: id,
+          outstanding: 0,
+          balances: balance,
+        });
+      }
+    }
+
+    let mut dev20_tokens = 0;
+
+    {
+      let mut balances = BTreeMap::<String, (Tick, u128)>::new();
+
+      for result in rtx.open_table(BIT20_BALANCES)?.iter()? {
+        let (_key, balance) = result?;
+        let balance = bincode::deserialize::<Balance>(balance.value())?;
+        balances
+          .entry(balance.tick.to_lowercase().hex())
+          .or_insert((balance.tick, 0))
+          .1 += balance.overall_balance;
+      }
+
+      for result in rtx.open_table(BIT20_TOKEN)?.iter()? {
+        let (key, info) = result?;
+        let info = bincode::deserialize::<TokenInfo>(info.value())?;
+        let balance = balances
+          .remove(key.value())
+          .map(|(_tick, balance)| balance)
+          .unwrap_or_default();
+
+        // Transferable amounts stay in the overall balance until they are
+        // transferred, so the overall balances alone add up to what was minted.
+        if balance != info.minted {
+          problems.push(Problem::Dev20Minted {
+            tick: info.tick.to_string(),
+            minted: info.minted,
+            balances: balance,
+          });
+        }
+
+        dev20_tokens += 1;
+      }
+
+      for (tick, balance) in balances.into_values() {
+        problems.push(Problem::Dev20Minted {
+          tick: tick.to_string(),
+          minted: 0,
+          balances: balance,
+        });
+      }
+    }
+
+    let mut inscriptions = 0;
+
+    {
+      let id_to_satpoint = rtx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
+      let satpoint_to_id = rtx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
+
+      for result in id_to_satpoint.iter()? {
+        let (id, satpoint) = result?;
+
+        let indexed = satpoint_to_id.get(satpoint.value())?;
+
+        // inscriptions can share a satpoint, and the entry holds only one of
+        // them, so it need only point to some inscription at that satpoint
+        let consistent = match &indexed {
+          Some(indexed) => id_to_satpoint
+            .get(indexed.value())?
+            .is_some_and(|forward| forward.value() == satpoint.value()),
+          None => false,
+        };
+
+        let indexed = indexed.map(|id| InscriptionId::load(*id.value()));
+
+        if !consistent {
+          problems.push(Problem::InscriptionSatpoint {
+            inscription_id: InscriptionId::load(*id.value()),
+            satpoint: SatPoint::load(*satpoint.value()),
+            indexed,
+          });
+        }
+
+        inscriptions += 1;
+      }
+
+      for result in satpoint_to_id.iter()? {
+        let (satpoint, id) = result?;
+
+        let forward = id_to_satpoint
+          .get(id.value())?
+          .map(|satpoint| SatPoint::load(*satpoint.value()));
+
+        if forward != Some(SatPoint::load(*satpoint.value())) {
+          problems.push(Problem::StaleSatpoint {
+            satpoint: SatPoint::load(*satpoint.value()),
+            inscription_id: InscriptionId::load(*id.value()),
+          });
+        }
+      }
+    }
+
+    drop(rtx);
+
+    let mut repaired = Vec::new();
+
+    if repair && problems.iter().any(Problem::repairable) {
+      self.repair_satpoint_to_inscription_id()?;
+      repaired.push(SATPOINT_TO_INSCRIPTION_ID.name().to_string());
+      problems.retain(|problem| !problem.repairable());
+    }
+
+    Ok(Report {
+      blocks,
+      dunes,
+      dev20_tokens,
+      inscriptions,
+      ok: problems.is_empty(),
+      problems,
+      repaired,
+    })
+  }
+
+  // Removes stale entries and adds missing ones. Entries that are still
+  // correct are kept, so a satpoint shared by several inscriptions keeps
+  // showing the same one.
+  fn repair_satpoint_to_inscription_id(&self) -> Result {
+    let wtx = self.begin_write()?;
+
+    {
+      let id_to_satpoint = wtx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
+      let mut satpoint_to_id = wtx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
+
+      let mut stale = Vec::new();
+
+      for result in satpoint_to_id.iter()? {
+        let (satpoint, id) = result?;
+
+        let forward = id_to_satpoint.get(id.value())?;
+
+        if forward.map(|forward| *forward.value()) != Some(*satpoint.value()) {
+          stale.push(*satpoint.value());
+        }
+      }
+
+      for satpoint in stale {
+        satpoint_to_id.remove(&satpoint)?;
+      }
+
+      for result in id_to_satpoint.iter()? {
+        let (id, satpoint) = result?;
+
+        if satpoint_to_id.get(satpoint.value())?.is_none() {
+          satpoint_to_id.insert(satpoint.value(), id.value())?;
+        }
+      }
+    }
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn consistent_index_has_no_problems() {
+    let context = Context::builder().build();
+    context.mine_blocks(2);
+
+    let report = context.index.verify(false).unwrap();
+
+    assert_eq!(report.blocks, 3);
+    assert!(report.problems.is_empty());
+    assert!(report.ok);
+  }
+
+  #[test]
+  fn block_hash_mismatch_is_reported() {
+    let context = Context::builder().build();
+    let blocks = context.mine_blocks(2);
+
+    let hash = BlockHash::from_inner([1; 32]);
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(HEIGHT_TO_BLOCK_HASH)
+        .unwrap()
+        .insert(&2, &hash.store())
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    let report = context.index.verify(true).unwrap();
+
+    assert_eq!(
+      report.problems,
+      [Problem::BlockHash {
+        height: 2,
+        index: hash,
+        node: Some(blocks[1].block_hash()),
+      }]
+    );
+    assert!(report.repaired.is_empty());
+    assert!(!report.ok);
+  }
+
+  #[test]
+  fn satpoint_tables_are_checked_and_repaired() {
+    let context = Context::builder().build();
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(INSCRIPTION_ID_TO_SATPOINT)
+        .unwrap()
+        .insert(&inscription_id(1).store(), &satpoint(1, 0).store())
+        .unwrap();
+      wtx
+        .open_table(SATPOINT_TO_INSCRIPTION_ID)
+        .unwrap()
+        .insert(&satpoint(2, 0).store(), &inscription_id(2).store())
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    assert_eq!(
+      context.index.verify(false).unwrap().problems,
+      [
+        Problem::InscriptionSatpoint {
+          inscription_id: inscription_id(1),
+          satpoint: satpoint(1, 0),
+          indexed: None,
+        },
+        Problem::StaleSatpoint {
+          satpoint: satpoint(2, 0),
+          inscription_id: inscription_id(2),
+        },
+      ]
+    );
+
+    let report = context.index.verify(true).unwrap();
+    assert_eq!(report.repaired, ["SATPOINT_TO_INSCRIPTION_ID"]);
+    assert!(report.ok);
+
+    assert!(context.index.verify(false).unwrap().ok);
+  }
+
+  #[test]
+  fn shared_satpoints_are_not_problems_and_survive_repair() {
+    let context = Context::builder().build();
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      let mut id_to_satpoint = wtx.open_table(INSCRIPTION_ID_TO_SATPOINT).unwrap();
+      id_to_satpoint
+        .insert(&inscription_id(1).store(), &satpoint(1, 0).store())
+        .unwrap();
+      id_to_satpoint
+        .insert(&inscription_id(2).store(), &satpoint(1, 0).store())
+        .unwrap();
+      id_to_satpoint
+        .insert(&inscription_id(3).store(), &satpoint(3, 0).store())
+        .unwrap();
+      drop(id_to_satpoint);
+      wtx
+        .open_table(SATPOINT_TO_INSCRIPTION_ID)
+        .unwrap()
+        .insert(&satpoint(1, 0).store(), &inscription_id(2).store())
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    assert_eq!(
+      context.index.verify(false).unwrap().problems,
+      [Problem::InscriptionSatpoint {
+        inscription_id: inscription_id(3),
+        satpoint: satpoint(3, 0),
+        indexed: None,
+      }]
+    );
+
+    assert!(context.index.verify(true).unwrap().ok);
+    assert!(context.index.verify(false).unwrap().ok);
+
+    let rtx = context.index.database.begin_read().unwrap();
+    let satpoint_to_id = rtx.open_table(SATPOINT_TO_INSCRIPTION_ID).unwrap();
+
+    assert_eq!(
+      satpoint_to_id
+        .get(&satpoint(1, 0).store())
+        .unwrap()
+        .map(|id| InscriptionId::load(*id.value())),
+      Some(inscription_id(2))
+    );
+    assert_eq!(
+      satpoint_to_id
+        .get(&satpoint(3, 0).store())
+        .unwrap()
+        .map(|id| InscriptionId::load(*id.value())),
+      Some(inscription_id(3))
+    );
+  }
+
+  #[test]
+  fn dev20_balances_must_add_up_to_minted() {
+    let context = Context::builder().build();
+
+    let tick = "doge".parse::<Tick>().unwrap();
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(BIT20_BALANCES)
+        .unwrap()
+        .insert(
+          "balance",
+          bincode::serialize(&Balance {
+            tick: tick.clone(),
+            overall_balance: 100,
+            transferable_balance: 40,
+          })
+          .unwrap()
+          .as_slice(),
+        )
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    assert_eq!(
+      context.index.verify(false).unwrap().problems,
+      [Problem::Dev20Minted {
+        tick: "doge".into(),
+        minted: 0,
+        balances: 100,
+      }]
+    );
+  }
+}
@@ -1,19 +1,55 @@
 use {
-  bitcoin::{
-    blockdata::{opcodes, script},
-    Script,
-  },
+  bitcoin::{blockdata::script, Script},
   std::str,
   super::*,
 };
 
 const PROTOCOL_ID: &[u8] = b"ord";
 
+const MAX_CHUNK_LEN: usize = 240;
+
+/// Content is not decoded past this size, so a small compressed body can't
+/// expand without bound.
+pub(crate) const MAX_DECODED_BODY_SIZE: u64 = 16 << 20;
+
+#[derive(Debug)]
+pub(crate) enum DecodeError {
+  Invalid { encoding: String, err: io::Error },
+  TooLarge { encoding: String },
+  UnknownEncoding { encoding: String },
+}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Invalid { encoding, err } => {
+        write!(
+          f,
+          "failed to decompress {encoding} inscription content: {err}"
+        )
+      }
+      Self::TooLarge { encoding } => write!(
+        f,
+        "{encoding} inscription content decodes to more than {MAX_DECODED_BODY_SIZE} bytes"
+      ),
+      Self::UnknownEncoding { encoding } => {
+        write!(f, "unknown inscription content encoding `{encoding}`")
+      }
+    }
+  }
+}
+
+impl std::error::Error for DecodeError {}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default)]
 pub(crate) struct Inscription {
   pub(crate) body: Option<Vec<u8>>,
+  pub(crate) content_encoding: Option<Vec<u8>>,
   pub(crate) content_type: Option<Vec<u8>>,
   pub(crate) delegate: Option<Vec<u8>>,
+  pub(crate) metadata: Option<Vec<u8>>,
+  pub(crate) metaprotocol: Option<Vec<u8>>,
+  pub(crate) parent: Option<Vec<u8>>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -29,7 +65,11 @@ impl Inscription {
     Self {
       content_type,
       body,
+      content_encoding: None,
       delegate: None,
+      metadata: None,
+      metaprotocol: None,
+      parent: None,
     }
   }
 
@@ -60,12 +100,118 @@ impl Inscription {
 
     Ok(Self {
       body: Some(body),
+      content_encoding: None,
       content_type: Some(content_type.into()),
       delegate: None,
+      metadata: None,
+      metaprotocol: None,
+      parent: None,
     })
   }
 
+  pub(crate) fn compress(&mut self) -> Result {
+    use std::io::Write;
+
+    let Some(body) = &self.body else {
+      return Ok(());
+    };
+
+    let mut brotli = Vec::new();
+    {
+      let mut writer = brotli::CompressorWriter::new(&mut brotli, 4096, 11, 24);
+      writer.write_all(body)?;
+    }
+
+    let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    gzip.write_all(body)?;
+    let gzip = gzip.finish()?;
+
+    let (encoding, compressed) = if brotli.len() <= gzip.len() {
+      ("br", brotli)
+    } else {
+      ("gzip", gzip)
+    };
+
+    // fees are paid per byte, so only keep the encoding if it actually helps
+    if compressed.len() < body.len() {
+      self.body = Some(compressed);
+      self.content_encoding = Some(encoding.into());
+    }
+
+    Ok(())
+  }
+
+  /// Groups of script_sig pushes in the order `InscriptionParser` reads them.
+  /// The first group holds the header, and each following group a countdown
+  /// and a body chunk, so the chain of transactions may break between any
+  /// two groups. Fields ride along with the last chunk.
+  pub(crate) fn script_sig_groups(&self) -> Result<Vec<Vec<Script>>> {
+    let body = self.body.as_deref().unwrap_or_default();
+
+    if body.is_empty() {
+      bail!("inscription body must not be empty");
+    }
+
+    // dogecoind's standard policy requires minimal pushes, so single bytes that
+    // have their own opcode, including field tags, must be pushed with it.
+    let push = |data: &[u8]| {
+      let builder = script::Builder::new();
+      match data {
+        [n @ 1..=16] => builder.push_int((*n).into()),
+        [0x81] => builder.push_int(-1),
+        _ => builder.push_slice(data),
+      }
+      .into_script()
+    };
+    let number = |n: usize| {
+      script::Builder::new()
+        .push_int(n.try_into().unwrap())
+        .into_script()
+    };
+
+    let chunks = body.chunks(MAX_CHUNK_LEN).collect::<Vec<&[u8]>>();
+
+    let mut groups = vec![vec![
+      push(PROTOCOL_ID),
+      number(chunks.len()),
+      push(self.content_type.as_deref().unwrap_or_default()),
+    ]];
+
+    for (i, chunk) in chunks.iter().enumerate() {
+      groups.push(vec![number(chunks.len() - i - 1), push(chunk)]);
+    }
+
+    let fields = groups.last_mut().unwrap();
+
+    if let Some(parent) = &self.parent {
+      fields.extend([push(&Tag::Parent.bytes()), push(parent)]);
+    }
+
+    if let Some(metadata) = &self.metadata {
+      for chunk in metadata.chunks(520) {
+        fields.extend([push(&Tag::Metadata.bytes()), push(chunk)]);
+      }
+    }
+
+    if let Some(metaprotocol) = &self.metaprotocol {
+      fields.extend([push(&Tag::Metaprotocol.bytes()), push(metaprotocol)]);
+    }
+
+    if let Some(content_encoding) = &self.content_encoding {
+      fields.extend([push(&Tag::ContentEncoding.bytes()), push(content_encoding)]);
+    }
+
+    if let Some(delegate) = &self.delegate {
+      fields.extend([push(&Tag::Delegate.bytes()), push(delegate)]);
+    }
+
+    Ok(groups)
+  }
+
+  #[cfg(test)]
   fn append_reveal_script_to_builder(&self, mut builder: script::Builder) -> script::Builder {
+    use bitcoin::blockdata::opcodes;
+
     builder = builder
       .push_opcode(opcodes::OP_FALSE)
       .push_opcode(opcodes::all::OP_IF)
@@ -75,6 +221,28 @@ impl Inscription {
       builder = builder.push_slice(&[1]).push_slice(content_type);
     }
 
+    if let Some(parent) = &self.parent {
+      builder = builder.push_slice(&Tag::Parent.bytes()).push_slice(parent);
+    }
+
+    if let Some(metaprotocol) = &self.metaprotocol {
+      builder = builder
+        .push_slice(&Tag::Metaprotocol.bytes())
+        .push_slice(metaprotocol);
+    }
+
+    if let Some(content_encoding) = &self.content_encoding {
+      builder = builder
+        .push_slice(&Tag::ContentEncoding.bytes())
+        .push_slice(content_encoding);
+    }
+
+    if let Some(metadata) = &self.metadata {
+      for chunk in metadata.chunks(520) {
+        builder = builder.push_slice(&Tag::Metadata.bytes()).push_slice(chunk);
+      }
+    }
+
     if let Some(body) = &self.body {
       builder = builder.push_slice(&[]);
       for chunk in body.chunks(520) {
@@ -85,6 +253,7 @@ impl Inscription {
     builder.push_opcode(opcodes::all::OP_ENDIF)
   }
 
+  #[cfg(test)]
   pub(crate) fn append_reveal_script(&self, builder: script::Builder) -> Script {
     self.append_reveal_script_to_builder(builder).into_script()
   }
@@ -110,6 +279,59 @@ impl Inscription {
     self.body
   }
 
+  /// Removes `encoding`, one of the encodings `compress` picks from, from
+  /// `body`.
+  pub(crate) fn decode(encoding: &str, body: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    use std::io::Read;
+
+    let mut decoded = Vec::new();
+
+    let limit = MAX_DECODED_BODY_SIZE + 1;
+
+    match encoding {
+      "br" => brotli::Decompressor::new(body, 4096)
+        .take(limit)
+        .read_to_end(&mut decoded),
+      "gzip" => flate2::read::GzDecoder::new(body)
+        .take(limit)
+        .read_to_end(&mut decoded),
+      _ => {
+        return Err(DecodeError::UnknownEncoding {
+          encoding: encoding.into(),
+        })
+      }
+    }
+    .map_err(|err| DecodeError::Invalid {
+      encoding: encoding.into(),
+      err,
+    })?;
+
+    if u64::try_from(decoded.len()).unwrap() > MAX_DECODED_BODY_SIZE {
+      return Err(DecodeError::TooLarge {
+        encoding: encoding.into(),
+      });
+    }
+
+    Ok(decoded)
+  }
+
+  /// SHA-256 of the body with its content encoding removed, so the same
+  /// content hashes the same whether or not it was compressed. Bodies that
+  /// can't be decoded are hashed as they are.
+  pub(crate) fn content_sha256(&self) -> Option<sha256::Hash> {
+    let body = self.body()?;
+
+    Some(
+      match self
+        .content_encoding()
+        .map(|encoding| Self::decode(encoding, body))
+      {
+        Some(Ok(decoded)) => sha256::Hash::hash(&decoded),
+        Some(Err(_)) | None => sha256::Hash::hash(body),
+      },
+    )
+  }
+
   pub(crate) fn content_length(&self) -> Option<usize> {
     Some(self.body()?.len())
   }
@@ -118,6 +340,60 @@ impl Inscription {
     Self::inscription_id_field(self.delegate.as_deref())
   }
 
+  pub(crate) fn metadata(&self) -> Option<serde_json::Value> {
+    ciborium::from_reader::<ciborium::Value, _>(self.metadata.as_deref()?)
+      .ok()
+      .map(Self::cbor_to_json)
+  }
+
+  // CBOR is a superset of JSON, so map keys that aren't text are rendered
+  // as their JSON encoding and byte strings as hex
+  fn cbor_to_json(value: ciborium::Value) -> serde_json::Value {
+    use {ciborium::Value as Cbor, serde_json::Value as Json};
+
+    match value {
+      Cbor::Integer(integer) => {
+        let integer = i128::from(integer);
+        if let Ok(integer) = i64::try_from(integer) {
+          Json::from(integer)
+        } else if let Ok(integer) = u64::try_from(integer) {
+          Json::from(integer)
+        } else {
+          Json::String(integer.to_string())
+        }
+      }
+      Cbor::Bytes(bytes) => Json::String(hex::encode(bytes)),
+      Cbor::Float(float) => serde_json::Number::from_f64(float)
+        .map(Json::Number)
+        .unwrap_or(Json::Null),
+      Cbor::Text(text) => Json::String(text),
+      Cbor::Bool(bool) => Json::Bool(bool),
+      Cbor::Tag(_, value) => Self::cbor_to_json(*value),
+      Cbor::Array(array) => Json::Array(array.into_iter().map(Self::cbor_to_json).collect()),
+      Cbor::Map(map) => Json::Object(
+        map
+          .into_iter()
+          .map(|(key, value)| {
+            let key = match Self::cbor_to_json(key) {
+              Json::String(key) => key,
+              key => key.to_string(),
+            };
+            (key, Self::cbor_to_json(value))
+          })
+          .collect(),
+      ),
+      _ => Json::Null,
+    }
+  }
+
+  pub(crate) fn metaprotocol(&self) -> Option<&str> {
+    str::from_utf8(self.metaprotocol.as_ref()?).ok()
+  }
+
+  pub(crate) fn parent(&self) -> Option<InscriptionId> {
+    Self::inscription_id_field(self.parent.as_deref())
+  }
+
   fn inscription_id_field(field: Option<&[u8]>) -> Option<InscriptionId> {
     let value = field.as_ref()?;
 
@@ -153,10 +429,27 @@ impl Inscription {
     Some(InscriptionId { txid, index })
   }
 
+  pub(crate) fn content_encoding(&self) -> Option<&str> {
+    str::from_utf8(self.content_encoding.as_ref()?).ok()
+  }
+
   pub(crate) fn content_type(&self) -> Option<&str> {
     str::from_utf8(self.content_type.as_ref()?).ok()
   }
 
+  #[cfg(test)]
+  pub(crate) fn to_script_sig(&self) -> Script {
+    Script::from(
+      self
+        .script_sig_groups()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .flat_map(|script| script.to_bytes())
+        .collect::<Vec<u8>>(),
+    )
+  }
+
   #[cfg(test)]
   pub(crate) fn to_witness(&self) -> Witness {
     let builder = script::Builder::new();
@@ -240,11 +533,19 @@ impl InscriptionParser {
             }
           }
 
+          let content_encoding = Tag::ContentEncoding.take(&mut fields);
           let delegate = Tag::Delegate.take(&mut fields);
+          let metadata = Tag::Metadata.take(&mut fields);
+          let metaprotocol = Tag::Metaprotocol.take(&mut fields);
+          let parent = Tag::Parent.take(&mut fields);
           let inscription = Inscription {
             content_type: Some(content_type),
             body: Some(body),
+            content_encoding,
             delegate,
+            metadata,
+            metaprotocol,
+            parent,
           };
 
           return ParsedInscription::Complete(inscription);
@@ -316,6 +617,13 @@ impl InscriptionParser {
         continue;
       }
 
+      // op_1negate
+      if bytes[0] == 79 {
+        push_datas.push(vec![0x81]);
+        bytes = &bytes[1..];
+        continue;
+      }
+
       // op_push 1-75
       if bytes[0] >= 1 && bytes[0] <= 75 {
         let len = bytes[0] as usize;
@@ -421,12 +729,308 @@ mod tests {
       InscriptionParser::parse(vec![Script::from(script.concat())]),
       ParsedInscription::Complete(Inscription {
         body: Some(vec![]),
+        content_encoding: None,
         content_type: Some(vec![]),
-        delegate: Some(vec![0; 32])
+        delegate: Some(vec![0; 32]),
+        metadata: None,
+        metaprotocol: None,
+        parent: None,
       })
     );
   }
 
+  #[test]
+  fn valid_with_parent() {
+    let parent = [[0x11; 32].as_slice(), &[1]].concat();
+    let mut script: Vec<&[u8]> = Vec::new();
+    script.push(&[3]);
+    script.push(b"ord");
+    script.push(&[81]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[83]);
+    script.push(&[33]);
+    script.push(&parent);
+
+    let ParsedInscription::Complete(inscription) =
+      InscriptionParser::parse(vec![Script::from(script.concat())])
+    else {
+      panic!("expected complete inscription");
+    };
+
+    assert_eq!(inscription.parent(), Some(inscription_id(1)));
+  }
+
+  #[test]
+  fn invalid_parent_is_ignored() {
+    let mut script: Vec<&[u8]> = Vec::new();
+    script.push(&[3]);
+    script.push(b"ord");
+    script.push(&[81]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[83]);
+    script.push(&[3]);
+    script.push(&[1, 2, 3]);
+
+    let ParsedInscription::Complete(inscription) =
+      InscriptionParser::parse(vec![Script::from(script.concat())])
+    else {
+      panic!("expected complete inscription");
+    };
+
+    assert_eq!(inscription.parent, Some(vec![1, 2, 3]));
+    assert_eq!(inscription.parent(), None);
+  }
+
+  #[test]
+  fn valid_with_chunked_metadata() {
+    let mut metadata = Vec::new();
+    ciborium::into_writer(&serde_json::json!({"trait": "woof"}), &mut metadata).unwrap();
+    let (first, second) = metadata.split_at(5);
+
+    let mut script: Vec<&[u8]> = Vec::new();
+    script.push(&[3]);
+    script.push(b"ord");
+    script.push(&[81]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[85]);
+    let first_len = [u8::try_from(first.len()).unwrap()];
+    script.push(&first_len);
+    script.push(first);
+    script.push(&[85]);
+    let second_len = [u8::try_from(second.len()).unwrap()];
+    script.push(&second_len);
+    script.push(second);
+
+    let ParsedInscription::Complete(inscription) =
+      InscriptionParser::parse(vec![Script::from(script.concat())])
+    else {
+      panic!("expected complete inscription");
+    };
+
+    assert_eq!(inscription.metadata, Some(metadata));
+    assert_eq!(
+      inscription.metadata(),
+      Some(serde_json::json!({"trait": "woof"}))
+    );
+  }
+
+  #[test]
+  fn metadata_with_non_text_keys() {
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &ciborium::Value::Map(vec![
+        (ciborium::Value::from(1), ciborium::Value::Bytes(vec![0xab])),
+        (
+          ciborium::Value::from("a"),
+          ciborium::Value::Array(vec![ciborium::Value::Bool(true), ciborium::Value::Null]),
+        ),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(metadata),
+        ..Default::default()
+      }
+      .metadata(),
+      Some(serde_json::json!({"1": "ab", "a": [true, null]}))
+    );
+  }
+
+  #[test]
+  fn invalid_metadata_is_ignored() {
+    assert_eq!(
+      Inscription {
+        metadata: Some(vec![0xff]),
+        ..Default::default()
+      }
+      .metadata(),
+      None
+    );
+  }
+
+  #[test]
+  fn valid_with_content_encoding() {
+    let mut script: Vec<&[u8]> = Vec::new();
+    script.push(&[3]);
+    script.push(b"ord");
+    script.push(&[81]);
+    script.push(&[9]);
+    script.push(b"text/html");
+    script.push(&[0]);
+    script.push(&[4]);
+    script.push(b"woof");
+    script.push(&[89]);
+    script.push(&[2]);
+    script.push(b"br");
+
+    let ParsedInscription::Complete(inscription) =
+      InscriptionParser::parse(vec![Script::from(script.concat())])
+    else {
+      panic!("expected complete inscription");
+    };
+
+    assert_eq!(inscription.content_encoding(), Some("br"));
+    assert_eq!(inscription.body(), Some(b"woof".as_slice()));
+  }
+
+  #[test]
+  fn compress_picks_smaller_encoding() {
+    let body = "woof ".repeat(100);
+
+    let mut inscription = inscription("text/html", &body);
+    inscription.compress().unwrap();
+
+    assert_eq!(inscription.content_encoding(), Some("br"));
+    assert!(inscription.content_length().unwrap() < body.len());
+
+    let mut decompressed = Vec::new();
+    std::io::Read::read_to_end(
+      &mut brotli::Decompressor::new(inscription.body().unwrap(), 4096),
+      &mut decompressed,
+    )
+    .unwrap();
+    assert_eq!(decompressed, body.as_bytes());
+  }
+
+  #[test]
+  fn compress_skips_incompressible_body() {
+    let mut inscription = inscription("text/plain", "a");
+    inscription.compress().unwrap();
+
+    assert_eq!(inscription.content_encoding, None);
+    assert_eq!(inscription.body(), Some(b"a".as_slice()));
+  }
+
+  #[test]
+  fn valid_with_metaprotocol() {
+    let mut script: Vec<&[u8]> = Vec::new();
+    script.push(&[3]);
+    script.push(b"ord");
+    script.push(&[81]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[0]);
+    script.push(&[87]);
+    script.push(&[6]);
+    script.push(b"dev-20");
+
+    let ParsedInscription::Complete(inscription) =
+      InscriptionParser::parse(vec![Script::from(script.concat())])
+    else {
+      panic!("expected complete inscription");
+    };
+
+    assert_eq!(inscription.metaprotocol(), Some("dev-20"));
+  }
+
+  fn script_sigs(groups: &[Vec<Script>]) -> Vec<Script> {
+    groups
+      .iter()
+      .map(|group| {
+        Script::from(
+          group
+            .iter()
+            .flat_map(|push| push.as_bytes().to_vec())
+            .collect::<Vec<u8>>(),
+        )
+      })
+      .collect()
+  }
+
+  #[test]
+  fn script_sig_groups_round_trip() {
+    let inscription = Inscription {
+      body: Some(vec![1; 1000]),
+      content_encoding: Some("br".into()),
+      content_type: Some("text/plain".into()),
+      delegate: None,
+      metadata: Some(vec![2; 600]),
+      metaprotocol: Some("dev-20".into()),
+      parent: Some(inscription_id(1).value()),
+    };
+
+    let groups = inscription.script_sig_groups().unwrap();
+
+    assert_eq!(groups.len(), 6);
+    assert_eq!(groups[0].len(), 3);
+    assert_eq!(groups[1].len(), 2);
+    assert_eq!(groups[5].len(), 12);
+
+    let one_script = vec![Script::from(
+      script_sigs(&groups)
+        .iter()
+        .flat_map(|script| script.as_bytes().to_vec())
+        .collect::<Vec<u8>>(),
+    )];
+
+    assert_eq!(
+      InscriptionParser::parse(one_script),
+      ParsedInscription::Complete(inscription.clone())
+    );
+
+    let mut scripts = script_sigs(&groups);
+    let first = Script::from([scripts[0].to_bytes(), scripts[1].to_bytes()].concat());
+    scripts.splice(0..2, [first]);
+
+    assert_eq!(
+      InscriptionParser::parse(scripts),
+      ParsedInscription::Complete(inscription)
+    );
+  }
+
+  #[test]
+  fn script_sig_groups_use_minimal_pushes() {
+    let mut body = vec![0x42; MAX_CHUNK_LEN];
+    body.push(0x81);
+
+    let inscription = Inscription {
+      body: Some(body),
+      content_encoding: Some("br".into()),
+      content_type: Some("text/plain".into()),
+      delegate: Some(inscription_id(2).value()),
+      metadata: Some(vec![16]),
+      metaprotocol: Some("7".into()),
+      parent: Some(inscription_id(1).value()),
+    };
+
+    let groups = inscription.script_sig_groups().unwrap();
+
+    for script in script_sigs(&groups) {
+      assert!(script.is_push_only(), "{script}");
+      assert!(
+        script
+          .instructions_minimal()
+          .all(|instruction| instruction.is_ok()),
+        "{script}"
+      );
+    }
+
+    assert_eq!(
+      InscriptionParser::parse(script_sigs(&groups)),
+      ParsedInscription::Complete(inscription)
+    );
+  }
+
+  #[test]
+  fn script_sig_groups_require_body() {
+    assert_eq!(
+      inscription("text/plain", [])
+        .script_sig_groups()
+        .unwrap_err()
+        .to_string(),
+      "inscription body must not be empty"
+    );
+  }
+
   #[test]
   fn empty() {
     assert_eq!(
@@ -15,6 +15,24 @@ impl Default for InscriptionId {
   }
 }
 
+impl InscriptionId {
+  pub(crate) fn value(self) -> Vec<u8> {
+    let index = self.index.to_le_bytes();
+    let mut index_slice = index.as_slice();
+
+    while index_slice.last().copied() == Some(0) {
+      index_slice = &index_slice[0..index_slice.len() - 1];
+    }
+
+    self
+      .txid
+      .into_inner()
+      .into_iter()
+      .chain(index_slice.iter().copied())
+      .collect()
+  }
+}
+
 impl<'de> Deserialize<'de> for InscriptionId {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where
@@ -156,6 +174,26 @@ mod tests {
     );
   }
 
+  #[test]
+  fn value() {
+    assert_eq!(
+      InscriptionId {
+        txid: txid(1),
+        index: 0,
+      }
+      .value(),
+      vec![0x11; 32],
+    );
+    assert_eq!(
+      InscriptionId {
+        txid: txid(1),
+        index: 0x0100,
+      }
+      .value(),
+      [[0x11; 32].as_slice(), &[0x00, 0x01]].concat(),
+    );
+  }
+
   #[test]
   fn from_str_bad_character() {
     assert_matches!(
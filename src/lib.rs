@@ -19,7 +19,7 @@ use {
     deserialize_from_str::DeserializeFromStr,
     epoch::Epoch,
     height::Height,
-    index::{Index, List, DuneEntry},
+    index::{AddressActivity, DuneEntry, DuneEvent, Index, InscriptionTransfer, List},
     inscription::Inscription,
     inscription_id::InscriptionId,
     tag::Tag,
@@ -37,7 +37,7 @@ use {
   bitcoin::{
     consensus::{self, Decodable, Encodable},
     hash_types::BlockHash,
-    hashes::Hash,
+    hashes::{sha256, Hash},
     blockdata::opcodes,
     blockdata::script::{self, Instruction},
     Address, Amount, Block, Network, OutPoint, Script, Sequence, Transaction, TxIn, TxOut, Txid,
@@ -118,6 +118,7 @@ mod inscription;
 mod inscription_id;
 mod tag;
 mod media;
+mod mempool;
 mod object;
 mod options;
 mod outgoing;
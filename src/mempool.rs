@@ -0,0 +1,449 @@
+use {
+  super::*,
+  crate::{
+    bit20::{
+      operation::{deserialize_bit20_operation, Action, Operation},
+      Deploy, Mint, Transfer,
+    },
+    index::Direction,
+    inscription::ParsedInscription,
+  },
+  std::sync::RwLock,
+};
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub(crate) enum PendingDev20Operation {
+  Deploy(Deploy),
+  Mint(Mint),
+  Transfer(Transfer),
+}
+
+impl From<Operation> for PendingDev20Operation {
+  fn from(operation: Operation) -> Self {
+    match operation {
+      Operation::Deploy(deploy) => Self::Deploy(deploy),
+      Operation::Mint(mint) => Self::Mint(mint),
+      Operation::InscribeTransfer(transfer) | Operation::Transfer(transfer) => {
+        Self::Transfer(transfer)
+      }
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PendingAction {
+  New,
+  Transfer,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub(crate) struct PendingInscription {
+  pub(crate) action: PendingAction,
+  pub(crate) content_length: Option<usize>,
+  pub(crate) content_type: Option<String>,
+  pub(crate) destination: Option<String>,
+  pub(crate) dev20: Option<PendingDev20Operation>,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub(crate) struct PendingDune {
+  pub(crate) dunestone: Dunestone,
+  pub(crate) txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub(crate) struct PendingActivity {
+  pub(crate) direction: Direction,
+  pub(crate) txid: Txid,
+  pub(crate) value: u64,
+}
+
+struct MempoolEntry {
+  activity: Vec<(String, Direction, u64)>,
+  dune: Option<PendingDune>,
+  inscriptions: Vec<PendingInscription>,
+  partial: Option<Vec<Transaction>>,
+  transaction: Transaction,
+}
+
+/// In-memory view of unconfirmed transactions, rebuilt by polling the node.
+/// Nothing here is persisted; confirmed state always comes from the index.
+pub(crate) struct Mempool {
+  enabled: bool,
+  entries: RwLock<HashMap<Txid, Arc<MempoolEntry>>>,
+}
+
+impl Mempool {
+  pub(crate) fn new(enabled: bool) -> Self {
+    Self {
+      enabled,
+      entries: RwLock::new(HashMap::new()),
+    }
+  }
+
+  pub(crate) fn is_enabled(&self) -> bool {
+    self.enabled
+  }
+
+  pub(crate) fn update(&self, index: &Index) -> Result {
+    if !self.enabled {
+      return Ok(());
+    }
+
+    let txids = index
+      .get_raw_mempool()?
+      .into_iter()
+      .collect::<HashSet<Txid>>();
+
+    // The node is queried without holding the lock, so readers only wait for
+    // the finished entries to be swapped in.
+    let mut entries = self
+      .entries
+      .read()
+      .unwrap()
+      .iter()
+      .filter(|(txid, _)| txids.contains(txid))
+      .map(|(txid, entry)| (*txid, entry.clone()))
+      .collect::<HashMap<Txid, Arc<MempoolEntry>>>();
+
+    let mut pending = HashMap::new();
+
+    for txid in txids {
+      if entries.contains_key(&txid) {
+        continue;
+      }
+
+      // transactions can leave the mempool between the two calls
+      if let Some(transaction) = index.get_transaction(txid)? {
+        pending.insert(txid, transaction);
+      }
+    }
+
+    while let Some(txid) = pending.keys().next().copied() {
+      Self::process(index, &mut entries, &mut pending, txid)?;
+    }
+
+    *self.entries.write().unwrap() = entries;
+
+    Ok(())
+  }
+
+  fn process(
+    index: &Index,
+    entries: &mut HashMap<Txid, Arc<MempoolEntry>>,
+    pending: &mut HashMap<Txid, Transaction>,
+    txid: Txid,
+  ) -> Result {
+    let Some(transaction) = pending.remove(&txid) else {
+      return Ok(());
+    };
+
+    // parents in the mempool are processed first, so that inscription chains
+    // and input addresses can be resolved from their entries
+    for input in &transaction.input {
+      if pending.contains_key(&input.previous_output.txid) {
+        Self::process(index, entries, pending, input.previous_output.txid)?;
+      }
+    }
+
+    let mut activity = Vec::new();
+    let mut inscriptions = Vec::new();
+    let mut offset = 0;
+
+    for input in &transaction.input {
+      let previous_output = input.previous_output;
+
+      if previous_output.is_null() {
+        continue;
+      }
+
+      let output = match entries.get(&previous_output.txid) {
+        Some(entry) => entry
+          .transaction
+          .output
+          .get(usize::try_from(previous_output.vout).unwrap())
+          .cloned(),
+        None => index.get_transaction(previous_output.txid)?.and_then(|tx| {
+          tx.output
+            .into_iter()
+            .nth(usize::try_from(previous_output.vout).unwrap())
+        }),
+      };
+
+      let Some(output) = output else {
+        continue;
+      };
+
+      if let Ok(address) = index.chain.address_from_script(&output.script_pubkey) {
+        activity.push((address.to_string(), Direction::Sent, output.value));
+      }
+
+      for (satpoint, inscription_id) in
+        index.get_inscription_satpoints_on_output(previous_output)?
+      {
+        let dev20 = index
+          .get_inscription_by_id(inscription_id)?
+          .and_then(|inscription| deserialize_bit20_operation(&inscription, &Action::Transfer).ok())
+          .map(PendingDev20Operation::from);
+
+        inscriptions.push(PendingInscription {
+          action: PendingAction::Transfer,
+          content_length: None,
+          content_type: None,
+          destination: Self::destination(index, &transaction, offset + satpoint.offset),
+          dev20,
+          inscription_id,
+          txid,
+        });
+      }
+
+      offset += output.value;
+    }
+
+    let previous_txid = transaction
+      .input
+      .first()
+      .map(|input| input.previous_output.txid);
+
+    let mut chain = match previous_txid {
+      Some(previous_txid) => match entries
+        .get(&previous_txid)
+        .and_then(|entry| entry.partial.clone())
+      {
+        Some(chain) => chain,
+        None => index.get_partial_inscription_transactions(previous_txid)?,
+      },
+      None => Vec::new(),
+    };
+
+    chain.push(transaction.clone());
+
+    let inscription_txid = chain[0].txid();
+
+    let partial = match Inscription::from_transactions(chain.clone()) {
+      ParsedInscription::None => None,
+      ParsedInscription::Partial => Some(chain),
+      ParsedInscription::Complete(inscription) => {
+        let dev20 = deserialize_bit20_operation(
+          &inscription,
+          &Action::New {
+            inscription: inscription.clone(),
+          },
+        )
+        .ok()
+        .map(PendingDev20Operation::from);
+
+        inscriptions.push(PendingInscription {
+          action: PendingAction::New,
+          content_length: inscription.content_length(),
+          content_type: inscription.content_type().map(str::to_string),
+          destination: Self::destination(index, &transaction, 0),
+          dev20,
+          inscription_id: InscriptionId {
+            txid: inscription_txid,
+            index: 0,
+          },
+          txid,
+        });
+
+        None
+      }
+    };
+
+    for output in &transaction.output {
+      if let Ok(address) = index.chain.address_from_script(&output.script_pubkey) {
+        activity.push((address.to_string(), Direction::Received, output.value));
+      }
+    }
+
+    let dune =
+      Dunestone::from_transaction(&transaction).map(|dunestone| PendingDune { dunestone, txid });
+
+    entries.insert(
+      txid,
+      Arc::new(MempoolEntry {
+        activity,
+        dune,
+        inscriptions,
+        partial,
+        transaction,
+      }),
+    );
+
+    Ok(())
+  }
+
+  fn destination(index: &Index, transaction: &Transaction, offset: u64) -> Option<String> {
+    let mut end = 0;
+
+    for output in &transaction.output {
+      end += output.value;
+
+      if offset < end {
+        return index
+          .chain
+          .address_from_script(&output.script_pubkey)
+          .ok()
+          .map(|address| address.to_string());
+      }
+    }
+
+    None
+  }
+
+  pub(crate) fn inscriptions(&self) -> Vec<PendingInscription> {
+    let mut inscriptions = self
+      .entries
+      .read()
+      .unwrap()
+      .values()
+      .flat_map(|entry| entry.inscriptions.iter().cloned())
+      .collect::<Vec<PendingInscription>>();
+
+    inscriptions.sort_by_key(|inscription| (inscription.txid, inscription.inscription_id));
+
+    inscriptions
+  }
+
+  pub(crate) fn dunes(&self) -> Vec<PendingDune> {
+    let mut dunes = self
+      .entries
+      .read()
+      .unwrap()
+      .values()
+      .filter_map(|entry| entry.dune.clone())
+      .collect::<Vec<PendingDune>>();
+
+    dunes.sort_by_key(|dune| dune.txid);
+
+    dunes
+  }
+
+  pub(crate) fn address(&self, address: &str) -> (Vec<PendingActivity>, Vec<PendingInscription>) {
+    let entries = self.entries.read().unwrap();
+
+    let mut activity = Vec::new();
+    let mut inscriptions = Vec::new();
+
+    for (txid, entry) in entries.iter() {
+      for (activity_address, direction, value) in &entry.activity {
+        if activity_address == address {
+          activity.push(PendingActivity {
+            direction: *direction,
+            txid: *txid,
+            value: *value,
+          });
+        }
+      }
+
+      inscriptions.extend(
+        entry
+          .inscriptions
+          .iter()
+          .filter(|inscription| inscription.destination.as_deref() == Some(address))
+          .cloned(),
+      );
+    }
+
+    activity.sort_by_key(|activity| activity.txid);
+    inscriptions.sort_by_key(|inscription| (inscription.txid, inscription.inscription_id));
+
+    (activity, inscriptions)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn disabled_mempool_is_not_polled() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0)],
+      ..Default::default()
+    });
+
+    let mempool = Mempool::new(false);
+    mempool.update(&context.index).unwrap();
+
+    assert!(mempool.entries.read().unwrap().is_empty());
+  }
+
+  #[test]
+  fn confirmed_transactions_are_evicted() {
+    let context = Context::builder().build();
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0)],
+      ..Default::default()
+    });
+
+    let mempool = Mempool::new(true);
+    mempool.update(&context.index).unwrap();
+
+    assert!(mempool.entries.read().unwrap().contains_key(&txid));
+    assert!(mempool.inscriptions().is_empty());
+    assert!(mempool.dunes().is_empty());
+
+    context.mine_blocks(1);
+    mempool.update(&context.index).unwrap();
+
+    assert!(mempool.entries.read().unwrap().is_empty());
+  }
+
+  #[test]
+  fn address_returns_pending_activity_and_destined_inscriptions() {
+    let mempool = Mempool::new(true);
+
+    let inscription = |destination: &str| PendingInscription {
+      action: PendingAction::Transfer,
+      content_length: None,
+      content_type: None,
+      destination: Some(destination.into()),
+      dev20: None,
+      inscription_id: inscription_id(1),
+      txid: txid(1),
+    };
+
+    mempool.entries.write().unwrap().insert(
+      txid(1),
+      Arc::new(MempoolEntry {
+        activity: vec![
+          ("foo".into(), Direction::Sent, 100),
+          ("bar".into(), Direction::Received, 90),
+        ],
+        dune: None,
+        inscriptions: vec![inscription("bar"), inscription("baz")],
+        partial: None,
+        transaction: Transaction {
+          version: 1,
+          lock_time: bitcoin::PackedLockTime::ZERO,
+          input: Vec::new(),
+          output: Vec::new(),
+        },
+      }),
+    );
+
+    assert_eq!(
+      mempool.address("bar"),
+      (
+        vec![PendingActivity {
+          direction: Direction::Received,
+          txid: txid(1),
+          value: 90,
+        }],
+        vec![inscription("bar")],
+      )
+    );
+
+    assert_eq!(mempool.address("qux"), (Vec::new(), Vec::new()));
+  }
+}
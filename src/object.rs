@@ -1,6 +1,6 @@
 use crate::sat::Sat;
 use crate::sat_point::SatPoint;
-use super::*;
+use {super::*, clap::ValueEnum};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Object {
@@ -21,7 +21,12 @@ impl FromStr for Object {
     use Representation::*;
 
     match Representation::from_str(s)? {
-      Address => Ok(Self::Address(s.parse()?)),
+      Address => Ok(Self::Address(
+        Chain::value_variants()
+          .iter()
+          .find_map(|chain| chain.address_from_str(s).ok())
+          .ok_or_else(|| anyhow!("address {s} is not valid on any chain"))?,
+      )),
       Decimal => Ok(Self::Sat(s.parse()?)),
       Hash => Ok(Self::Hash(
         bitcoin::hashes::sha256::Hash::from_str(s)?.into_inner(),
@@ -196,4 +201,28 @@ mod tests {
     case("A", Object::Dune(Dune(0)));
     case("B", Object::Dune(Dune(1)));
   }
+
+  #[test]
+  fn base58_addresses() {
+    assert_eq!(
+      "D597kHXGdkwkryF9oGhz9Bp1ypTpD1u99Z"
+        .parse::<Object>()
+        .unwrap(),
+      Object::Address(
+        Chain::Mainnet
+          .address_from_str("D597kHXGdkwkryF9oGhz9Bp1ypTpD1u99Z")
+          .unwrap()
+      ),
+    );
+    assert_eq!(
+      "2MsFFCK16VhsCcvPXruztdzzcTZEQCbNKjJ"
+        .parse::<Object>()
+        .unwrap(),
+      Object::Address(
+        Chain::Testnet
+          .address_from_str("2MsFFCK16VhsCcvPXruztdzzcTZEQCbNKjJ")
+          .unwrap()
+      ),
+    );
+  }
 }
@@ -45,6 +45,11 @@ pub(crate) struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Track activity history of all addresses, starting at <FIRST_INSCRIPTION_HEIGHT>."
+  )]
+  pub(crate) index_addresses: bool,
   #[arg(long, help = "Track bit20 tokens and balances.")]
   #[arg(long = "index-b1t20", alias = "index-bit20")]
   pub(crate) index_b1t20: bool,
@@ -55,10 +60,28 @@ pub(crate) struct Options {
   pub(crate) index_dunes: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
+  #[arg(long, help = "Index words in text inscriptions for full-text search.")]
+  pub(crate) index_text: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Keep at most <MAX_SAVEPOINTS> savepoints to roll back to after a reorg. [default: 5]"
+  )]
+  pub(crate) max_savepoints: Option<u32>,
+  #[arg(
+    long,
+    help = "Read blocks from Dogecoin Core's blk*.dat files during initial sync, instead of over RPC."
+  )]
+  pub(crate) read_block_files: bool,
   #[arg(long, short, help = "Use regtest. Equivalent to `--chain regtest`.")]
   pub(crate) regtest: bool,
+  #[arg(
+    long,
+    help = "Roll back to the newest savepoint still on the active chain, or rebuild the index from genesis if there is none, after an unrecoverable reorg, instead of stopping."
+  )]
+  pub(crate) reorg_auto_rollback: bool,
   #[arg(long, help = "Connect to Dogecoin Core RPC at <RPC_URL>.")]
   pub(crate) rpc_url: Option<String>,
   #[arg(
@@ -66,6 +89,17 @@ pub(crate) struct Options {
   help = "Number of parallel requests to dogecoin node."
   )]
   pub(crate) nr_parallel_requests: Option<usize>,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Create a savepoint every <SAVEPOINT_INTERVAL> blocks. [default: 10]"
+  )]
+  pub(crate) savepoint_interval: Option<u32>,
+  #[arg(
+    long,
+    help = "Only create savepoints within <SAVEPOINT_TIP_DISTANCE> blocks of the chain tip. [default: 25]"
+  )]
+  pub(crate) savepoint_tip_distance: Option<u32>,
   #[arg(long, short, help = "Use signet. Equivalent to `--chain signet`.")]
   pub(crate) signet: bool,
   #[arg(long, short, help = "Use testnet. Equivalent to `--chain testnet`.")]
@@ -133,11 +167,31 @@ impl Options {
     self.nr_parallel_requests.clone().unwrap_or(12)
   }
 
+  pub(crate) fn max_savepoints(&self) -> u32 {
+    self.max_savepoints.unwrap_or(5)
+  }
+
+  pub(crate) fn savepoint_interval(&self) -> u32 {
+    self.savepoint_interval.unwrap_or(10)
+  }
+
+  pub(crate) fn savepoint_tip_distance(&self) -> u32 {
+    self.savepoint_tip_distance.unwrap_or(25)
+  }
+
   pub(crate) fn cookie_file(&self) -> Result<PathBuf> {
     if let Some(cookie_file) = &self.cookie_file {
       return Ok(cookie_file.clone());
     }
 
+    Ok(self.dogecoin_data_dir()?.join(".cookie"))
+  }
+
+  pub(crate) fn dogecoin_blocks_dir(&self) -> Result<PathBuf> {
+    Ok(self.dogecoin_data_dir()?.join("blocks"))
+  }
+
+  fn dogecoin_data_dir(&self) -> Result<PathBuf> {
     let path = if let Some(dogecoin_data_dir) = &self.dogecoin_data_dir {
       dogecoin_data_dir.clone()
     } else if cfg!(target_os = "linux") {
@@ -150,9 +204,7 @@ impl Options {
         .join("Dogecoin")
     };
 
-    let path = self.chain().join_with_data_dir(&path);
-
-    Ok(path.join(".cookie"))
+    Ok(self.chain().join_with_data_dir(&path))
   }
 
   pub(crate) fn data_dir(&self) -> Result<PathBuf> {
@@ -166,6 +218,13 @@ impl Options {
     Ok(self.chain().join_with_data_dir(&base))
   }
 
+  pub(crate) fn index_path(&self) -> Result<PathBuf> {
+    match &self.index {
+      Some(path) => Ok(path.clone()),
+      None => Ok(self.data_dir()?.join("index.redb")),
+    }
+  }
+
   pub(crate) fn load_config(&self) -> Result<Config> {
     match &self.config {
       Some(path) => Ok(serde_yaml::from_reader(File::open(path)?)?),
@@ -383,6 +442,33 @@ mod tests {
     }));
   }
 
+  #[test]
+  fn blocks_dir_is_in_dogecoin_data_dir() {
+    let arguments = Arguments::try_parse_from([
+      "ord",
+      "--dogecoin-data-dir=foo",
+      "--chain=testnet",
+      "--read-block-files",
+      "index",
+    ])
+    .unwrap();
+
+    assert!(arguments.options.read_block_files);
+
+    let blocks_dir = arguments
+      .options
+      .dogecoin_blocks_dir()
+      .unwrap()
+      .display()
+      .to_string();
+
+    assert!(blocks_dir.ends_with(if cfg!(windows) {
+      r"foo\testnet3\blocks"
+    } else {
+      "foo/testnet3/blocks"
+    }));
+  }
+
   #[test]
   fn mainnet_data_dir() {
     let data_dir = Arguments::try_parse_from(["ord", "index"])
@@ -583,6 +669,36 @@ mod tests {
     )
   }
 
+  #[test]
+  fn savepoint_settings() {
+    let options = Arguments::try_parse_from(["ord", "index", "update"])
+      .unwrap()
+      .options;
+
+    assert_eq!(options.max_savepoints(), 5);
+    assert_eq!(options.savepoint_interval(), 10);
+    assert_eq!(options.savepoint_tip_distance(), 25);
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--max-savepoints=2",
+      "--savepoint-interval=3",
+      "--savepoint-tip-distance=4",
+      "index",
+      "update",
+    ])
+    .unwrap()
+    .options;
+
+    assert_eq!(options.max_savepoints(), 2);
+    assert_eq!(options.savepoint_interval(), 3);
+    assert_eq!(options.savepoint_tip_distance(), 4);
+
+    assert!(
+      Arguments::try_parse_from(["ord", "--savepoint-interval=0", "index", "update"]).is_err()
+    );
+  }
+
   #[test]
   fn default_config_is_returned_if_config_option_is_not_passed() {
     assert_eq!(
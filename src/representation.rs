@@ -1,4 +1,4 @@
-use {super::*, regex::RegexSet};
+use {super::*, clap::ValueEnum, regex::RegexSet};
 
 #[derive(Debug, Copy, Clone)]
 pub(crate) enum Representation {
@@ -34,6 +34,13 @@ impl FromStr for Representation {
   type Err = Error;
 
   fn from_str(s: &str) -> Result<Self> {
+    if Chain::value_variants()
+      .iter()
+      .any(|chain| chain.address_from_str(s).is_ok())
+    {
+      return Ok(Self::Address);
+    }
+
     if let Some(i) = REGEX_SET.matches(s).into_iter().next() {
       Ok(PATTERNS[i].0)
     } else {
@@ -68,4 +75,16 @@ mod tests {
       .iter()
       .all(|(_representation, pattern)| pattern.starts_with('^') && pattern.ends_with('$')));
   }
+
+  #[test]
+  fn base58_addresses_are_recognized() {
+    assert!(matches!(
+      "D597kHXGdkwkryF9oGhz9Bp1ypTpD1u99Z".parse::<Representation>(),
+      Ok(Representation::Address)
+    ));
+    assert!(matches!(
+      "D597kHXGdkwkryF9oGhz9Bp1ypTpD1u99a".parse::<Representation>(),
+      Err(_)
+    ));
+  }
 }
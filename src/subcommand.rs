@@ -1,8 +1,11 @@
 use super::*;
 
+pub mod address;
 pub mod balances;
+pub mod decode;
 pub mod epochs;
 pub mod find;
+pub mod find_duplicates;
 mod index;
 pub mod info;
 pub mod list;
@@ -22,14 +25,20 @@ fn print_json(output: impl Serialize) -> Result {
 
 #[derive(Debug, Parser)]
 pub(crate) enum Subcommand {
+  #[command(about = "List an address's transaction history")]
+  Address(address::AddressHistory),
   #[command(about = "List all dune balances")]
   Balances,
+  #[command(about = "Decode a transaction's dunestone and explain why it is a cenotaph")]
+  Decode(decode::Decode),
   #[command(about = "List the first satoshis of each reward epoch")]
   Epochs,
   #[command(about = "Find a satoshi's current location")]
   Find(find::Find),
-  #[command(about = "Update the index")]
-  Index,
+  #[command(about = "List inscriptions whose content duplicates an earlier inscription")]
+  FindDuplicates,
+  #[command(about = "Update, export or import the index")]
+  Index(index::IndexCommand),
   #[command(about = "Display index statistics")]
   Info(info::Info),
   #[command(about = "List the satoshis in an output")]
@@ -39,7 +48,7 @@ pub(crate) enum Subcommand {
   #[command(about = "Run an explorer server populated with inscriptions")]
   Preview(preview::Preview),
   #[command(about = "List all dunes")]
-  Dunes,
+  Dunes(dunes::Dunes),
   #[command(about = "Run the explorer server")]
   Server(server::Server),
   #[command(about = "Display information about a block's subsidy")]
@@ -53,15 +62,18 @@ pub(crate) enum Subcommand {
 impl Subcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
+      Self::Address(address) => address.run(options),
       Self::Balances => balances::run(options),
+      Self::Decode(decode) => decode.run(options),
       Self::Epochs => epochs::run(),
       Self::Find(find) => find.run(options),
-      Self::Index => index::run(options),
+      Self::FindDuplicates => find_duplicates::run(options),
+      Self::Index(index) => index.run(options),
       Self::Info(info) => info.run(options),
       Self::List(list) => list.run(options),
       Self::Parse(parse) => parse.run(),
       Self::Preview(preview) => preview.run(),
-      Self::Dunes => dunes::run(options),
+      Self::Dunes(dunes) => dunes.run(options),
       Self::Server(server) => {
         let index = Arc::new(Index::open(&options)?);
         let handle = axum_server::Handle::new();
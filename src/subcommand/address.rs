@@ -0,0 +1,49 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct AddressHistory {
+  #[clap(help = "List transactions that sent from or received to <ADDRESS>.")]
+  address: Address,
+  #[clap(long, default_value = "0", help = "Show page <PAGE> of results.")]
+  page: usize,
+  #[clap(
+    long,
+    default_value = "100",
+    help = "Show <LIMIT> transactions per page."
+  )]
+  limit: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Output {
+  pub(crate) address: String,
+  pub(crate) more: bool,
+  pub(crate) page: usize,
+  pub(crate) transactions: Vec<AddressActivity>,
+}
+
+impl AddressHistory {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    ensure!(
+      index.has_address_index(),
+      "`ord address` requires index created with `--index-addresses` flag",
+    );
+
+    ensure!(self.limit > 0, "limit must be greater than zero");
+
+    index.update()?;
+
+    let address = self.address.to_string();
+
+    let (transactions, more) = index.get_address_activity(&address, self.page, self.limit)?;
+
+    Ok(Box::new(Output {
+      address,
+      more,
+      page: self.page,
+      transactions,
+    }))
+  }
+}
@@ -0,0 +1,165 @@
+use {super::*, crate::dunes::Decoded};
+
+#[derive(Debug, Parser)]
+#[command(group(
+  ArgGroup::new("source")
+    .required(true)
+    .args(&["txid", "file"]),
+))]
+pub(crate) struct Decode {
+  #[arg(long, help = "Fetch transaction <TXID> from Dogecoin Core.")]
+  txid: Option<Txid>,
+  #[arg(long, help = "Load raw or hex-encoded transaction from <FILE>.")]
+  file: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Output {
+  pub(crate) txid: Txid,
+  pub(crate) dunestone: Option<Decoded>,
+}
+
+impl Decode {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let transaction = match (self.txid, self.file) {
+      (Some(txid), None) => options
+        .dogecoin_rpc_client()?
+        .get_raw_transaction(&txid, None)
+        .with_context(|| format!("failed to fetch transaction {txid}"))?,
+      (None, Some(file)) => Self::read(&file)?,
+      _ => unreachable!("clap requires exactly one of `--txid` and `--file`"),
+    };
+
+    Ok(Box::new(decode(&transaction)?))
+  }
+
+  fn read(path: &Path) -> Result<Transaction> {
+    let bytes = fs::read(path).with_context(|| format!("io error reading {}", path.display()))?;
+
+    if let Ok(transaction) = consensus::encode::deserialize(&bytes) {
+      return Ok(transaction);
+    }
+
+    let hex = std::str::from_utf8(&bytes)
+      .ok()
+      .and_then(|text| hex::decode(text.trim()).ok())
+      .with_context(|| format!("{} does not contain a transaction", path.display()))?;
+
+    consensus::encode::deserialize(&hex)
+      .with_context(|| format!("{} does not contain a transaction", path.display()))
+  }
+}
+
+pub(crate) fn decode(transaction: &Transaction) -> Result<Output> {
+  Ok(Output {
+    txid: transaction.txid(),
+    dunestone: Dunestone::decode(transaction)
+      .map_err(|err| anyhow!("failed to parse dunestone script: {err}"))?,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::dunes::{varint, Flaw},
+    bitcoin::PackedLockTime,
+  };
+
+  fn transaction(integers: &[u128], outputs: usize) -> Transaction {
+    let mut payload = Vec::new();
+
+    for integer in integers {
+      varint::encode_to_vec(*integer, &mut payload);
+    }
+
+    let mut output = vec![TxOut {
+      script_pubkey: script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_slice(b"D")
+        .push_slice(&payload)
+        .into_script(),
+      value: 0,
+    }];
+
+    output.extend((1..outputs).map(|_| TxOut {
+      script_pubkey: Script::new(),
+      value: 0,
+    }));
+
+    Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input: Vec::new(),
+      output,
+    }
+  }
+
+  #[test]
+  fn transaction_without_dunestone() {
+    let transaction = Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    assert_eq!(
+      decode(&transaction).unwrap(),
+      Output {
+        txid: transaction.txid(),
+        dunestone: None,
+      }
+    );
+  }
+
+  #[test]
+  fn valid_dunestone_has_no_flaws() {
+    let decoded = decode(&transaction(&[12, 1, 0, 1 << 16, 5, 1], 2))
+      .unwrap()
+      .dunestone
+      .unwrap();
+
+    assert!(!decoded.dunestone.cenotaph);
+    assert_eq!(decoded.dunestone.pointer, Some(1));
+    assert_eq!(
+      decoded.dunestone.edicts,
+      vec![Edict {
+        id: 1 << 16,
+        amount: 5,
+        output: 1,
+      }]
+    );
+    assert_eq!(decoded.integers, vec![12, 1, 0, 1 << 16, 5, 1]);
+    assert!(decoded.flaws.is_empty());
+  }
+
+  #[test]
+  fn cenotaph_flaws_are_reported() {
+    let decoded = decode(&transaction(&[2, 1 << 8, 10, 0, 0, 1 << 16, 5, 7], 2))
+      .unwrap()
+      .dunestone
+      .unwrap();
+
+    assert!(decoded.dunestone.cenotaph);
+    assert_eq!(
+      decoded.flaws,
+      vec![
+        Flaw::EdictOutput(7),
+        Flaw::UnrecognizedFlag(1 << 8),
+        Flaw::UnrecognizedEvenTag(10),
+      ]
+    );
+  }
+
+  #[test]
+  fn supply_overflow_is_reported() {
+    let decoded = decode(&transaction(&[2, 3, 6, 2, 20, u128::MAX], 2))
+      .unwrap()
+      .dunestone
+      .unwrap();
+
+    assert!(decoded.dunestone.cenotaph);
+    assert_eq!(decoded.flaws, vec![Flaw::SupplyOverflow]);
+  }
+}
@@ -1,5 +1,37 @@
 use super::*;
 
+#[derive(Debug, Parser)]
+pub(crate) struct Dunes {
+  #[command(subcommand)]
+  subcommand: Option<DunesSubcommand>,
+}
+
+#[derive(Debug, Parser)]
+enum DunesSubcommand {
+  #[command(about = "List the holders of a dune")]
+  Holders(Holders),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Holders {
+  #[arg(help = "List holders of <DUNE>. May contain `.` or `•`as spacers.")]
+  dune: SpacedDune,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct HoldersOutput {
+  pub(crate) dune: SpacedDune,
+  pub(crate) id: DuneId,
+  pub(crate) holders: Vec<Holder>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Holder {
+  pub(crate) address: Option<String>,
+  pub(crate) balance: Pile,
+  pub(crate) script_pubkey: String,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub dunes: BTreeMap<Dune, DuneInfo>,
@@ -27,7 +59,53 @@ pub struct DuneInfo {
   pub tx: u32,
 }
 
-pub(crate) fn run(options: Options) -> SubcommandResult {
+impl Dunes {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self.subcommand {
+      None => run(options),
+      Some(DunesSubcommand::Holders(holders)) => holders.run(options),
+    }
+  }
+}
+
+impl Holders {
+  fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    ensure!(
+      index.has_dune_index(),
+      "`ord dunes holders` requires index created with `--index-dunes` flag",
+    );
+
+    index.update()?;
+
+    let (id, entry) = index
+      .dune(self.dune.dune)?
+      .with_context(|| format!("dune `{}` has not been etched", self.dune))?;
+
+    let chain = options.chain();
+
+    Ok(Box::new(HoldersOutput {
+      dune: entry.spaced_dune(),
+      id,
+      holders: index
+        .get_dune_holders(id, 0, usize::MAX)?
+        .0
+        .into_iter()
+        .map(|(script, balance)| Holder {
+          address: chain
+            .address_from_script(&script)
+            .ok()
+            .map(|address| address.to_string()),
+          balance: entry.pile(balance),
+          script_pubkey: hex::encode(script.as_bytes()),
+        })
+        .collect(),
+    }))
+  }
+}
+
+fn run(options: Options) -> SubcommandResult {
   let index = Index::open(&options)?;
 
   ensure!(
@@ -89,3 +167,31 @@ pub(crate) fn run(options: Options) -> SubcommandResult {
       .collect::<BTreeMap<Dune, DuneInfo>>(),
   }))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn holders_subcommand() {
+    match Arguments::try_parse_from(["ord", "dunes", "holders", "A•A"])
+      .unwrap()
+      .subcommand
+    {
+      Subcommand::Dunes(Dunes {
+        subcommand: Some(DunesSubcommand::Holders(Holders { dune })),
+      }) => assert_eq!(dune.to_string(), "A•A"),
+      subcommand => panic!("unexpected subcommand: {subcommand:?}"),
+    }
+  }
+
+  #[test]
+  fn dunes_without_subcommand_lists_dunes() {
+    assert!(matches!(
+      Arguments::try_parse_from(["ord", "dunes"])
+        .unwrap()
+        .subcommand,
+      Subcommand::Dunes(Dunes { subcommand: None })
+    ));
+  }
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub sha256: sha256::Hash,
+  pub original: InscriptionId,
+  pub duplicates: Vec<InscriptionId>,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let index = Index::open(&options)?;
+
+  index.update()?;
+
+  Ok(Box::new(
+    index
+      .get_duplicates()?
+      .into_iter()
+      .map(|(sha256, mut ids)| Output {
+        sha256,
+        original: ids.remove(0),
+        duplicates: ids,
+      })
+      .collect::<Vec<Output>>(),
+  ))
+}
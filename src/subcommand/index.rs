@@ -1,10 +1,45 @@
 use super::*;
 
+mod export;
+mod import;
+mod migrate;
 mod update;
+mod verify;
 
-pub(crate) fn run(options: Options) -> SubcommandResult {
-  let index = Index::open(&options)?;
+#[derive(Debug, Parser)]
+pub(crate) struct IndexCommand {
+  #[command(subcommand)]
+  subcommand: Option<IndexSubcommand>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) enum IndexSubcommand {
+  #[command(about = "Write a checksummed snapshot of the index at a block height")]
+  Export(export::Export),
+  #[command(about = "Create the index from a snapshot and sync it to the chain tip")]
+  Import(import::Import),
+  #[command(about = "Upgrade an index built by an older version of ord in place")]
+  Migrate(migrate::Migrate),
+  #[command(about = "Update the index")]
+  Update,
+  #[command(about = "Check the index for inconsistencies")]
+  Verify(verify::Verify),
+}
+
+impl IndexCommand {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self.subcommand {
+      Some(IndexSubcommand::Export(export)) => export.run(options),
+      Some(IndexSubcommand::Import(import)) => import.run(options),
+      Some(IndexSubcommand::Migrate(migrate)) => migrate.run(options),
+      Some(IndexSubcommand::Update) => update::run(options),
+      Some(IndexSubcommand::Verify(verify)) => verify.run(options),
+      None => {
+        let index = Index::open(&options)?;
 
-  index.update()?;
-  Ok(Box::new(()))
+        index.update()?;
+        Ok(Box::new(()))
+      }
+    }
+  }
 }
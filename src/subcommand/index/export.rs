@@ -0,0 +1,36 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Export {
+  #[arg(long, help = "Export the index as of block <HEIGHT>.")]
+  height: u32,
+  #[arg(help = "Write the snapshot to <FILE>.")]
+  file: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub block_hash: BlockHash,
+  pub height: u32,
+  pub sha256: sha256::Hash,
+  pub size: u64,
+}
+
+impl Export {
+  pub(crate) fn run(self, mut options: Options) -> SubcommandResult {
+    options.height_limit = Some(self.height + 1);
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let snapshot = index.export_snapshot(self.height, &self.file)?;
+
+    Ok(Box::new(Output {
+      block_hash: snapshot.block_hash,
+      height: snapshot.height,
+      sha256: snapshot.sha256,
+      size: snapshot.size,
+    }))
+  }
+}
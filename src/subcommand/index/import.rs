@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Import {
+  #[arg(help = "Read the snapshot from <FILE>.")]
+  file: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub block_count: u32,
+  pub block_hash: BlockHash,
+  pub height: u32,
+}
+
+impl Import {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let snapshot = crate::index::import_snapshot(&options, &self.file)?;
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    Ok(Box::new(Output {
+      block_count: index.block_count()?,
+      block_hash: snapshot.block_hash,
+      height: snapshot.height,
+    }))
+  }
+}
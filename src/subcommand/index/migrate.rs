@@ -0,0 +1,13 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Migrate {
+  #[arg(long, help = "Report what would change without writing to the index.")]
+  dry_run: bool,
+}
+
+impl Migrate {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    Ok(Box::new(crate::index::migrate(&options, self.dry_run)?))
+  }
+}
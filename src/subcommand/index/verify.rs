@@ -0,0 +1,15 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Verify {
+  #[arg(long, help = "Repair derived tables that fail verification.")]
+  repair: bool,
+}
+
+impl Verify {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    Ok(Box::new(index.verify(self.repair)?))
+  }
+}
@@ -3,20 +3,27 @@ use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 use {
   self::{
+    accept_encoding::AcceptEncoding,
     deserialize_from_str::DeserializeFromStr,
     error::{OptionExt, ServerError, ServerResult},
   },
   super::*,
   crate::{
     bit20::{script_key::ScriptKey, Tick},
+    inscription::DecodeError,
+    mempool::{Mempool, PendingActivity, PendingInscription},
     page_config::PageConfig,
     templates::{
-      AddressOutputJson, BlockHtml, BlockJson, DuneAddressJson, DuneBalance, DuneBalancesHtml,
-      DuneEntryJson, DuneHtml, DuneJson, DuneOutput, DuneOutputJson, DunesHtml, HomeHtml,
-      InputHtml, InscriptionByAddressJson, InscriptionHtml, InscriptionJson, InscriptionsHtml,
-      OutputHtml, OutputJson, PageContent, PageHtml, PreviewAudioHtml, PreviewImageHtml,
-      PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
-      RangeHtml, RareTxt, SatHtml, ShibescriptionJson, TransactionHtml, Utxo, BIT20,
+      AddressHtml, AddressOutputJson, AddressTransactionsJson, BlockHtml, BlockInfoJson, BlockJson,
+      Dev20InscriptionReceiptsJson, Dev20ReceiptJson, Dev20TransactionReceiptsJson,
+      DuneAddressJson, DuneBalance, DuneBalancesHtml, DuneEntryJson, DuneEventsJson,
+      DuneHolderJson, DuneHoldersJson, DuneHtml, DuneJson, DuneOutput, DuneOutputJson, DunesHtml,
+      HomeHtml, InputHtml, InscriptionByAddressJson, InscriptionHistoryJson, InscriptionHtml,
+      InscriptionIdsJson, InscriptionJson, InscriptionRecursiveJson, InscriptionsBySha256Json,
+      InscriptionsHtml, MetaprotocolInscriptionsJson, OutputHtml, OutputJson, PageContent,
+      PageHtml, PreviewAudioHtml, PreviewImageHtml, PreviewModelHtml, PreviewPdfHtml,
+      PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RangeHtml, RareTxt, SatHtml,
+      SearchHtml, SearchJson, ShibescriptionJson, TransactionHtml, Utxo, BIT20,
     },
   },
   axum::{
@@ -47,6 +54,7 @@ use {
   },
 };
 
+mod accept_encoding;
 mod error;
 mod query;
 
@@ -124,11 +132,31 @@ struct OutputsQuery {
   outputs: String,
 }
 
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct MempoolAddressJson {
+  pub(crate) activity: Vec<PendingActivity>,
+  pub(crate) address: String,
+  pub(crate) inscriptions: Vec<PendingInscription>,
+}
+
+#[derive(Deserialize)]
+struct PageQuery {
+  limit: Option<usize>,
+  page: Option<usize>,
+}
+
 #[derive(Deserialize)]
 struct JsonQuery {
   json: Option<bool>,
 }
 
+#[derive(Deserialize)]
+struct InscriptionsQuery {
+  json: Option<bool>,
+  metaprotocol: Option<String>,
+  page: Option<usize>,
+}
+
 enum BlockQuery {
   Height(u32),
   Hash(BlockHash),
@@ -175,6 +203,14 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct SearchQuery {
+  json: Option<bool>,
+  page: Option<usize>,
+  q: Option<String>,
+  query: Option<String>,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -230,6 +266,8 @@ pub(crate) struct Server {
   https: bool,
   #[clap(long, help = "Redirect HTTP traffic to HTTPS.")]
   redirect_http_to_https: bool,
+  #[clap(long, help = "Track unconfirmed transactions in the mempool.")]
+  mempool: bool,
 }
 
 impl Server {
@@ -237,6 +275,9 @@ impl Server {
     Runtime::new()?.block_on(async {
       let index_clone = index.clone();
 
+      let mempool = Arc::new(Mempool::new(self.mempool));
+      let mempool_clone = mempool.clone();
+
       let index_thread = thread::spawn(move || loop {
         if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
           break;
@@ -244,6 +285,9 @@ impl Server {
         if let Err(error) = index_clone.update() {
           log::warn!("{error}");
         }
+        if let Err(error) = mempool_clone.update(&index_clone) {
+          log::warn!("failed to update mempool: {error}");
+        }
         thread::sleep(Duration::from_millis(5000));
       });
       INDEXER.lock().unwrap().replace(index_thread);
@@ -262,6 +306,10 @@ impl Server {
         .route("/", get(Self::home))
         .route("/block-count", get(Self::block_count))
         .route("/block/:query", get(Self::block))
+        .route("/blockhash", get(Self::block_hash))
+        .route("/blockhash/:height", get(Self::block_hash_from_height))
+        .route("/blockheight", get(Self::block_height))
+        .route("/blocktime", get(Self::block_time))
         .route("/blocks/:query/:endquery", get(Self::blocks))
         .route("/bounties", get(Self::bounties))
         .route("/content/:inscription_id", get(Self::content))
@@ -270,8 +318,16 @@ impl Server {
         .route("/feed.xml", get(Self::feed))
         .route("/input/:block/:transaction/:input", get(Self::input))
         .route("/inscription/:inscription_id", get(Self::inscription))
+        .route(
+          "/inscription/:inscription_id/history",
+          get(Self::inscription_history),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions/:from", get(Self::inscriptions_from))
+        .route(
+          "/inscriptions/by-hash/:sha256",
+          get(Self::inscriptions_by_sha256),
+        )
         .route(
           "/inscriptions_on_outputs",
           get(Self::inscriptions_by_outputs),
@@ -281,14 +337,48 @@ impl Server {
           get(Self::shibescriptions_by_outputs),
         )
         .route("/install.sh", get(Self::install_script))
+        .route("/mempool/address/:address", get(Self::mempool_address))
+        .route("/mempool/dunes", get(Self::mempool_dunes))
+        .route("/mempool/inscriptions", get(Self::mempool_inscriptions))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
         .route("/outputs/:output_list", get(Self::outputs))
         .route("/address/:address", get(Self::outputs_by_address))
+        .route("/address/:address/page", get(Self::address))
+        .route(
+          "/address/:address/transactions",
+          get(Self::address_transactions),
+        )
         .route("/preview/:inscription_id", get(Self::preview))
+        .route("/r/blockhash", get(Self::block_hash_json))
+        .route(
+          "/r/blockhash/:height",
+          get(Self::block_hash_from_height_json),
+        )
+        .route("/r/blockheight", get(Self::block_height_json))
+        .route("/r/blockinfo/:query", get(Self::block_info_json))
+        .route("/r/blocktime", get(Self::block_time_json))
+        .route("/r/children/:inscription_id", get(Self::children_json))
+        .route(
+          "/r/children/:inscription_id/:page",
+          get(Self::children_paginated_json),
+        )
+        .route(
+          "/r/inscription/:inscription_id",
+          get(Self::inscription_json),
+        )
+        .route("/r/metadata/:inscription_id", get(Self::metadata_json))
+        .route("/r/sat/:sat", get(Self::sat_inscriptions_json))
+        .route(
+          "/r/sat/:sat/:page",
+          get(Self::sat_inscriptions_paginated_json),
+        )
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
+        .route("/reorgs", get(Self::reorgs))
         .route("/dune/:dune", get(Self::dune))
+        .route("/dune/:dune/events", get(Self::dune_events))
+        .route("/dune/:dune/holders", get(Self::dune_holders))
         .route("/dunes", get(Self::dunes))
         .route("/dunes/balances", get(Self::dunes_balances))
         .route(
@@ -319,6 +409,15 @@ impl Server {
           "/dev20/address/:address/balance",
           get(Self::bit20_all_balance),
         )
+        .route(
+          "/dev20/inscription/:inscription_id/receipt",
+          get(Self::bit20_inscription_receipts),
+        )
+        .route(
+          "/dev20/tx/:txid/receipts",
+          get(Self::bit20_transaction_receipts),
+        )
+        .route("/decode/:txid", get(Self::decode))
         .route("/dunes_on_outputs", get(Self::dunes_by_outputs))
         .route("/sat/:sat", get(Self::sat))
         .route("/search", get(Self::search_by_query))
@@ -327,6 +426,7 @@ impl Server {
         .route("/status", get(Self::status))
         .route("/tx/:txid", get(Self::transaction))
         .layer(Extension(index))
+        .layer(Extension(mempool))
         .layer(Extension(page_config))
         .layer(Extension(Arc::new(config)))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -957,12 +1057,32 @@ impl Server {
     )
   }
 
+  async fn address(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+  ) -> ServerResult<PageHtml<AddressHtml>> {
+    let address = index
+      .chain
+      .address_from_str(&address)
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let outputs = index.get_account_outputs(address.to_string())?;
+
+    Ok(AddressHtml { address, outputs }.page(page_config))
+  }
+
   async fn outputs_by_address(
     Extension(index): Extension<Arc<Index>>,
     Path(address): Path<String>,
-  ) -> Result<String, ServerError> {
+  ) -> ServerResult<String> {
+    let address = index
+      .chain
+      .address_from_str(&address)
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
     let mut outputs = vec![];
-    let outpoints = index.get_account_outputs(address)?;
+    let outpoints = index.get_account_outputs(address.to_string())?;
 
     outputs.push(AddressOutputJson::new(outpoints));
 
@@ -971,6 +1091,87 @@ impl Server {
     Ok(outputs_json)
   }
 
+  async fn address_transactions(
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+    Query(query): Query<PageQuery>,
+  ) -> ServerResult<Response> {
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    index
+      .chain
+      .address_from_str(&address)
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let page = query.page.unwrap_or_default();
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+
+    let (transactions, more) = index.get_address_activity(&address, page, limit)?;
+
+    Ok(
+      Json(AddressTransactionsJson {
+        address,
+        more,
+        page,
+        transactions,
+      })
+      .into_response(),
+    )
+  }
+
+  fn mempool_enabled(mempool: &Mempool) -> ServerResult<()> {
+    if mempool.is_enabled() {
+      Ok(())
+    } else {
+      Err(ServerError::NotFound(
+        "this server is not tracking the mempool".to_string(),
+      ))
+    }
+  }
+
+  async fn mempool_inscriptions(
+    Extension(mempool): Extension<Arc<Mempool>>,
+  ) -> ServerResult<Response> {
+    Self::mempool_enabled(&mempool)?;
+
+    Ok(Json(mempool.inscriptions()).into_response())
+  }
+
+  async fn mempool_dunes(Extension(mempool): Extension<Arc<Mempool>>) -> ServerResult<Response> {
+    Self::mempool_enabled(&mempool)?;
+
+    Ok(Json(mempool.dunes()).into_response())
+  }
+
+  async fn mempool_address(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Arc<Mempool>>,
+    Path(address): Path<String>,
+  ) -> ServerResult<Response> {
+    Self::mempool_enabled(&mempool)?;
+
+    let address = index
+      .chain
+      .address_from_str(&address)
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?
+      .to_string();
+
+    let (activity, inscriptions) = mempool.address(&address);
+
+    Ok(
+      Json(MempoolAddressJson {
+        activity,
+        address,
+        inscriptions,
+      })
+      .into_response(),
+    )
+  }
+
   async fn outputs(
     Extension(server_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1040,6 +1241,48 @@ impl Server {
     }
   }
 
+  async fn bit20_transaction_receipts(
+    Extension(index): Extension<Arc<Index>>,
+    Path(txid): Path<Txid>,
+  ) -> ServerResult<Response> {
+    let receipts = index.get_bit20_receipts_by_txid(txid)?;
+
+    if receipts.is_empty() {
+      return Err(ServerError::NotFound(format!(
+        "no dev-20 receipts found for transaction {txid}"
+      )));
+    }
+
+    Ok(
+      Json(Dev20TransactionReceiptsJson {
+        txid,
+        receipts: receipts.into_iter().map(Dev20ReceiptJson::from).collect(),
+      })
+      .into_response(),
+    )
+  }
+
+  async fn bit20_inscription_receipts(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Response> {
+    let receipts = index.get_bit20_receipts_by_inscription_id(inscription_id)?;
+
+    if receipts.is_empty() {
+      return Err(ServerError::NotFound(format!(
+        "no dev-20 receipts found for inscription {inscription_id}"
+      )));
+    }
+
+    Ok(
+      Json(Dev20InscriptionReceiptsJson {
+        inscription_id,
+        receipts: receipts.into_iter().map(Dev20ReceiptJson::from).collect(),
+      })
+      .into_response(),
+    )
+  }
+
   async fn bit20_all_tick_info(
     Extension(index): Extension<Arc<Index>>,
   ) -> Result<Response, ServerError> {
@@ -1114,6 +1357,10 @@ impl Server {
     Ok(RareTxt(index.rare_sat_satpoints()?))
   }
 
+  async fn reorgs(Extension(index): Extension<Arc<Index>>) -> ServerResult<Response> {
+    Ok(Json(index.get_reorgs()?).into_response())
+  }
+
   async fn dune(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1148,10 +1395,14 @@ impl Server {
       .inscription_exists(inscription)?
       .then_some(inscription);
 
+    let holders = index.get_dune_holder_count(id)?;
+
     Ok(if !query.json.unwrap_or_default() {
       DuneHtml {
         id,
         entry,
+        events: index.get_dune_events(id, 0, 20)?.0,
+        holders,
         mintable,
         inscription,
       }
@@ -1171,6 +1422,7 @@ impl Server {
           symbol: entry.symbol,
           timestamp: entry.timestamp,
         },
+        holders,
         id,
         mintable,
         inscription,
@@ -1179,6 +1431,87 @@ impl Server {
     })
   }
 
+  async fn dune_events(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(dune_query)): Path<DeserializeFromStr<query::Dune>>,
+    Query(query): Query<PageQuery>,
+  ) -> ServerResult<Response> {
+    let dune = match dune_query {
+      query::Dune::SpacedDune(spaced_dune) => spaced_dune.dune,
+      query::Dune::DuneId(dune_id) => index
+        .get_dune_by_id(dune_id)?
+        .ok_or_not_found(|| format!("dune {dune_id}"))?,
+    };
+
+    let (id, entry) = index
+      .dune(dune)?
+      .ok_or_not_found(|| format!("dune {dune}"))?;
+
+    let page = query.page.unwrap_or_default();
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+
+    let (events, more) = index.get_dune_events(id, page, limit)?;
+
+    Ok(
+      Json(DuneEventsJson {
+        dune: entry.spaced_dune(),
+        events,
+        id,
+        more,
+        page,
+      })
+      .into_response(),
+    )
+  }
+
+  async fn dune_holders(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(dune_query)): Path<DeserializeFromStr<query::Dune>>,
+    Query(query): Query<PageQuery>,
+  ) -> ServerResult<Response> {
+    let dune = match dune_query {
+      query::Dune::SpacedDune(spaced_dune) => spaced_dune.dune,
+      query::Dune::DuneId(dune_id) => index
+        .get_dune_by_id(dune_id)?
+        .ok_or_not_found(|| format!("dune {dune_id}"))?,
+    };
+
+    let (id, entry) = index
+      .dune(dune)?
+      .ok_or_not_found(|| format!("dune {dune}"))?;
+
+    let page = query.page.unwrap_or_default();
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+
+    let (holders, more) = index.get_dune_holders(id, page, limit)?;
+
+    let holders = holders
+      .into_iter()
+      .map(|(script, balance)| DuneHolderJson {
+        address: page_config
+          .chain
+          .address_from_script(&script)
+          .ok()
+          .map(|address| address.to_string()),
+        balance,
+        script_pubkey: hex::encode(script.as_bytes()),
+      })
+      .collect();
+
+    Ok(
+      Json(DuneHoldersJson {
+        dune: entry.spaced_dune(),
+        holders,
+        id,
+        more,
+        page,
+        total: index.get_dune_holder_count(id)?,
+      })
+      .into_response(),
+    )
+  }
+
   async fn dunes(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1636,6 +1969,23 @@ impl Server {
     })
   }
 
+  async fn decode(
+    Extension(index): Extension<Arc<Index>>,
+    Path(txid): Path<Txid>,
+  ) -> ServerResult<Response> {
+    let transaction = index
+      .get_transaction(txid)?
+      .ok_or_not_found(|| format!("transaction {txid}"))?;
+
+    Ok(
+      Json(
+        super::decode::decode(&transaction)
+          .map_err(|err| ServerError::BadRequest(err.to_string()))?,
+      )
+      .into_response(),
+    )
+  }
+
   async fn status(Extension(index): Extension<Arc<Index>>) -> (StatusCode, &'static str) {
     if index.is_unrecoverably_reorged() {
       (
@@ -1651,10 +2001,69 @@ impl Server {
   }
 
   async fn search_by_query(
+    Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Query(search): Query<Search>,
-  ) -> ServerResult<Redirect> {
-    Self::search(&index, &search.query).await
+    Query(search): Query<SearchQuery>,
+  ) -> ServerResult<Response> {
+    if let Some(q) = search.q {
+      return Self::search_text(
+        page_config,
+        &index,
+        q,
+        search.page.unwrap_or_default(),
+        search.json.unwrap_or_default(),
+      );
+    }
+
+    let query = search
+      .query
+      .ok_or_else(|| ServerError::BadRequest("missing search query".into()))?;
+
+    Ok(Self::search(&index, &query).await?.into_response())
+  }
+
+  fn search_text(
+    page_config: Arc<PageConfig>,
+    index: &Index,
+    query: String,
+    page: usize,
+    json: bool,
+  ) -> ServerResult<Response> {
+    if !index.has_text_index() {
+      return Err(ServerError::NotFound(
+        "this server has no text index".to_string(),
+      ));
+    }
+
+    let (inscriptions, more) = index.search_text(&query, page, 100).map_err(|err| {
+      match err.downcast_ref::<crate::index::TextSearchError>() {
+        Some(err) => ServerError::BadRequest(err.to_string()),
+        None => ServerError::Internal(err),
+      }
+    })?;
+
+    if json {
+      Ok(
+        Json(SearchJson {
+          inscriptions,
+          more,
+          page,
+          query,
+        })
+        .into_response(),
+      )
+    } else {
+      Ok(
+        SearchHtml {
+          inscriptions,
+          more,
+          page,
+          query,
+        }
+        .page(page_config)
+        .into_response(),
+      )
+    }
   }
 
   async fn search_by_path(
@@ -1689,6 +2098,8 @@ impl Server {
       Ok(Redirect::to(&format!("/output/{query}")))
     } else if INSCRIPTION_ID.is_match(query) {
       Ok(Redirect::to(&format!("/inscription/{query}")))
+    } else if index.chain.address_from_str(query).is_ok() {
+      Ok(Redirect::to(&format!("/address/{query}/page")))
     } else if DUNE.is_match(query) {
       Ok(Redirect::to(&format!("/dune/{query}")))
     } else if DUNE_ID.is_match(query) {
@@ -1834,6 +2245,7 @@ impl Server {
     Extension(config): Extension<Arc<Config>>,
     Path(inscription_id): Path<InscriptionId>,
     Extension(page_config): Extension<Arc<PageConfig>>,
+    accept_encoding: AcceptEncoding,
   ) -> ServerResult<Response> {
     if config.is_hidden(inscription_id) {
       return Ok(PreviewUnknownHtml.into_response());
@@ -1850,16 +2262,13 @@ impl Server {
     }
 
     Ok(
-      Self::content_response(inscription, &page_config)
+      Self::content_response(inscription, &accept_encoding, &page_config)?
         .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
         .into_response(),
     )
   }
 
-  fn content_response(
-    inscription: Inscription,
-    page_config: &PageConfig,
-  ) -> Option<(HeaderMap, Vec<u8>)> {
+  fn content_security_policy_headers(page_config: &PageConfig) -> ServerResult<HeaderMap> {
     let mut headers = HeaderMap::new();
     match &page_config.csp_origin {
       None => {
@@ -1876,12 +2285,21 @@ impl Server {
         let csp = format!("default-src {origin}/content/ {origin}/blockheight {origin}/blockhash {origin}/blockhash/ {origin}/blocktime {origin}/r/ 'unsafe-eval' 'unsafe-inline' data: blob:");
         headers.insert(
           header::CONTENT_SECURITY_POLICY,
-          HeaderValue::from_str(&csp)
-            .map_err(|err| ServerError::Internal(Error::from(err)))
-            .ok()?,
+          HeaderValue::from_str(&csp).map_err(|err| ServerError::Internal(Error::from(err)))?,
         );
       }
     }
+
+    Ok(headers)
+  }
+
+  fn content_response(
+    inscription: Inscription,
+    accept_encoding: &AcceptEncoding,
+    page_config: &PageConfig,
+  ) -> ServerResult<Option<(HeaderMap, Vec<u8>)>> {
+    let mut headers = Self::content_security_policy_headers(page_config)?;
+
     headers.insert(
       header::CACHE_CONTROL,
       HeaderValue::from_static("max-age=31536000, immutable"),
@@ -1894,37 +2312,345 @@ impl Server {
         .unwrap_or(HeaderValue::from_static("application/octet-stream")),
     );
 
-    Some((headers, inscription.into_body()?))
+    let content_encoding = inscription.content_encoding().map(str::to_owned);
+
+    if content_encoding.is_some() {
+      headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+    }
+
+    let Some(body) = inscription.into_body() else {
+      return Ok(None);
+    };
+
+    let body = match content_encoding.as_deref() {
+      None => body,
+      Some(encoding) if accept_encoding.is_acceptable(encoding) => {
+        headers.insert(
+          header::CONTENT_ENCODING,
+          HeaderValue::from_str(encoding).map_err(|err| ServerError::Internal(err.into()))?,
+        );
+        body
+      }
+      Some(encoding) => Self::decompress(encoding, &body)?,
+    };
+
+    Ok(Some((headers, body)))
   }
 
-  async fn preview(
-    Extension(index): Extension<Arc<Index>>,
-    Extension(config): Extension<Arc<Config>>,
-    Extension(page_config): Extension<Arc<PageConfig>>,
-    Path(inscription_id): Path<InscriptionId>,
+  fn decompress(encoding: &str, body: &[u8]) -> ServerResult<Vec<u8>> {
+    Inscription::decode(encoding, body).map_err(|err| match err {
+      DecodeError::UnknownEncoding { encoding } => ServerError::NotAcceptable(format!(
+        "inscription content encoding `{encoding}` is not acceptable and cannot be decoded"
+      )),
+      DecodeError::TooLarge { encoding } => ServerError::NotAcceptable(format!(
+        "inscription content encoding `{encoding}` is not acceptable and content is too large to decode"
+      )),
+      DecodeError::Invalid { .. } => ServerError::Internal(err.into()),
+    })
+  }
+
+  fn recursive_response(
+    page_config: &PageConfig,
+    immutable: bool,
+    response: impl IntoResponse,
   ) -> ServerResult<Response> {
-    if config.is_hidden(inscription_id) {
-      return Ok(PreviewUnknownHtml.into_response());
-    }
+    let mut headers = Self::content_security_policy_headers(page_config)?;
 
-    let mut inscription = index
-      .get_inscription_by_id(inscription_id)?
-      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+    headers.insert(
+      header::CACHE_CONTROL,
+      HeaderValue::from_static(if immutable {
+        "max-age=31536000, immutable"
+      } else {
+        "no-store"
+      }),
+    );
 
-    if let Some(delegate) = inscription.delegate() {
-      inscription = index
-        .get_inscription_by_id(delegate)?
-        .ok_or_not_found(|| format!("delegate {inscription_id}"))?
-    }
+    Ok((headers, response).into_response())
+  }
 
-    return match inscription.media() {
-      Media::Audio => Ok(PreviewAudioHtml { inscription_id }.into_response()),
-      Media::Iframe => Ok(
-        Self::content_response(inscription, &page_config)
-          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-          .into_response(),
-      ),
-      Media::Model => Ok(
+  fn latest_block_hash(index: &Index) -> ServerResult<BlockHash> {
+    index.block_hash(None)?.ok_or_not_found(|| "genesis block")
+  }
+
+  fn block_hash_at_height(index: &Index, height: u32) -> ServerResult<BlockHash> {
+    index
+      .block_hash(Some(height))?
+      .ok_or_not_found(|| format!("block {height}"))
+  }
+
+  fn latest_block_time(index: &Index) -> ServerResult<u32> {
+    let hash = Self::latest_block_hash(index)?;
+
+    Ok(
+      index
+        .block_header(hash)?
+        .ok_or_not_found(|| format!("block {hash}"))?
+        .time,
+    )
+  }
+
+  async fn block_height(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(&page_config, false, Self::index_height(&index)?.to_string())
+  }
+
+  async fn block_height_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(&page_config, false, Json(Self::index_height(&index)?.n()))
+  }
+
+  async fn block_hash(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(
+      &page_config,
+      false,
+      Self::latest_block_hash(&index)?.to_string(),
+    )
+  }
+
+  async fn block_hash_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(&page_config, false, Json(Self::latest_block_hash(&index)?))
+  }
+
+  async fn block_hash_from_height(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(height): Path<u32>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(
+      &page_config,
+      false,
+      Self::block_hash_at_height(&index, height)?.to_string(),
+    )
+  }
+
+  async fn block_hash_from_height_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(height): Path<u32>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(
+      &page_config,
+      false,
+      Json(Self::block_hash_at_height(&index, height)?),
+    )
+  }
+
+  async fn block_time(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(
+      &page_config,
+      false,
+      Self::latest_block_time(&index)?.to_string(),
+    )
+  }
+
+  async fn block_time_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Response> {
+    Self::recursive_response(&page_config, false, Json(Self::latest_block_time(&index)?))
+  }
+
+  async fn block_info_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Block>>,
+  ) -> ServerResult<Response> {
+    let hash = match query {
+      query::Block::Height(height) => Self::block_hash_at_height(&index, height)?,
+      query::Block::Hash(hash) => hash,
+    };
+
+    let info = index
+      .block_header_info(hash)?
+      .ok_or_not_found(|| format!("block {hash}"))?;
+
+    Self::recursive_response(&page_config, false, Json(BlockInfoJson::from(info)))
+  }
+
+  async fn inscription_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Response> {
+    let entry = index
+      .get_inscription_entry(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    let inscription = index
+      .get_inscription_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    let satpoint = index
+      .get_inscription_satpoint_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    let value = index
+      .get_transaction(satpoint.outpoint.txid)?
+      .and_then(|transaction| {
+        transaction
+          .output
+          .into_iter()
+          .nth(satpoint.outpoint.vout.try_into().unwrap())
+      })
+      .map(|output| output.value);
+
+    Self::recursive_response(
+      &page_config,
+      false,
+      Json(InscriptionRecursiveJson {
+        content_length: inscription.content_length(),
+        content_type: inscription.content_type().map(|s| s.to_string()),
+        fee: entry.fee,
+        height: entry.height,
+        id: inscription_id,
+        number: entry.inscription_number,
+        output: satpoint.outpoint,
+        sat: entry.sat,
+        satpoint,
+        timestamp: entry.timestamp,
+        value,
+      }),
+    )
+  }
+
+  async fn children_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Response> {
+    Self::children_inner(&page_config, &index, inscription_id, 0)
+  }
+
+  async fn children_paginated_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((inscription_id, page)): Path<(InscriptionId, usize)>,
+  ) -> ServerResult<Response> {
+    Self::children_inner(&page_config, &index, inscription_id, page)
+  }
+
+  fn children_inner(
+    page_config: &PageConfig,
+    index: &Index,
+    inscription_id: InscriptionId,
+    page: usize,
+  ) -> ServerResult<Response> {
+    if !index.inscription_exists(inscription_id)? {
+      return Err(ServerError::NotFound(format!(
+        "inscription {inscription_id} not found"
+      )));
+    }
+
+    let (ids, more) = index.get_children(inscription_id, page, 100)?;
+
+    Self::recursive_response(
+      page_config,
+      false,
+      Json(InscriptionIdsJson { ids, more, page }),
+    )
+  }
+
+  async fn metadata_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Response> {
+    let metadata = index
+      .get_inscription_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?
+      .metadata()
+      .ok_or_not_found(|| format!("inscription {inscription_id} metadata"))?;
+
+    Self::recursive_response(&page_config, true, Json(metadata))
+  }
+
+  async fn sat_inscriptions_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(sat)): Path<DeserializeFromStr<Sat>>,
+  ) -> ServerResult<Response> {
+    Self::sat_inscriptions_inner(&page_config, &index, sat, 0)
+  }
+
+  async fn sat_inscriptions_paginated_json(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(sat), page)): Path<(DeserializeFromStr<Sat>, usize)>,
+  ) -> ServerResult<Response> {
+    Self::sat_inscriptions_inner(&page_config, &index, sat, page)
+  }
+
+  fn sat_inscriptions_inner(
+    page_config: &PageConfig,
+    index: &Index,
+    sat: Sat,
+    page: usize,
+  ) -> ServerResult<Response> {
+    if !index.has_sat_index() {
+      return Err(ServerError::NotFound(
+        "this server has no sat index".to_string(),
+      ));
+    }
+
+    let ids = if page == 0 {
+      index.get_inscription_id_by_sat(sat)?.into_iter().collect()
+    } else {
+      Vec::new()
+    };
+
+    Self::recursive_response(
+      page_config,
+      false,
+      Json(InscriptionIdsJson {
+        ids,
+        more: false,
+        page,
+      }),
+    )
+  }
+
+  async fn preview(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
+    accept_encoding: AcceptEncoding,
+  ) -> ServerResult<Response> {
+    if config.is_hidden(inscription_id) {
+      return Ok(PreviewUnknownHtml.into_response());
+    }
+
+    let mut inscription = index
+      .get_inscription_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    if let Some(delegate) = inscription.delegate() {
+      inscription = index
+        .get_inscription_by_id(delegate)?
+        .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+    }
+
+    return match inscription.media() {
+      Media::Audio => Ok(PreviewAudioHtml { inscription_id }.into_response()),
+      Media::Iframe => Ok(
+        Self::content_response(inscription, &accept_encoding, &page_config)?
+          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
+          .into_response(),
+      ),
+      Media::Model => Ok(
         (
           [(
             header::CONTENT_SECURITY_POLICY,
@@ -1974,6 +2700,13 @@ impl Server {
       .get_inscription_by_id(inscription_id)?
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
+    let sha256 = inscription.content_sha256();
+
+    let original = sha256
+      .map(|sha256| index.get_original_by_sha256(sha256))
+      .transpose()?
+      .flatten();
+
     if let Some(delegate) = inscription.delegate() {
       let delegate_inscription = index
         .get_inscription_by_id(delegate)?
@@ -2008,19 +2741,34 @@ impl Server {
 
     let dune = index.get_dune_by_inscription_id(inscription_id)?;
 
+    let parent = index.get_parent(inscription_id)?;
+
+    let metadata = inscription.metadata();
+
+    let metaprotocol = inscription.metaprotocol().map(str::to_owned);
+
+    let (children, _more) = index.get_children(inscription_id, 0, 4)?;
+
     if !query.json.unwrap_or_default() {
       Ok(
         InscriptionHtml {
           chain: page_config.chain,
+          children,
           genesis_fee: entry.fee,
           genesis_height: entry.height,
+          history: index.get_inscription_history(inscription_id, 0, 20)?.0,
           inscription,
           inscription_id,
           next,
           inscription_number: entry.inscription_number,
+          metadata,
+          metaprotocol,
+          original,
           output,
+          parent,
           previous,
           sat: entry.sat,
+          sha256,
           satpoint,
           timestamp: timestamp(entry.timestamp.into()),
           dune,
@@ -2043,16 +2791,22 @@ impl Server {
       Ok(
         Json(ShibescriptionJson {
           chain: page_config.chain,
+          children,
           genesis_fee: entry.fee,
           genesis_height: entry.height,
           inscription,
           inscription_id,
           next,
           inscription_number: entry.inscription_number,
+          metadata,
+          metaprotocol,
+          original,
           output,
           address,
+          parent,
           previous,
           sat: entry.sat,
+          sha256,
           satpoint,
           timestamp: Default::default(),
           dune,
@@ -2062,11 +2816,79 @@ impl Server {
     }
   }
 
+  async fn inscription_history(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    Query(query): Query<PageQuery>,
+  ) -> ServerResult<Response> {
+    index
+      .get_inscription_entry(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    let page = query.page.unwrap_or_default();
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+
+    let (history, more) = index.get_inscription_history(inscription_id, page, limit)?;
+
+    Ok(
+      Json(InscriptionHistoryJson {
+        history,
+        inscription_id,
+        more,
+        page,
+      })
+      .into_response(),
+    )
+  }
+
   async fn inscriptions(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
-  ) -> ServerResult<PageHtml<InscriptionsHtml>> {
-    Self::inscriptions_inner(page_config, index, None).await
+    Query(query): Query<InscriptionsQuery>,
+  ) -> ServerResult<Response> {
+    let Some(metaprotocol) = query.metaprotocol else {
+      return Ok(
+        Self::inscriptions_inner(page_config, index, None)
+          .await?
+          .into_response(),
+      );
+    };
+
+    let page = query.page.unwrap_or_default();
+
+    let (ids, more) = index.get_inscriptions_by_metaprotocol(&metaprotocol, page, 100)?;
+
+    Ok(if query.json.unwrap_or_default() {
+      Json(MetaprotocolInscriptionsJson {
+        ids,
+        metaprotocol,
+        more,
+        page,
+      })
+      .into_response()
+    } else {
+      InscriptionsHtml {
+        inscriptions: ids,
+        metaprotocol: Some(metaprotocol),
+        prev: page.checked_sub(1).map(|prev| prev as u64),
+        next: more.then_some(page as u64 + 1),
+      }
+      .page(page_config)
+      .into_response()
+    })
+  }
+
+  async fn inscriptions_by_sha256(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(sha256)): Path<DeserializeFromStr<sha256::Hash>>,
+  ) -> ServerResult<Response> {
+    Ok(
+      Json(InscriptionsBySha256Json {
+        ids: index.get_inscriptions_by_sha256(sha256)?,
+        sha256,
+      })
+      .into_response(),
+    )
   }
 
   async fn shibescriptions_by_outputs(
@@ -2204,6 +3026,7 @@ impl Server {
 
         let content_type = inscription.content_type().map(|s| s.to_string());
         let content_length = inscription.content_length();
+        let metaprotocol = inscription.metaprotocol().map(str::to_owned);
         let content = inscription.into_body();
 
         let str_content = if let Some(ref ct) = content_type {
@@ -2225,6 +3048,8 @@ impl Server {
           genesis_height: entry.height,
           inscription_id,
           inscription_number: entry.inscription_number,
+          metaprotocol,
+          parent: index.get_parent(inscription_id)?,
           //dune: None,
           timestamp: entry.timestamp,
           tx_id: tx_id.to_string(),
@@ -2256,6 +3081,7 @@ impl Server {
     Ok(
       InscriptionsHtml {
         inscriptions,
+        metaprotocol: None,
         next,
         prev,
       }
@@ -2281,7 +3107,11 @@ mod tests {
 
   use {super::*, reqwest::Url, std::net::TcpListener};
 
-  use crate::dunes::{Dunestone, Edict, Etching};
+  use crate::{
+    dunes::{Dunestone, Edict, Etching},
+    index::Direction,
+    inscription::MAX_DECODED_BODY_SIZE,
+  };
 
   struct TestServer {
     dogecoin_rpc_server: test_bitcoincore_rpc::Handle,
@@ -2669,149 +3499,599 @@ mod tests {
   }
 
   #[test]
-  fn search_by_query_returns_sat() {
-    TestServer::new().assert_redirect("/search?query=0", "/sat/0");
-  }
+  fn search_by_query_returns_sat() {
+    TestServer::new().assert_redirect("/search?query=0", "/sat/0");
+  }
+
+  #[test]
+  fn search_by_query_returns_dune() {
+    TestServer::new().assert_redirect("/search?query=ABCD", "/dune/ABCD");
+  }
+
+  #[test]
+  fn search_by_query_returns_inscription() {
+    TestServer::new().assert_redirect(
+      "/search?query=0000000000000000000000000000000000000000000000000000000000000000i0",
+      "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0",
+    );
+  }
+
+  #[test]
+  fn text_search_requires_text_index() {
+    TestServer::new().assert_response(
+      "/search?q=wow",
+      StatusCode::NOT_FOUND,
+      "this server has no text index",
+    );
+  }
+
+  #[test]
+  fn text_search_without_results() {
+    TestServer::new_with_args(&["--index-text"], &[]).assert_response(
+      "/search?q=such%20wow&json=true",
+      StatusCode::OK,
+      r#"{"inscriptions":[],"more":false,"page":0,"query":"such wow"}"#,
+    );
+  }
+
+  #[test]
+  fn text_search_rejects_short_prefix() {
+    TestServer::new_with_args(&["--index-text"], &[]).assert_response(
+      "/search?q=a*",
+      StatusCode::BAD_REQUEST,
+      "prefix `a*` is too short, prefixes must be at least 3 characters",
+    );
+  }
+
+  #[test]
+  fn search_is_whitespace_insensitive() {
+    TestServer::new().assert_redirect("/search/ 0 ", "/sat/0");
+  }
+
+  #[test]
+  fn search_by_path_returns_sat() {
+    TestServer::new().assert_redirect("/search/0", "/sat/0");
+  }
+
+  #[test]
+  fn search_for_blockhash_returns_block() {
+    TestServer::new().assert_redirect(
+      "/search/1a91e3dace36e2be3bf030a65679fe821aa1d6ef92e7c9902eb318182c355691",
+      "/block/1a91e3dace36e2be3bf030a65679fe821aa1d6ef92e7c9902eb318182c355691",
+    );
+  }
+
+  #[test]
+  fn search_for_txid_returns_transaction() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000",
+      "/tx/0000000000000000000000000000000000000000000000000000000000000000",
+    );
+  }
+
+  #[test]
+  fn search_for_outpoint_returns_output() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000:0",
+      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+    );
+  }
+
+  #[test]
+  fn search_for_inscription_id_returns_inscription() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000i0",
+      "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0",
+    );
+  }
+
+  #[test]
+  fn search_for_address_returns_address() {
+    TestServer::new().assert_redirect(
+      "/search/mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth",
+      "/address/mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth/page",
+    );
+  }
+
+  #[test]
+  fn address_page() {
+    TestServer::new().assert_response_regex(
+      "/address/mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth/page",
+      StatusCode::OK,
+      ".*<h1>Address <span class=monospace>mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth</span></h1>.*",
+    );
+  }
+
+  #[test]
+  fn address_outputs_are_json() {
+    TestServer::new().assert_response(
+      "/address/mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth",
+      StatusCode::OK,
+      r#"[{"outpoint":[]}]"#,
+    );
+  }
+
+  #[test]
+  fn address_must_be_valid_on_chain() {
+    let server = TestServer::new();
+
+    for path in [
+      "/address/1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+      "/address/1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2/page",
+    ] {
+      assert_eq!(server.get(path).status(), StatusCode::BAD_REQUEST);
+    }
+  }
+
+  #[test]
+  fn search_by_path_returns_dune() {
+    TestServer::new().assert_redirect("/search/ABCD", "/dune/ABCD");
+  }
+
+  #[test]
+  fn search_by_dune_id_returns_dune() {
+    let server = TestServer::new_with_regtest_with_index_dunes();
+
+    server.mine_blocks(1);
+
+    let dune = Dune(u128::from(21_000_000 * COIN_VALUE));
+
+    server.assert_response_regex(format!("/dune/{dune}"), StatusCode::NOT_FOUND, ".*");
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0)],
+      witness: inscription("text/plain", "hello").to_witness(),
+      op_return: Some(
+        Dunestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::max_value(),
+            output: 0,
+          }],
+          etching: Some(Etching {
+            dune,
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_redirect("/search/2/1", "/dune/NVTDIJZYIPU");
+    server.assert_redirect("/search?query=2/1", "/dune/NVTDIJZYIPU");
+
+    server.assert_response_regex("/dune/100/200", StatusCode::NOT_FOUND, ".*");
+
+    server.assert_response_regex(
+      "/search/100000000000000000000/200000000000000000",
+      StatusCode::BAD_REQUEST,
+      ".*",
+    );
+  }
+
+  #[test]
+  fn http_to_https_redirect_with_path() {
+    TestServer::new_with_args(&[], &["--redirect-http-to-https", "--https"]).assert_redirect(
+      "/sat/0",
+      &format!("https://{}/sat/0", System::host_name().unwrap()),
+    );
+  }
+
+  #[test]
+  fn http_to_https_redirect_with_empty() {
+    TestServer::new_with_args(&[], &["--redirect-http-to-https", "--https"])
+      .assert_redirect("/", &format!("https://{}/", System::host_name().unwrap()));
+  }
+
+  #[test]
+  fn status() {
+    TestServer::new().assert_response("/status", StatusCode::OK, "OK");
+  }
+
+  #[test]
+  fn block_count_endpoint() {
+    let test_server = TestServer::new();
+
+    let response = test_server.get("/block-count");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "1");
+
+    test_server.mine_blocks(1);
+
+    let response = test_server.get("/block-count");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "2");
+  }
+
+  #[test]
+  fn block_height_endpoint() {
+    let test_server = TestServer::new();
+
+    test_server.assert_response("/blockheight", StatusCode::OK, "0");
+    test_server.assert_response("/r/blockheight", StatusCode::OK, "0");
+
+    test_server.mine_blocks(1);
+
+    test_server.assert_response("/blockheight", StatusCode::OK, "1");
+    test_server.assert_response("/r/blockheight", StatusCode::OK, "1");
+  }
+
+  #[test]
+  fn block_hash_endpoints() {
+    let test_server = TestServer::new();
+
+    let hash = test_server.mine_blocks(1)[0].block_hash();
+
+    test_server.assert_response("/blockhash", StatusCode::OK, &hash.to_string());
+    test_server.assert_response("/blockhash/1", StatusCode::OK, &hash.to_string());
+    test_server.assert_response("/r/blockhash", StatusCode::OK, &format!("\"{hash}\""));
+    test_server.assert_response("/r/blockhash/1", StatusCode::OK, &format!("\"{hash}\""));
+    test_server.assert_response("/blockhash/2", StatusCode::NOT_FOUND, "block 2 not found");
+  }
+
+  #[test]
+  fn recursive_endpoints_have_content_security_policy() {
+    let test_server = TestServer::new();
+
+    let response = test_server.get("/r/blockheight");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
+    );
+    assert_eq!(
+      response
+        .headers()
+        .get_all(header::CONTENT_SECURITY_POLICY)
+        .into_iter()
+        .collect::<Vec<&HeaderValue>>(),
+      &[
+        "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
+        "default-src *:*/content/ *:*/blockheight *:*/blockhash *:*/blockhash/ *:*/blocktime *:*/r/ 'unsafe-eval' 'unsafe-inline' data: blob:",
+      ]
+    );
+  }
+
+  #[test]
+  fn recursive_inscription_endpoints_require_inscription() {
+    let test_server = TestServer::new();
+
+    let inscription_id = InscriptionId::from(Txid::all_zeros());
+
+    test_server.assert_response(
+      format!("/r/children/{inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} not found"),
+    );
+    test_server.assert_response(
+      format!("/r/metadata/{inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} not found"),
+    );
+    test_server.assert_response(
+      format!("/r/inscription/{inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} not found"),
+    );
+  }
+
+  #[test]
+  fn recursive_children_and_metadata_are_served() {
+    let test_server = TestServer::new();
+    test_server.mine_blocks(1);
+
+    let parent = InscriptionId::from(test_server.dogecoin_rpc_server.broadcast_tx(
+      TransactionTemplate {
+        inputs: &[(1, 0, 0)],
+        script_sig: inscription("text/plain;charset=utf-8", "parent").to_script_sig(),
+        ..Default::default()
+      },
+    ));
+    test_server.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer(&serde_json::json!({"name": "child"}), &mut metadata).unwrap();
+
+    let script_sig = Inscription {
+      metadata: Some(metadata),
+      parent: Some(parent.value()),
+      ..inscription("text/plain;charset=utf-8", "child")
+    }
+    .to_script_sig();
+
+    let child = InscriptionId::from(test_server.dogecoin_rpc_server.broadcast_tx(
+      TransactionTemplate {
+        inputs: &[(2, 0, 0), (2, 1, 0)],
+        script_sig,
+        ..Default::default()
+      },
+    ));
+    test_server.mine_blocks(1);
+
+    let response = test_server.get(format!("/r/children/{parent}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.json::<InscriptionIdsJson>().unwrap(),
+      InscriptionIdsJson {
+        ids: vec![child],
+        more: false,
+        page: 0,
+      }
+    );
+
+    let response = test_server.get(format!("/r/children/{parent}/1"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.json::<InscriptionIdsJson>().unwrap(),
+      InscriptionIdsJson {
+        ids: Vec::new(),
+        more: false,
+        page: 1,
+      }
+    );
+
+    test_server.assert_response(
+      format!("/r/metadata/{child}"),
+      StatusCode::OK,
+      r#"{"name":"child"}"#,
+    );
+    test_server.assert_response(
+      format!("/r/metadata/{parent}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {parent} metadata not found"),
+    );
+  }
+
+  #[test]
+  fn mempool_endpoints_require_mempool_flag() {
+    let test_server = TestServer::new();
 
-  #[test]
-  fn search_by_query_returns_dune() {
-    TestServer::new().assert_redirect("/search?query=ABCD", "/dune/ABCD");
+    for path in ["/mempool/inscriptions", "/mempool/dunes"] {
+      test_server.assert_response(
+        path,
+        StatusCode::NOT_FOUND,
+        "this server is not tracking the mempool",
+      );
+    }
   }
 
   #[test]
-  fn search_by_query_returns_inscription() {
-    TestServer::new().assert_redirect(
-      "/search?query=0000000000000000000000000000000000000000000000000000000000000000i0",
-      "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0",
-    );
-  }
+  fn mempool_endpoints_return_json() {
+    let test_server = TestServer::new_with_args(&[], &["--mempool"]);
 
-  #[test]
-  fn search_is_whitespace_insensitive() {
-    TestServer::new().assert_redirect("/search/ 0 ", "/sat/0");
-  }
+    let response = test_server.get("/mempool/inscriptions");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "[]");
 
-  #[test]
-  fn search_by_path_returns_sat() {
-    TestServer::new().assert_redirect("/search/0", "/sat/0");
+    let response = test_server.get("/mempool/dunes");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "[]");
   }
 
   #[test]
-  fn search_for_blockhash_returns_block() {
-    TestServer::new().assert_redirect(
-      "/search/1a91e3dace36e2be3bf030a65679fe821aa1d6ef92e7c9902eb318182c355691",
-      "/block/1a91e3dace36e2be3bf030a65679fe821aa1d6ef92e7c9902eb318182c355691",
-    );
-  }
+  fn mempool_address_returns_json() {
+    let test_server = TestServer::new_with_args(&[], &["--mempool"]);
 
-  #[test]
-  fn search_for_txid_returns_transaction() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000",
-      "/tx/0000000000000000000000000000000000000000000000000000000000000000",
-    );
-  }
+    let address = "mfWyW5fc9NUj75YAnFgoRLrjxgLDn2MMth";
 
-  #[test]
-  fn search_for_outpoint_returns_output() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000:0",
-      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+    test_server.assert_response(
+      format!("/mempool/address/{address}"),
+      StatusCode::OK,
+      &format!(r#"{{"activity":[],"address":"{address}","inscriptions":[]}}"#),
     );
-  }
 
-  #[test]
-  fn search_for_inscription_id_returns_inscription() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000i0",
-      "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0",
+    assert_eq!(
+      test_server.get("/mempool/address/foo").status(),
+      StatusCode::BAD_REQUEST
     );
   }
 
   #[test]
-  fn search_by_path_returns_dune() {
-    TestServer::new().assert_redirect("/search/ABCD", "/dune/ABCD");
+  fn mempool_address_rejects_foreign_chain_address() {
+    let test_server = TestServer::new_with_args(&[], &["--mempool"]);
+
+    for address in [
+      "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw",
+      "DBXu2kgc3xtvCUWFcxFE3r9hEYgmuaaCyD",
+    ] {
+      assert_eq!(
+        test_server
+          .get(format!("/mempool/address/{address}"))
+          .status(),
+        StatusCode::BAD_REQUEST
+      );
+    }
   }
 
   #[test]
-  fn search_by_dune_id_returns_dune() {
-    let server = TestServer::new_with_regtest_with_index_dunes();
+  fn address_transactions_returns_indexed_activity() {
+    let test_server = TestServer::new_with_args(&["--index-addresses"], &[]);
+    test_server.mine_blocks(1);
 
-    server.mine_blocks(1);
+    let address = Address {
+      payload: bitcoin::util::address::Payload::PubkeyHash(bitcoin::PubkeyHash::all_zeros()),
+      network: Network::Regtest,
+    };
 
-    let dune = Dune(u128::from(21_000_000 * COIN_VALUE));
+    let txid = test_server
+      .dogecoin_rpc_server
+      .broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0)],
+        recipient: Some(address.clone()),
+        ..Default::default()
+      });
+    test_server.mine_blocks(1);
 
-    server.assert_response_regex(format!("/dune/{dune}"), StatusCode::NOT_FOUND, ".*");
+    let response = test_server.get(format!("/address/{address}/transactions"));
 
-    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0)],
-      witness: inscription("text/plain", "hello").to_witness(),
-      op_return: Some(
-        Dunestone {
-          edicts: vec![Edict {
-            id: 0,
-            amount: u128::max_value(),
-            output: 0,
-          }],
-          etching: Some(Etching {
-            dune,
-            ..Default::default()
-          }),
-          ..Default::default()
-        }
-        .encipher(),
-      ),
-      ..Default::default()
-    });
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.json::<AddressTransactionsJson>().unwrap(),
+      AddressTransactionsJson {
+        address: address.to_string(),
+        more: false,
+        page: 0,
+        transactions: vec![AddressActivity {
+          height: 2,
+          txid,
+          direction: Direction::Received,
+          value: 50 * COIN_VALUE,
+        }],
+      }
+    );
+  }
 
-    server.mine_blocks(1);
+  #[test]
+  fn dev20_receipts_not_found() {
+    let test_server = TestServer::new();
 
-    server.assert_redirect("/search/2/1", "/dune/NVTDIJZYIPU");
-    server.assert_redirect("/search?query=2/1", "/dune/NVTDIJZYIPU");
+    let txid = Txid::all_zeros();
+    let inscription_id = InscriptionId::from(txid);
 
-    server.assert_response_regex("/dune/100/200", StatusCode::NOT_FOUND, ".*");
+    test_server.assert_response(
+      format!("/dev20/tx/{txid}/receipts"),
+      StatusCode::NOT_FOUND,
+      &format!("no dev-20 receipts found for transaction {txid}"),
+    );
+    test_server.assert_response(
+      format!("/dev20/inscription/{inscription_id}/receipt"),
+      StatusCode::NOT_FOUND,
+      &format!("no dev-20 receipts found for inscription {inscription_id}"),
+    );
+  }
 
-    server.assert_response_regex(
-      "/search/100000000000000000000/200000000000000000",
-      StatusCode::BAD_REQUEST,
-      ".*",
+  #[test]
+  fn dune_holders_not_found() {
+    TestServer::new_with_args(&["--index-dunes"], &[]).assert_response(
+      "/dune/AAAAAAAAAAAAA/holders",
+      StatusCode::NOT_FOUND,
+      "dune AAAAAAAAAAAAA not found",
     );
   }
 
   #[test]
-  fn http_to_https_redirect_with_path() {
-    TestServer::new_with_args(&[], &["--redirect-http-to-https", "--https"]).assert_redirect(
-      "/sat/0",
-      &format!("https://{}/sat/0", System::host_name().unwrap()),
+  fn dune_events_not_found() {
+    TestServer::new_with_args(&["--index-dunes"], &[]).assert_response(
+      "/dune/AAAAAAAAAAAAA/events",
+      StatusCode::NOT_FOUND,
+      "dune AAAAAAAAAAAAA not found",
     );
   }
 
   #[test]
-  fn http_to_https_redirect_with_empty() {
-    TestServer::new_with_args(&[], &["--redirect-http-to-https", "--https"])
-      .assert_redirect("/", &format!("https://{}/", System::host_name().unwrap()));
+  fn inscriptions_by_unknown_sha256() {
+    TestServer::new().assert_response(
+      "/inscriptions/by-hash/2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+      StatusCode::OK,
+      r#"{"ids":[],"sha256":"2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"}"#,
+    );
   }
 
   #[test]
-  fn status() {
-    TestServer::new().assert_response("/status", StatusCode::OK, "OK");
+  fn inscription_history_not_found() {
+    TestServer::new().assert_response(
+      "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0/history",
+      StatusCode::NOT_FOUND,
+      "inscription 0000000000000000000000000000000000000000000000000000000000000000i0 not found",
+    );
   }
 
   #[test]
-  fn block_count_endpoint() {
+  fn inscription_history_follows_transfers() {
     let test_server = TestServer::new();
+    test_server.mine_blocks(1);
 
-    let response = test_server.get("/block-count");
+    let reveal = test_server
+      .dogecoin_rpc_server
+      .broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0)],
+        script_sig: inscription("text/plain;charset=utf-8", "hello").to_script_sig(),
+        ..Default::default()
+      });
+    test_server.mine_blocks(1);
 
-    assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(response.text().unwrap(), "1");
+    let inscription_id = InscriptionId::from(reveal);
 
+    let owner = Address {
+      payload: bitcoin::util::address::Payload::PubkeyHash(bitcoin::PubkeyHash::all_zeros()),
+      network: Network::Regtest,
+    };
+
+    let transfer = test_server
+      .dogecoin_rpc_server
+      .broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 1, 0)],
+        recipient: Some(owner.clone()),
+        ..Default::default()
+      });
     test_server.mine_blocks(1);
 
-    let response = test_server.get("/block-count");
+    let response = test_server.get(format!("/inscription/{inscription_id}/history"));
 
     assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(response.text().unwrap(), "2");
+    assert_eq!(
+      response.json::<InscriptionHistoryJson>().unwrap(),
+      InscriptionHistoryJson {
+        history: vec![
+          InscriptionTransfer {
+            height: 3,
+            sequence: 0,
+            txid: transfer,
+            old_satpoint: Some(SatPoint {
+              outpoint: OutPoint::new(reveal, 0),
+              offset: 0,
+            }),
+            new_satpoint: SatPoint {
+              outpoint: OutPoint::new(transfer, 0),
+              offset: 0,
+            },
+            owner: Some(owner.to_string()),
+          },
+          InscriptionTransfer {
+            height: 2,
+            sequence: 0,
+            txid: reveal,
+            old_satpoint: None,
+            new_satpoint: SatPoint {
+              outpoint: OutPoint::new(reveal, 0),
+              offset: 0,
+            },
+            owner: None,
+          },
+        ],
+        inscription_id,
+        more: false,
+        page: 0,
+      }
+    );
+  }
+
+  #[test]
+  fn decode_transaction() {
+    let test_server = TestServer::new();
+
+    let txid = Txid::all_zeros();
+
+    test_server.assert_response(
+      format!("/decode/{txid}"),
+      StatusCode::NOT_FOUND,
+      &format!("transaction {txid} not found"),
+    );
+
+    let txid = test_server.mine_blocks(1)[0].txdata[0].txid();
+
+    test_server.assert_response(
+      format!("/decode/{txid}"),
+      StatusCode::OK,
+      &format!(r#"{{"txid":"{txid}","dunestone":null}}"#),
+    );
   }
 
   #[test]
@@ -3171,6 +4451,23 @@ mod tests {
     test_server.assert_response_regex("/status", StatusCode::OK, "unrecoverable reorg detected.*");
   }
 
+  #[test]
+  fn reorgs() {
+    let test_server = TestServer::new();
+
+    test_server.assert_response("/reorgs", StatusCode::OK, "[]");
+
+    test_server.mine_blocks(2);
+    test_server.dogecoin_rpc_server.invalidate_tip();
+    test_server.mine_blocks(2);
+
+    test_server.assert_response_regex(
+      "/reorgs",
+      StatusCode::OK,
+      r#"\[\{"height":3,"depth":2,"old_block_hash":"[[:xdigit:]]{64}","new_block_hash":"[[:xdigit:]]{64}","recovered":true\}\]"#,
+    );
+  }
+
   #[test]
   fn rare_with_sat_index() {
     TestServer::new_with_sat_index().assert_response(
@@ -3383,23 +4680,63 @@ mod tests {
     );
   }
 
+  fn content_page_config() -> PageConfig {
+    PageConfig {
+      chain: Chain::Mainnet,
+      domain: None,
+      index_sats: false,
+      csp_origin: None,
+    }
+  }
+
+  fn compressed_inscription(encoding: &str, body: &[u8]) -> Inscription {
+    use std::io::Write;
+
+    let compressed = match encoding {
+      "br" => {
+        let mut compressed = Vec::new();
+        brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22)
+          .write_all(body)
+          .unwrap();
+        compressed
+      }
+      "gzip" => {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+      }
+      _ => body.to_vec(),
+    };
+
+    Inscription {
+      content_encoding: Some(encoding.into()),
+      ..Inscription::new(Some("text/html".into()), Some(compressed))
+    }
+  }
+
   #[test]
   fn content_response_no_content() {
     assert_eq!(
-      Server::content_response(Inscription::new(
-        Some("text/plain".as_bytes().to_vec()),
-        None
-      )),
+      Server::content_response(
+        Inscription::new(Some("text/plain".as_bytes().to_vec()), None),
+        &AcceptEncoding::default(),
+        &content_page_config(),
+      )
+      .ok()
+      .unwrap(),
       None
     );
   }
 
   #[test]
   fn content_response_with_content() {
-    let (headers, body) = Server::content_response(Inscription::new(
-      Some("text/plain".as_bytes().to_vec()),
-      Some(vec![1, 2, 3]),
-    ))
+    let (headers, body) = Server::content_response(
+      Inscription::new(Some("text/plain".as_bytes().to_vec()), Some(vec![1, 2, 3])),
+      &AcceptEncoding::default(),
+      &content_page_config(),
+    )
+    .ok()
+    .unwrap()
     .unwrap();
 
     assert_eq!(headers["content-type"], "text/plain");
@@ -3408,13 +4745,107 @@ mod tests {
 
   #[test]
   fn content_response_no_content_type() {
-    let (headers, body) =
-      Server::content_response(Inscription::new(None, Some(Vec::new()))).unwrap();
+    let (headers, body) = Server::content_response(
+      Inscription::new(None, Some(Vec::new())),
+      &AcceptEncoding::default(),
+      &content_page_config(),
+    )
+    .ok()
+    .unwrap()
+    .unwrap();
 
     assert_eq!(headers["content-type"], "application/octet-stream");
     assert!(body.is_empty());
   }
 
+  #[test]
+  fn content_response_passes_through_accepted_encoding() {
+    let inscription = compressed_inscription("br", b"<p>woof</p>");
+    let compressed = inscription.body.clone().unwrap();
+
+    let (headers, body) = Server::content_response(
+      inscription,
+      &AcceptEncoding(Some("gzip, br".into())),
+      &content_page_config(),
+    )
+    .ok()
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["content-encoding"], "br");
+    assert_eq!(body, compressed);
+  }
+
+  #[test]
+  fn content_response_decompresses_unaccepted_encoding() {
+    for encoding in ["br", "gzip"] {
+      let (headers, body) = Server::content_response(
+        compressed_inscription(encoding, b"<p>woof</p>"),
+        &AcceptEncoding::default(),
+        &content_page_config(),
+      )
+      .ok()
+      .unwrap()
+      .unwrap();
+
+      assert!(headers.get("content-encoding").is_none());
+      assert_eq!(body, b"<p>woof</p>");
+    }
+  }
+
+  #[test]
+  fn content_response_varies_on_accept_encoding() {
+    let (headers, _body) = Server::content_response(
+      compressed_inscription("br", b"<p>woof</p>"),
+      &AcceptEncoding(Some("br".into())),
+      &content_page_config(),
+    )
+    .ok()
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(headers["vary"], "accept-encoding");
+
+    let (headers, _body) = Server::content_response(
+      Inscription::new(Some("text/plain".into()), Some(vec![1, 2, 3])),
+      &AcceptEncoding::default(),
+      &content_page_config(),
+    )
+    .ok()
+    .unwrap()
+    .unwrap();
+
+    assert!(headers.get("vary").is_none());
+  }
+
+  #[test]
+  fn content_response_limits_decompressed_size() {
+    let body = vec![0; usize::try_from(MAX_DECODED_BODY_SIZE).unwrap() + 1];
+
+    for encoding in ["br", "gzip"] {
+      assert!(matches!(
+        Server::content_response(
+          compressed_inscription(encoding, &body),
+          &AcceptEncoding::default(),
+          &content_page_config(),
+        ),
+        Err(ServerError::NotAcceptable(_))
+      ));
+    }
+  }
+
+  #[test]
+  fn content_response_rejects_unknown_encoding() {
+    assert!(matches!(
+      Server::content_response(
+        compressed_inscription("zstd", b"<p>woof</p>"),
+        &AcceptEncoding::default(),
+        &content_page_config(),
+      ),
+      Err(ServerError::NotAcceptable(_))
+    ));
+  }
+
   #[test]
   fn text_preview() {
     let server = TestServer::new();
@@ -3798,6 +5229,49 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inscriptions_by_unknown_metaprotocol() {
+    TestServer::new().assert_response(
+      "/inscriptions?metaprotocol=dev-20&json=true",
+      StatusCode::OK,
+      r#"{"ids":[],"metaprotocol":"dev-20","more":false,"page":0}"#,
+    );
+  }
+
+  #[test]
+  fn inscriptions_by_metaprotocol_page() {
+    let test_server = TestServer::new();
+    test_server.mine_blocks(1);
+
+    let txid = test_server
+      .dogecoin_rpc_server
+      .broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0)],
+        script_sig: Inscription {
+          metaprotocol: Some("dev-20".into()),
+          ..inscription("text/plain;charset=utf-8", "hello")
+        }
+        .to_script_sig(),
+        ..Default::default()
+      });
+    test_server.mine_blocks(1);
+
+    let inscription_id = InscriptionId::from(txid);
+
+    test_server.assert_response_regex(
+      "/inscriptions?metaprotocol=dev-20",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscriptions: dev-20</h1>.*<a href=/inscription/{inscription_id}>.*prev\nnext.*"
+      ),
+    );
+    test_server.assert_response(
+      "/inscriptions?metaprotocol=dev-20&json=true",
+      StatusCode::OK,
+      &format!(r#"{{"ids":["{inscription_id}"],"metaprotocol":"dev-20","more":false,"page":0}}"#),
+    );
+  }
+
   #[test]
   fn inscriptions_page_with_no_prev_or_next() {
     TestServer::new_with_sat_index().assert_response_regex(
@@ -0,0 +1,77 @@
+use {
+  super::*,
+  axum::{async_trait, extract::FromRequestParts, http::request::Parts},
+};
+
+#[derive(Default, Debug)]
+pub(crate) struct AcceptEncoding(pub(crate) Option<String>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AcceptEncoding
+where
+  S: Send + Sync,
+{
+  type Rejection = (StatusCode, &'static str);
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    Ok(Self(
+      parts
+        .headers
+        .get(header::ACCEPT_ENCODING)
+        .map(|value| value.to_str().map(str::to_owned))
+        .transpose()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid accept-encoding header"))?,
+    ))
+  }
+}
+
+impl AcceptEncoding {
+  pub(crate) fn is_acceptable(&self, encoding: &str) -> bool {
+    let Some(accept_encoding) = &self.0 else {
+      return false;
+    };
+
+    accept_encoding.split(',').any(|item| {
+      let mut parameters = item.split(';');
+
+      let coding = parameters.next().unwrap_or_default().trim();
+
+      let quality = parameters
+        .find_map(|parameter| parameter.trim().strip_prefix("q="))
+        .map(|quality| quality.trim().parse::<f64>().unwrap_or_default())
+        .unwrap_or(1.0);
+
+      (coding == "*" || coding.eq_ignore_ascii_case(encoding)) && quality > 0.0
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn missing_header_accepts_nothing() {
+    assert!(!AcceptEncoding(None).is_acceptable("br"));
+  }
+
+  #[test]
+  fn listed_encodings_are_acceptable() {
+    let accept_encoding = AcceptEncoding(Some("gzip, deflate, br".into()));
+    assert!(accept_encoding.is_acceptable("br"));
+    assert!(accept_encoding.is_acceptable("gzip"));
+    assert!(!accept_encoding.is_acceptable("zstd"));
+  }
+
+  #[test]
+  fn zero_quality_is_not_acceptable() {
+    let accept_encoding = AcceptEncoding(Some("gzip;q=0.5, br;q=0".into()));
+    assert!(accept_encoding.is_acceptable("gzip"));
+    assert!(!accept_encoding.is_acceptable("br"));
+  }
+
+  #[test]
+  fn wildcard_accepts_everything() {
+    assert!(AcceptEncoding(Some("*".into())).is_acceptable("br"));
+  }
+}
@@ -3,6 +3,7 @@ use super::*;
 pub(super) enum ServerError {
   Internal(Error),
   BadRequest(String),
+  NotAcceptable(String),
   NotFound(String),
 }
 
@@ -21,6 +22,7 @@ impl IntoResponse for ServerError {
         )
           .into_response()
       }
+      Self::NotAcceptable(message) => (StatusCode::NOT_ACCEPTABLE, message).into_response(),
       Self::NotFound(message) => (StatusCode::NOT_FOUND, message).into_response(),
       Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
     }
@@ -18,12 +18,15 @@ use {
 
 pub mod balance;
 pub mod create;
+pub(crate) mod dev20;
 pub mod etch;
 pub(crate) mod inscribe;
 pub mod inscriptions;
+pub(crate) mod mint;
 pub mod outputs;
 pub mod receive;
 mod restore;
+mod resume;
 pub mod sats;
 pub mod send;
 pub(crate) mod transaction_builder;
@@ -35,16 +38,22 @@ pub(crate) enum Wallet {
   Balance,
   #[clap(about = "Create new wallet")]
   Create(create::Create),
+  #[clap(subcommand, about = "Deploy, mint and transfer dev-20 tokens")]
+  Dev20(dev20::Dev20),
   #[clap(about = "Create dune")]
   Etch(etch::Etch),
   #[clap(about = "Create inscription")]
   Inscribe(inscribe::Inscribe),
   #[clap(about = "List wallet inscriptions")]
   Inscriptions,
+  #[clap(about = "Mint a dune")]
+  Mint(mint::Mint),
   #[clap(about = "Generate receive address")]
   Receive,
   #[clap(about = "Restore wallet")]
   Restore(restore::Restore),
+  #[clap(about = "Resume broadcasting an interrupted inscription")]
+  Resume(resume::Resume),
   #[clap(about = "List wallet satoshis")]
   Sats(sats::Sats),
   #[clap(about = "Send sat or inscription")]
@@ -60,11 +69,14 @@ impl Wallet {
     match self {
       Self::Balance => balance::run(options),
       Self::Create(create) => create.run(options),
+      Self::Dev20(dev20) => dev20.run(options),
       Self::Etch(etch) => etch.run(options),
       Self::Inscribe(inscribe) => inscribe.run(options),
       Self::Inscriptions => inscriptions::run(options),
+      Self::Mint(mint) => mint.run(options),
       Self::Receive => receive::run(options),
       Self::Restore(restore) => restore.run(options),
+      Self::Resume(resume) => resume.run(options),
       Self::Sats(sats) => sats.run(options),
       Self::Send(send) => send.run(options),
       Self::Transactions(transactions) => transactions.run(options),
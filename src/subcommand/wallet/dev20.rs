@@ -0,0 +1,448 @@
+use {
+  super::*,
+  crate::{
+    bit20::{
+      operation::{deserialize_bit20_operation, Action},
+      params::{MAXIMUM_SUPPLY, PROTOCOL_LITERAL},
+      script_key::ScriptKey,
+      Num, Tick,
+    },
+    wallet::Wallet,
+  },
+  bigdecimal::num_bigint::Sign,
+  bitcoin::PackedLockTime,
+  inscribe::Inscribe,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) enum Dev20 {
+  #[clap(about = "Deploy a dev-20 token")]
+  Deploy(Deploy),
+  #[clap(about = "Mint a dev-20 token")]
+  Mint(Mint),
+  #[clap(about = "Inscribe a dev-20 transfer and send it to a recipient")]
+  Transfer(Transfer),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Deploy {
+  #[clap(long, help = "Deploy token with ticker <TICK>.")]
+  tick: Tick,
+  #[clap(long, help = "Set maximum supply to <MAX>.")]
+  max: Num,
+  #[clap(long, help = "Limit each mint to <LIMIT>. Defaults to <MAX>.")]
+  limit: Option<Num>,
+  #[clap(long, help = "Send deploy inscription to <DESTINATION>.")]
+  destination: Option<Address>,
+  #[clap(flatten)]
+  inscribe: InscribeOptions,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Mint {
+  #[clap(long, help = "Mint token with ticker <TICK>.")]
+  tick: Tick,
+  #[clap(long, help = "Mint <AMOUNT> tokens.")]
+  amount: Num,
+  #[clap(long, help = "Send mint inscription to <DESTINATION>.")]
+  destination: Option<Address>,
+  #[clap(flatten)]
+  inscribe: InscribeOptions,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Transfer {
+  #[clap(long, help = "Transfer token with ticker <TICK>.")]
+  tick: Tick,
+  #[clap(long, help = "Transfer <AMOUNT> tokens.")]
+  amount: Num,
+  #[clap(help = "Send transfer inscription to <RECIPIENT>.")]
+  recipient: Address,
+  #[clap(flatten)]
+  inscribe: InscribeOptions,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct InscribeOptions {
+  #[clap(
+    long,
+    default_value = "1.0",
+    help = "Use fee rate of <FEE_RATE> sats/vB"
+  )]
+  fee_rate: FeeRate,
+  #[clap(
+    long,
+    help = "Use <COMMIT_FEE_RATE> sats/vbyte for commit transaction.\nDefaults to <FEE_RATE> if unset."
+  )]
+  commit_fee_rate: Option<FeeRate>,
+  #[clap(long, help = "Do not back up recovery key.")]
+  no_backup: bool,
+  #[clap(long, help = "Don't sign or broadcast transactions.")]
+  dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Output {
+  #[serde(flatten)]
+  pub inscribe: inscribe::Output,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub send: Option<Txid>,
+}
+
+#[derive(Serialize)]
+struct Body<'a> {
+  p: &'a str,
+  op: &'a str,
+  tick: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  max: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  lim: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  amt: Option<String>,
+}
+
+impl Body<'_> {
+  fn into_inscription(self) -> Result<Inscription> {
+    let inscription = Inscription {
+      body: Some(serde_json::to_vec(&self)?),
+      content_encoding: None,
+      content_type: Some("text/plain;charset=utf-8".into()),
+      delegate: None,
+      metadata: None,
+      metaprotocol: None,
+      parent: None,
+    };
+
+    deserialize_bit20_operation(
+      &inscription,
+      &Action::New {
+        inscription: inscription.clone(),
+      },
+    )
+    .context("generated dev-20 inscription is invalid")?;
+
+    Ok(inscription)
+  }
+}
+
+impl Dev20 {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    ensure!(
+      index.has_bit20_index(),
+      "`ord wallet dev20` requires index created with `--index-dunes` flag",
+    );
+
+    index.update()?;
+
+    let output = match self {
+      Self::Deploy(deploy) => deploy.run(&options, &index)?,
+      Self::Mint(mint) => mint.run(&options, &index)?,
+      Self::Transfer(transfer) => transfer.run(&options, &index)?,
+    };
+
+    Ok(Box::new(output))
+  }
+}
+
+impl Deploy {
+  fn run(self, options: &Options, index: &Index) -> Result<Output> {
+    ensure!(
+      index.get_bit20_token_info(&self.tick)?.is_none(),
+      "dev-20 token `{}` has already been deployed",
+      self.tick,
+    );
+
+    let max = check_amount("max", &self.max)?;
+
+    if let Some(limit) = &self.limit {
+      let limit = check_amount("limit", limit)?;
+      ensure!(
+        limit <= max,
+        "limit {limit} is greater than maximum supply {max}"
+      );
+    }
+
+    let inscription = Body {
+      p: PROTOCOL_LITERAL,
+      op: "deploy",
+      tick: self.tick.as_str(),
+      max: Some(self.max.to_string()),
+      lim: self.limit.as_ref().map(Num::to_string),
+      amt: None,
+    }
+    .into_inscription()?;
+
+    Ok(Output {
+      inscribe: self
+        .inscribe
+        .inscribe(options, index, inscription, self.destination)?,
+      send: None,
+    })
+  }
+}
+
+impl Mint {
+  fn run(self, options: &Options, index: &Index) -> Result<Output> {
+    let token_info = index
+      .get_bit20_token_info(&self.tick)?
+      .with_context(|| format!("dev-20 token `{}` has not been deployed", self.tick))?;
+
+    let amount = check_amount("amount", &self.amount)?;
+
+    ensure!(
+      amount <= token_info.limit_per_mint,
+      "mint amount {amount} exceeds limit of {} per mint",
+      token_info.limit_per_mint,
+    );
+
+    ensure!(
+      token_info.minted < token_info.supply,
+      "dev-20 token `{}` has been fully minted",
+      self.tick,
+    );
+
+    let inscription = Body {
+      p: PROTOCOL_LITERAL,
+      op: "mint",
+      tick: self.tick.as_str(),
+      max: None,
+      lim: None,
+      amt: Some(self.amount.to_string()),
+    }
+    .into_inscription()?;
+
+    Ok(Output {
+      inscribe: self
+        .inscribe
+        .inscribe(options, index, inscription, self.destination)?,
+      send: None,
+    })
+  }
+}
+
+impl Transfer {
+  fn run(self, options: &Options, index: &Index) -> Result<Output> {
+    index
+      .get_bit20_token_info(&self.tick)?
+      .with_context(|| format!("dev-20 token `{}` has not been deployed", self.tick))?;
+
+    let amount = check_amount("amount", &self.amount)?;
+
+    // the transfer inscription must be received by the address holding the
+    // balance, otherwise the indexer rejects it
+    let holder = Self::holder(options, index, &self.tick, amount)?;
+
+    let inscription = Body {
+      p: PROTOCOL_LITERAL,
+      op: "transfer",
+      tick: self.tick.as_str(),
+      max: None,
+      lim: None,
+      amt: Some(self.amount.to_string()),
+    }
+    .into_inscription()?;
+
+    let inscribe = self
+      .inscribe
+      .inscribe(options, index, inscription, Some(holder))?;
+
+    let send = if self.inscribe.dry_run {
+      None
+    } else {
+      Some(Self::send(
+        options,
+        index,
+        inscribe.reveal,
+        self.recipient,
+        self.inscribe.fee_rate,
+      )?)
+    };
+
+    Ok(Output { inscribe, send })
+  }
+
+  fn holder(options: &Options, index: &Index, tick: &Tick, amount: u128) -> Result<Address> {
+    let network = options.chain().network();
+
+    let mut seen = HashSet::new();
+    let mut most = 0;
+
+    for outpoint in index.get_unspent_outputs(Wallet::load(options)?)?.keys() {
+      let Some(transaction) = index.get_transaction(outpoint.txid)? else {
+        continue;
+      };
+
+      let script_key = ScriptKey::from_script(
+        &transaction.output[usize::try_from(outpoint.vout).unwrap()].script_pubkey,
+        network,
+      );
+
+      if !seen.insert(script_key.clone()) {
+        continue;
+      }
+
+      let ScriptKey::Address(address) = &script_key else {
+        continue;
+      };
+
+      let available = index
+        .get_bit20_balance(&script_key, tick)?
+        .map(|balance| balance.overall_balance - balance.transferable_balance)
+        .unwrap_or_default();
+
+      if available >= amount {
+        return Ok(address.clone());
+      }
+
+      most = most.max(available);
+    }
+
+    bail!("insufficient `{tick}` balance, at most {most} available in a single wallet address")
+  }
+
+  fn send(
+    options: &Options,
+    index: &Index,
+    reveal: Txid,
+    recipient: Address,
+    fee_rate: FeeRate,
+  ) -> Result<Txid> {
+    let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
+
+    // the reveal is spent before it confirms. a transaction can't confirm
+    // before the one it spends, and the indexer applies a block's transactions
+    // in order, so the transfer inscription is credited to the holder before it
+    // moves, even when both confirm in the same block
+    let reveal_tx = index
+      .get_transaction(reveal)?
+      .with_context(|| format!("reveal transaction {reveal} not found"))?;
+
+    let utxos = index.get_unspent_outputs(Wallet::load(options)?)?;
+
+    let mut locked = index
+      .get_inscriptions(None)?
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<Vec<OutPoint>>();
+
+    locked.extend(index.get_dunic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?);
+
+    if !client.lock_unspent(&locked)? {
+      bail!("failed to lock UTXOs");
+    }
+
+    let unfunded_transaction = Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint {
+          txid: reveal,
+          vout: 0,
+        },
+        script_sig: Script::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        script_pubkey: recipient.script_pubkey(),
+        value: reveal_tx.output[0].value,
+      }],
+    };
+
+    // keep the recipient first, so the inscribed sat lands in its output
+    let unsigned_transaction = client
+      .fund_raw_transaction(
+        &unfunded_transaction,
+        Some(&bitcoincore_rpc::json::FundRawTransactionOptions {
+          change_position: Some(1),
+          fee_rate: Some(Amount::from_sat((fee_rate.n() * 1000.0).ceil() as u64)),
+          ..Default::default()
+        }),
+        Some(false),
+      )?
+      .hex;
+
+    let signed_transaction = client
+      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+      .hex;
+
+    Ok(client.send_raw_transaction(&signed_transaction)?)
+  }
+}
+
+impl InscribeOptions {
+  fn inscribe(
+    &self,
+    options: &Options,
+    index: &Index,
+    inscription: Inscription,
+    destination: Option<Address>,
+  ) -> Result<inscribe::Output> {
+    Inscribe {
+      satpoint: None,
+      fee_rate: self.fee_rate,
+      commit_fee_rate: self.commit_fee_rate,
+      file: None,
+      batch: None,
+      compress: false,
+      no_backup: self.no_backup,
+      no_limit: false,
+      dry_run: self.dry_run,
+      destination,
+      metadata: None,
+      parent: None,
+    }
+    .inscribe(options, index, inscription)
+  }
+}
+
+fn check_amount(name: &str, amount: &Num) -> Result<u128> {
+  ensure!(
+    amount.sign() == Sign::Plus,
+    "{name} must be greater than zero"
+  );
+
+  ensure!(
+    *amount <= *MAXIMUM_SUPPLY,
+    "{name} {amount} exceeds maximum supply of {}",
+    *MAXIMUM_SUPPLY,
+  );
+
+  Ok(amount.checked_to_u128()?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn deploy_body_omits_missing_limit() {
+    let tick = "doge".parse::<Tick>().unwrap();
+
+    let inscription = Body {
+      p: PROTOCOL_LITERAL,
+      op: "deploy",
+      tick: tick.as_str(),
+      max: Some("21000000".into()),
+      lim: None,
+      amt: None,
+    }
+    .into_inscription()
+    .unwrap();
+
+    assert_eq!(
+      inscription.body().unwrap(),
+      br#"{"p":"dev-20","op":"deploy","tick":"doge","max":"21000000"}"#
+    );
+  }
+
+  #[test]
+  fn amounts_must_be_positive_integers() {
+    assert_eq!(check_amount("amount", &"10".parse().unwrap()).unwrap(), 10);
+    assert!(check_amount("amount", &"0".parse().unwrap()).is_err());
+    assert!(check_amount("amount", &"1.5".parse().unwrap()).is_err());
+  }
+}
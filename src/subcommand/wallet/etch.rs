@@ -9,15 +9,45 @@ pub(crate) struct Etch {
   fee_rate: FeeRate,
   #[clap(long, help = "Etch dune <DUNE>. May contain `.` or `•`as spacers.")]
   dune: SpacedDune,
-  #[clap(long, help = "Set supply to <SUPPLY>.")]
+  #[clap(
+    long,
+    help = "Set supply to <SUPPLY>. Must equal premine plus cap times limit if mint terms are set."
+  )]
   supply: Decimal,
   #[clap(long, help = "Set currency symbol to <SYMBOL>.")]
   symbol: char,
+  #[clap(
+    long,
+    help = "Allocate <PREMINE> to the etcher. Defaults to <SUPPLY> without mint terms, otherwise to 0."
+  )]
+  premine: Option<Decimal>,
+  #[clap(long, help = "Allow at most <CAP> mints.")]
+  cap: Option<u128>,
+  #[clap(long, help = "Mint <LIMIT> dunes per mint.")]
+  limit: Option<Decimal>,
+  #[clap(long, help = "Open mints at block height <HEIGHT_START>.")]
+  height_start: Option<u64>,
+  #[clap(long, help = "Close mints at block height <HEIGHT_END>.")]
+  height_end: Option<u64>,
+  #[clap(long, help = "Open mints <OFFSET_START> blocks after the etching.")]
+  offset_start: Option<u64>,
+  #[clap(long, help = "Close mints <OFFSET_END> blocks after the etching.")]
+  offset_end: Option<u64>,
+  #[clap(long, help = "Opt in to future protocol changes.")]
+  turbo: bool,
+  #[clap(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Debug)]
 pub struct Output {
   pub transaction: Txid,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub dunestone: Option<Dunestone>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub script_pubkey: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub hex: Option<String>,
 }
 
 impl Etch {
@@ -31,7 +61,7 @@ impl Etch {
 
     index.update()?;
 
-    let SpacedDune { dune, spacers } = self.dune;
+    let dune = self.dune.dune;
 
     let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
 
@@ -54,31 +84,7 @@ impl Etch {
 
     ensure!(!dune.is_reserved(), "dune `{}` is reserved", dune);
 
-    ensure!(
-      self.divisibility <= crate::dunes::MAX_DIVISIBILITY,
-      "<DIVISIBILITY> must be equal to or less than 38"
-    );
-
-    let destination = get_change_address(&client)?;
-
-    let dunestone = Dunestone {
-      etching: Some(Etching {
-        divisibility: Some(self.divisibility),
-        terms: None,
-        premine: None,
-        dune: Some(dune),
-        spacers: Some(spacers),
-        symbol: Some(self.symbol),
-        turbo: false,
-      }),
-      edicts: vec![Edict {
-        amount: self.supply.to_amount(self.divisibility)?,
-        id: 0,
-        output: 1,
-      }],
-      pointer: None,
-      cenotaph: false,
-    };
+    let dunestone = self.dunestone(count + 1)?;
 
     let script_pubkey = dunestone.encipher();
 
@@ -88,13 +94,15 @@ impl Etch {
       script_pubkey.len()
     );
 
+    let destination = get_change_address(&client)?;
+
     let unfunded_transaction = Transaction {
       version: 1,
       lock_time: PackedLockTime::ZERO,
       input: Vec::new(),
       output: vec![
         TxOut {
-          script_pubkey,
+          script_pubkey: script_pubkey.clone(),
           value: 0,
         },
         TxOut {
@@ -116,13 +124,211 @@ impl Etch {
 
     let unsigned_transaction = fund_raw_transaction(&client, self.fee_rate, &unfunded_transaction)?;
 
+    if self.dry_run {
+      let transaction: Transaction = consensus::encode::deserialize(&unsigned_transaction)?;
+
+      return Ok(Box::new(Output {
+        transaction: transaction.txid(),
+        dunestone: Some(dunestone),
+        script_pubkey: Some(hex::encode(script_pubkey.as_bytes())),
+        hex: Some(hex::encode(&unsigned_transaction)),
+      }));
+    }
+
     let signed_transaction = client
         .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
         .hex;
 
     let transaction = client.send_raw_transaction(&signed_transaction)?;
 
-    Ok(Box::new(Output { transaction }))
+    Ok(Box::new(Output {
+      transaction,
+      dunestone: None,
+      script_pubkey: None,
+      hex: None,
+    }))
+  }
+
+  fn dunestone(&self, height: u64) -> Result<Dunestone> {
+    ensure!(
+      self.divisibility <= crate::dunes::MAX_DIVISIBILITY,
+      "<DIVISIBILITY> must be equal to or less than 38"
+    );
+
+    let supply = self.supply.to_amount(self.divisibility)?;
+
+    let has_terms = self.cap.is_some()
+      || self.limit.is_some()
+      || self.height_start.is_some()
+      || self.height_end.is_some()
+      || self.offset_start.is_some()
+      || self.offset_end.is_some();
+
+    let premine = match self.premine {
+      Some(premine) => premine.to_amount(self.divisibility)?,
+      None if has_terms => 0,
+      None => supply,
+    };
+
+    let terms = if has_terms {
+      let cap = self.cap.context("mint terms require `--cap`")?;
+      ensure!(cap > 0, "<CAP> must be greater than zero");
+
+      let limit = self
+        .limit
+        .context("mint terms require `--limit`")?
+        .to_amount(self.divisibility)?;
+      ensure!(limit > 0, "<LIMIT> must be greater than zero");
+      ensure!(
+        limit <= crate::dunes::MAX_LIMIT,
+        "<LIMIT> must be equal to or less than {}",
+        crate::dunes::MAX_LIMIT,
+      );
+
+      // the updater treats an etching whose premine and mintable supply
+      // overflow as a cenotaph
+      let total = cap
+        .checked_mul(limit)
+        .and_then(|mintable| mintable.checked_add(premine))
+        .context("premine plus cap times limit overflows")?;
+
+      ensure!(
+        total == supply,
+        "supply {supply} must equal premine plus cap times limit: {premine} + {cap} * {limit} = {total}",
+      );
+
+      if let (Some(start), Some(end)) = (self.height_start, self.height_end) {
+        ensure!(
+          end > start,
+          "<HEIGHT_END> must be greater than <HEIGHT_START>"
+        );
+      }
+
+      if let Some(end) = self.height_end {
+        ensure!(
+          end > height,
+          "<HEIGHT_END> must be greater than the etching height {height}"
+        );
+      }
+
+      if let (Some(start), Some(end)) = (self.offset_start, self.offset_end) {
+        ensure!(
+          end > start,
+          "<OFFSET_END> must be greater than <OFFSET_START>"
+        );
+      }
+
+      if let Some(end) = self.offset_end {
+        ensure!(end > 0, "<OFFSET_END> must be greater than zero");
+      }
+
+      Some(Terms {
+        cap: Some(cap),
+        limit: Some(limit),
+        height: (self.height_start, self.height_end),
+        offset: (self.offset_start, self.offset_end),
+      })
+    } else {
+      ensure!(
+        premine == supply,
+        "premine {premine} must equal supply {supply} without mint terms"
+      );
+
+      None
+    };
+
+    Ok(Dunestone {
+      etching: Some(Etching {
+        divisibility: Some(self.divisibility),
+        terms,
+        premine: (premine > 0).then_some(premine),
+        dune: Some(self.dune.dune),
+        spacers: Some(self.dune.spacers),
+        symbol: Some(self.symbol),
+        turbo: self.turbo,
+      }),
+      edicts: Vec::new(),
+      pointer: None,
+      cenotaph: false,
+    })
   }
 }
 
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn etch(args: &str) -> Etch {
+    match Arguments::try_parse_from(
+      format!("ord wallet etch --fee-rate 1 --dune AAAAAAAAAAAAA --symbol $ {args}")
+        .split_whitespace(),
+    )
+    .unwrap()
+    .subcommand
+    {
+      Subcommand::Wallet(Wallet::Etch(etch)) => etch,
+      subcommand => panic!("unexpected subcommand: {subcommand:?}"),
+    }
+  }
+
+  #[test]
+  fn supply_without_terms_is_premined() {
+    let dunestone = etch("--divisibility 0 --supply 1000").dunestone(1).unwrap();
+
+    let etching = dunestone.etching.unwrap();
+    assert_eq!(etching.premine, Some(1000));
+    assert_eq!(etching.terms, None);
+  }
+
+  #[test]
+  fn terms_must_add_up_to_supply() {
+    let dunestone = etch("--divisibility 1 --supply 110 --premine 10 --cap 10 --limit 10")
+      .dunestone(1)
+      .unwrap();
+
+    let etching = dunestone.etching.unwrap();
+    assert_eq!(etching.premine, Some(100));
+    assert_eq!(
+      etching.terms,
+      Some(Terms {
+        cap: Some(10),
+        limit: Some(100),
+        height: (None, None),
+        offset: (None, None),
+      })
+    );
+
+    assert_eq!(
+      etch("--divisibility 0 --supply 100 --premine 10 --cap 10 --limit 10")
+        .dunestone(1)
+        .unwrap_err()
+        .to_string(),
+      "supply 100 must equal premine plus cap times limit: 10 + 10 * 10 = 110",
+    );
+  }
+
+  #[test]
+  fn terms_overflow_is_rejected() {
+    assert_eq!(
+      etch(&format!(
+        "--divisibility 0 --supply 1 --premine 1 --cap {} --limit 2",
+        u128::MAX
+      ))
+      .dunestone(1)
+      .unwrap_err()
+      .to_string(),
+      "premine plus cap times limit overflows",
+    );
+  }
+
+  #[test]
+  fn height_end_must_be_in_the_future() {
+    assert_eq!(
+      etch("--divisibility 0 --supply 10 --cap 10 --limit 1 --height-end 5")
+        .dunestone(5)
+        .unwrap_err()
+        .to_string(),
+      "<HEIGHT_END> must be greater than the etching height 5",
+    );
+  }
+}
@@ -1,30 +1,385 @@
-use bitcoin::SchnorrSighashType;
-use bitcoin::secp256k1::schnorr::Signature;
 use {
   super::*,
   crate::wallet::Wallet,
+  batch::Batchfile,
   bitcoin::{
     blockdata::{opcodes, script},
-    PrivateKey,
-    util::schnorr::{TapTweak, TweakedKeyPair, TweakedPublicKey, UntweakedKeyPair},
-    util::sighash::{Prevouts, SighashCache},
+    hashes::hex::FromHex,
     locktime::PackedLockTime,
-    policy::MAX_STANDARD_TX_WEIGHT,
-    secp256k1::{self, constants::SCHNORR_SIGNATURE_SIZE, rand, Secp256k1, XOnlyPublicKey},
-    util::taproot::{ControlBlock, LeafVersion, TapLeafHash, TaprootBuilder},
-    Witness,
+    secp256k1::{self, rand, PublicKey, Secp256k1, SecretKey},
+    EcdsaSig, EcdsaSighashType, PrivateKey, Witness,
   },
-  bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, Timestamp},
+  bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, SignRawTransactionInput, Timestamp},
   bitcoincore_rpc::Client,
+  serde::de::DeserializeOwned,
 };
 use crate::sat_point::SatPoint;
 
+mod batch;
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Output {
-  commit: Txid,
-  inscription: InscriptionId,
-  reveal: Txid,
-  fees: u64,
+  pub commit: Txid,
+  pub inscription: InscriptionId,
+  pub parent: Option<InscriptionId>,
+  pub reveal: Txid,
+  pub transactions: Vec<Txid>,
+  pub fees: u64,
+}
+
+impl Output {
+  fn new(transactions: &[Transaction], parent: Option<InscriptionId>, fees: u64) -> Self {
+    Self {
+      commit: transactions[0].txid(),
+      inscription: transactions[1].txid().into(),
+      parent,
+      reveal: transactions[transactions.len() - 1].txid(),
+      transactions: transactions.iter().map(Transaction::txid).collect(),
+      fees,
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ParentInfo {
+  pub(crate) id: InscriptionId,
+  pub(crate) destination: Address,
+  pub(crate) location: SatPoint,
+  pub(crate) tx_out: TxOut,
+}
+
+/// Signed transactions of an inscription, or a batch of them, that have not
+/// all been broadcast yet, saved so `ord wallet resume` can pick up where
+/// broadcasting stopped.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct State<T> {
+  pub(crate) output: T,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub(crate) recovery_keys: Vec<String>,
+  pub(crate) transactions: Vec<String>,
+  pub(crate) broadcast: usize,
+}
+
+impl<T: Serialize + DeserializeOwned> State<T> {
+  pub(crate) fn new(output: T, recovery_keys: Vec<String>, transactions: &[Transaction]) -> Self {
+    Self {
+      output,
+      recovery_keys,
+      transactions: transactions
+        .iter()
+        .map(consensus::encode::serialize_hex)
+        .collect(),
+      broadcast: 0,
+    }
+  }
+
+  pub(crate) fn path(options: &Options, name: impl Display) -> Result<PathBuf> {
+    Ok(
+      options
+        .data_dir()?
+        .join("inscribe")
+        .join(format!("{name}.json")),
+    )
+  }
+
+  pub(crate) fn load(path: &Path) -> Result<Self> {
+    serde_json::from_slice(
+      &fs::read(path).with_context(|| format!("io error reading {}", path.display()))?,
+    )
+    .with_context(|| format!("{} is not a valid inscription state file", path.display()))
+  }
+
+  fn save(&self, path: &Path) -> Result {
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)
+        .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+
+    fs::write(path, serde_json::to_vec_pretty(self)?)
+      .with_context(|| format!("io error writing {}", path.display()))
+  }
+
+  pub(crate) fn transactions(&self) -> Result<Vec<Transaction>> {
+    self
+      .transactions
+      .iter()
+      .map(|hex| -> Result<Transaction> {
+        Ok(consensus::encode::deserialize(&Vec::from_hex(hex)?)?)
+      })
+      .collect()
+  }
+
+  /// Saves the state to `path`, broadcasts the remaining transactions in
+  /// order, recording progress after each one, and removes the state file
+  /// once they are all out.
+  pub(crate) fn broadcast(mut self, client: &Client, path: &Path) -> Result<T> {
+    let transactions = self.transactions()?;
+
+    self.save(path)?;
+
+    while self.broadcast < transactions.len() {
+      let transaction = &transactions[self.broadcast];
+
+      if let Err(err) = client.send_raw_transaction(transaction) {
+        // a previous run may have stopped after sending but before saving
+        if client
+          .get_raw_transaction(&transaction.txid(), None)
+          .is_err()
+        {
+          return Err(err).with_context(|| {
+            format!(
+              "failed to send transaction {} of {}, run `ord wallet resume {}` to retry",
+              self.broadcast + 1,
+              transactions.len(),
+              path.display(),
+            )
+          });
+        }
+      }
+
+      self.broadcast += 1;
+      self.save(path)?;
+    }
+
+    fs::remove_file(path).with_context(|| format!("failed to remove {}", path.display()))?;
+
+    Ok(self.output)
+  }
+}
+
+/// The transactions that follow the commit transaction. Each one spends a
+/// pay-to-script-hash output locked to a throwaway key, and reveals one
+/// partial of the inscription in its script_sig, where the redeem script
+/// drops it from the stack again.
+pub(crate) struct RevealChain {
+  destination: Address,
+  fees: Vec<u64>,
+  locks: Vec<Script>,
+  parent_info: Option<ParentInfo>,
+  partials: Vec<Vec<Script>>,
+  secret_key: SecretKey,
+}
+
+impl RevealChain {
+  const MAX_PARTIAL_LEN: usize = 1500;
+  const MAX_STANDARD_SCRIPT_SIG_SIZE: usize = 1650;
+  const SIGNATURE_LEN: usize = 73;
+
+  fn new(
+    inscription: &Inscription,
+    destination: Address,
+    parent_info: Option<ParentInfo>,
+    fee_rate: FeeRate,
+    no_limit: bool,
+  ) -> Result<Self> {
+    let mut partials: Vec<Vec<Script>> = Vec::new();
+    let mut partial_len = 0;
+
+    for group in inscription.script_sig_groups()? {
+      let group_len = group.iter().map(Script::len).sum::<usize>();
+
+      match partials.last_mut() {
+        Some(partial) if partial_len + group_len <= Self::MAX_PARTIAL_LEN => {
+          partial.extend(group);
+          partial_len += group_len;
+        }
+        _ => {
+          partials.push(group);
+          partial_len = group_len;
+        }
+      }
+    }
+
+    let secret_key = SecretKey::new(&mut rand::thread_rng());
+    let public_key = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+
+    let locks = partials
+      .iter()
+      .map(|partial| Self::lock_script(&public_key, partial.len()))
+      .collect();
+
+    let mut chain = Self {
+      destination,
+      fees: vec![0; partials.len()],
+      locks,
+      parent_info,
+      partials,
+      secret_key,
+    };
+
+    for i in 0..chain.partials.len() {
+      let mut transaction =
+        chain.unsigned_transaction(i, OutPoint::null(), &[0; Self::SIGNATURE_LEN]);
+
+      let script_sig_len = transaction.input[0].script_sig.len();
+
+      if !no_limit && script_sig_len > Self::MAX_STANDARD_SCRIPT_SIG_SIZE {
+        bail!(
+          "reveal transaction script_sig size greater than {} (MAX_STANDARD_SCRIPT_SIG_SIZE): {script_sig_len}",
+          Self::MAX_STANDARD_SCRIPT_SIG_SIZE,
+        );
+      }
+
+      // the parent input is signed by the wallet, so estimate a pay-to-pubkey-hash
+      // signature and public key
+      for txin in transaction.input.iter_mut().skip(1) {
+        txin.script_sig = script::Builder::new()
+          .push_slice(&[0; 72])
+          .push_slice(&[0; 33])
+          .into_script();
+      }
+
+      chain.fees[i] = fee_rate.fee(transaction.vsize()).to_sat();
+    }
+
+    if let Some(parent_info) = &chain.parent_info {
+      // the reveal fee comes out of the first input, so the parent sat lands
+      // in the parent output unless it sits too close to the end of its output
+      if parent_info.location.offset + chain.reveal_fee() >= parent_info.tx_out.value {
+        bail!(
+          "parent at {} would not be returned by the reveal transaction",
+          parent_info.location
+        );
+      }
+    }
+
+    Ok(chain)
+  }
+
+  fn lock_script(public_key: &PublicKey, pushes: usize) -> Script {
+    let mut builder = script::Builder::new()
+      .push_slice(&public_key.serialize())
+      .push_opcode(opcodes::all::OP_CHECKSIGVERIFY);
+
+    for _ in 0..pushes {
+      builder = builder.push_opcode(opcodes::all::OP_DROP);
+    }
+
+    builder.push_opcode(opcodes::OP_TRUE).into_script()
+  }
+
+  fn unlock_script(partial: &[Script], signature: &[u8], lock: &Script) -> Script {
+    let mut script_sig = partial
+      .iter()
+      .flat_map(|push| push.to_bytes())
+      .collect::<Vec<u8>>();
+
+    script_sig.extend(
+      script::Builder::new()
+        .push_slice(signature)
+        .push_slice(lock.as_bytes())
+        .into_script()
+        .to_bytes(),
+    );
+
+    Script::from(script_sig)
+  }
+
+  fn commit_address(&self, network: Network) -> Result<Address> {
+    Ok(Address::p2sh(&self.locks[0], network)?)
+  }
+
+  fn fees(&self) -> u64 {
+    self.fees.iter().sum()
+  }
+
+  fn reveal_fee(&self) -> u64 {
+    self.fees[self.fees.len() - 1]
+  }
+
+  /// Value of the output spent by transaction `i`, enough to pay for it and
+  /// every transaction after it, and still leave the postage.
+  fn value(&self, i: usize) -> u64 {
+    TransactionBuilder::TARGET_POSTAGE.to_sat() + self.fees[i..].iter().sum::<u64>()
+  }
+
+  fn recovery_key(&self, network: Network) -> PrivateKey {
+    PrivateKey::new(self.secret_key, network)
+  }
+
+  fn unsigned_transaction(
+    &self,
+    i: usize,
+    previous_output: OutPoint,
+    signature: &[u8],
+  ) -> Transaction {
+    let mut input = vec![TxIn {
+      previous_output,
+      script_sig: Self::unlock_script(&self.partials[i], signature, &self.locks[i]),
+      witness: Witness::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+    }];
+
+    let mut output = vec![TxOut {
+      script_pubkey: match self.locks.get(i + 1) {
+        Some(lock) => Script::new_p2sh(&lock.script_hash()),
+        None => self.destination.script_pubkey(),
+      },
+      value: self.value(i + 1),
+    }];
+
+    if let Some(parent_info) = self
+      .parent_info
+      .as_ref()
+      .filter(|_| i + 1 == self.partials.len())
+    {
+      input.push(TxIn {
+        previous_output: parent_info.location.outpoint,
+        script_sig: Script::new(),
+        witness: Witness::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      });
+
+      output.push(TxOut {
+        script_pubkey: parent_info.destination.script_pubkey(),
+        value: parent_info.tx_out.value,
+      });
+    }
+
+    Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input,
+      output,
+    }
+  }
+
+  /// Builds and signs the chain on top of the commit output. Legacy txids
+  /// commit to script_sigs, so the commit transaction must already be signed.
+  fn transactions(&self, commit: OutPoint) -> Vec<Transaction> {
+    let secp256k1 = Secp256k1::new();
+
+    let mut previous_output = commit;
+    let mut transactions = Vec::new();
+
+    for i in 0..self.partials.len() {
+      let mut transaction = self.unsigned_transaction(i, previous_output, &[]);
+
+      let signature_hash =
+        transaction.signature_hash(0, &self.locks[i], EcdsaSighashType::All.to_u32());
+
+      let signature = secp256k1.sign_ecdsa(
+        &secp256k1::Message::from_slice(signature_hash.as_inner())
+          .expect("should be cryptographically secure hash"),
+        &self.secret_key,
+      );
+
+      transaction.input[0].script_sig = Self::unlock_script(
+        &self.partials[i],
+        &EcdsaSig::sighash_all(signature).to_vec(),
+        &self.locks[i],
+      );
+
+      previous_output = OutPoint {
+        txid: transaction.txid(),
+        vout: 0,
+      };
+
+      transactions.push(transaction);
+    }
+
+    transactions
+  }
 }
 
 #[derive(Debug, Parser)]
@@ -42,31 +397,84 @@ pub(crate) struct Inscribe {
     help = "Use <COMMIT_FEE_RATE> sats/vbyte for commit transaction.\nDefaults to <FEE_RATE> if unset."
   )]
   pub(crate) commit_fee_rate: Option<FeeRate>,
-  #[clap(help = "Shibescribe sat with contents of <FILE>")]
-  pub(crate) file: PathBuf,
+  #[clap(
+    help = "Shibescribe sat with contents of <FILE>",
+    required_unless_present = "batch",
+    conflicts_with = "batch"
+  )]
+  pub(crate) file: Option<PathBuf>,
+  #[clap(
+    long,
+    help = "Shibescribe every file listed in <BATCH>, a YAML manifest with per-inscription destination, metadata, parent and delegate.",
+    conflicts_with_all = ["destination", "metadata", "parent", "satpoint"]
+  )]
+  pub(crate) batch: Option<PathBuf>,
+  #[clap(
+    long,
+    help = "Compress inscription content with brotli or gzip, whichever is smaller."
+  )]
+  pub(crate) compress: bool,
   #[clap(long, help = "Do not back up recovery key.")]
   pub(crate) no_backup: bool,
   #[clap(
     long,
-    help = "Do not check that transactions are equal to or below the MAX_STANDARD_TX_WEIGHT of 400,000 weight units. Transactions over this limit are currently nonstandard and will not be relayed by bitcoind in its default configuration. Do not use this flag unless you understand the implications."
+    help = "Do not check that reveal transaction script_sigs are equal to or below the MAX_STANDARD_SCRIPT_SIG_SIZE of 1,650 bytes. Transactions over this limit are currently nonstandard and will not be relayed by dogecoind in its default configuration. Do not use this flag unless you understand the implications."
   )]
   pub(crate) no_limit: bool,
   #[clap(long, help = "Don't sign or broadcast transactions.")]
   pub(crate) dry_run: bool,
   #[clap(long, help = "Send inscription to <DESTINATION>.")]
   pub(crate) destination: Option<Address>,
+  #[clap(
+    long,
+    help = "Include JSON or CBOR in <METADATA> as inscription metadata. JSON is converted to CBOR."
+  )]
+  pub(crate) metadata: Option<PathBuf>,
+  #[clap(long, help = "Make inscription a child of <PARENT>.")]
+  pub(crate) parent: Option<InscriptionId>,
 }
 
 impl Inscribe {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
-    let inscription = Inscription::from_file(options.chain(), &self.file)?;
+    if let Some(batch) = &self.batch {
+      let batchfile = Batchfile::load(batch)?;
+
+      let index = Index::open(&options)?;
+      index.update()?;
+
+      return Ok(Box::new(batchfile.inscribe(&self, &options, &index)?));
+    }
+
+    let file = self
+      .file
+      .as_ref()
+      .ok_or_else(|| anyhow!("either <FILE> or --batch is required"))?;
+
+    let mut inscription = Inscription::from_file(options.chain(), file)?;
+
+    if self.compress {
+      inscription.compress()?;
+    }
+
+    if let Some(metadata) = &self.metadata {
+      inscription.metadata = Some(Self::parse_metadata(metadata)?);
+    }
 
     let index = Index::open(&options)?;
     index.update()?;
 
+    Ok(Box::new(self.inscribe(&options, &index, inscription)?))
+  }
+
+  pub(crate) fn inscribe(
+    &self,
+    options: &Options,
+    index: &Index,
+    mut inscription: Inscription,
+  ) -> Result<Output> {
     let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
 
-    let mut utxos = index.get_unspent_outputs(Wallet::load(&options)?)?;
+    let utxos = index.get_unspent_outputs(Wallet::load(options)?)?;
 
     let dunic_utxos = index.get_dunic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?;
 
@@ -76,64 +484,177 @@ impl Inscribe {
 
     let reveal_tx_destination = self
       .destination
+      .clone()
       .map(Ok)
       .unwrap_or_else(|| get_change_address(&client))?;
 
-    let (unsigned_commit_tx, reveal_tx, recovery_key_pair) =
-      Inscribe::create_inscription_transactions(
-        self.satpoint,
-        inscription,
-        inscriptions,
-        options.chain().network(),
-        utxos.clone(),
-        dunic_utxos,
-        commit_tx_change,
-        reveal_tx_destination,
-        self.commit_fee_rate.unwrap_or(self.fee_rate),
-        self.fee_rate,
-        self.no_limit,
-      )?;
-
-    utxos.insert(
-      reveal_tx.input[0].previous_output,
-      Amount::from_sat(
-        unsigned_commit_tx.output[reveal_tx.input[0].previous_output.vout as usize].value,
-      ),
-    );
+    let parent_info = match self.parent {
+      Some(parent) => {
+        inscription.parent = Some(parent.value());
+        Some(Self::get_parent_info(&client, index, &utxos, parent)?)
+      }
+      None => None,
+    };
 
-    let fees =
-      Self::calculate_fee(&unsigned_commit_tx, &utxos) + Self::calculate_fee(&reveal_tx, &utxos);
+    let network = options.chain().network();
 
-    let mut reveal = Txid::all_zeros();
-    let mut commit = Txid::all_zeros();
+    let (unsigned_commit_tx, reveal_chain) = Inscribe::create_inscription_transactions(
+      self.satpoint,
+      inscription,
+      parent_info,
+      inscriptions,
+      network,
+      utxos.clone(),
+      dunic_utxos,
+      commit_tx_change,
+      reveal_tx_destination,
+      self.commit_fee_rate.unwrap_or(self.fee_rate),
+      self.fee_rate,
+      self.no_limit,
+    )?;
+
+    let fees = Self::calculate_fee(&unsigned_commit_tx, &utxos) + reveal_chain.fees();
+
+    let commit_address = reveal_chain.commit_address(network)?;
 
     if self.dry_run {
-      reveal = reveal_tx.txid();
-      commit = unsigned_commit_tx.txid();
-    } else {
-      if !self.no_backup {
-        Inscribe::backup_recovery_key(&client, recovery_key_pair, options.chain().network())?;
-      }
+      let commit = Self::commit_outpoint(&unsigned_commit_tx, &commit_address);
+
+      let mut transactions = vec![unsigned_commit_tx];
+      transactions.extend(reveal_chain.transactions(commit));
+
+      return Ok(Output::new(&transactions, self.parent, fees));
+    }
+
+    if !self.no_backup {
+      Inscribe::backup_recovery_key(&client, reveal_chain.recovery_key(network))?;
+    }
 
-      let signed_raw_commit_tx = client
+    let commit_tx: Transaction = consensus::encode::deserialize(
+      &client
         .sign_raw_transaction_with_wallet(&unsigned_commit_tx, None, None)?
-        .hex;
+        .hex,
+    )?;
+
+    let commit = Self::commit_outpoint(&commit_tx, &commit_address);
 
-      commit = client
-        .send_raw_transaction(&signed_raw_commit_tx)
-        .context("Failed to send commit transaction")?;
+    let mut transactions = vec![commit_tx];
+    transactions.extend(reveal_chain.transactions(commit));
+
+    if let Some(parent_info) = &reveal_chain.parent_info {
+      Self::sign_parent_input(&client, transactions.last_mut().unwrap(), parent_info)?;
+    }
 
-      reveal = client
-        .send_raw_transaction(&reveal_tx)
-        .context("Failed to send reveal transaction")?;
+    let output = Output::new(&transactions, self.parent, fees);
+
+    let recovery_keys = if self.no_backup {
+      Vec::new()
+    } else {
+      vec![reveal_chain.recovery_key(network).to_wif()]
     };
 
-    Ok(Box::new(Output {
-      commit,
-      reveal,
-      inscription: reveal.into(),
-      fees,
-    }))
+    let path = State::<Output>::path(options, output.inscription)?;
+
+    State::new(output, recovery_keys, &transactions).broadcast(&client, &path)
+  }
+
+  /// The wallet signs the parent input of a reveal transaction. The parent
+  /// output may come from a transaction that hasn't been broadcast yet, so
+  /// it is passed along.
+  fn sign_parent_input(
+    client: &Client,
+    reveal_tx: &mut Transaction,
+    parent_info: &ParentInfo,
+  ) -> Result {
+    let mut signed_reveal_tx: Transaction = consensus::encode::deserialize(
+      &client
+        .sign_raw_transaction_with_wallet(
+          &*reveal_tx,
+          Some(&[SignRawTransactionInput {
+            txid: parent_info.location.outpoint.txid,
+            vout: parent_info.location.outpoint.vout,
+            script_pub_key: parent_info.tx_out.script_pubkey.clone(),
+            redeem_script: None,
+            amount: Some(Amount::from_sat(parent_info.tx_out.value)),
+          }]),
+          None,
+        )?
+        .hex,
+    )?;
+
+    // the wallet only signs the parent input, keep our own script_sig
+    signed_reveal_tx.input[0].script_sig = reveal_tx.input[0].script_sig.clone();
+
+    *reveal_tx = signed_reveal_tx;
+
+    Ok(())
+  }
+
+  fn commit_outpoint(commit_tx: &Transaction, commit_address: &Address) -> OutPoint {
+    let vout = commit_tx
+      .output
+      .iter()
+      .position(|output| output.script_pubkey == commit_address.script_pubkey())
+      .expect("should find sat commit/inscription output");
+
+    OutPoint {
+      txid: commit_tx.txid(),
+      vout: vout.try_into().unwrap(),
+    }
+  }
+
+  fn parse_metadata(path: &Path) -> Result<Vec<u8>> {
+    let bytes = fs::read(path).with_context(|| format!("io error reading {}", path.display()))?;
+
+    if path.extension().and_then(|extension| extension.to_str()) == Some("cbor") {
+      ciborium::from_reader::<ciborium::Value, _>(bytes.as_slice())
+        .with_context(|| format!("{} is not valid CBOR", path.display()))?;
+
+      return Ok(bytes);
+    }
+
+    let json = serde_json::from_slice::<serde_json::Value>(&bytes)
+      .with_context(|| format!("{} is not valid JSON", path.display()))?;
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&json, &mut cbor)?;
+
+    Ok(cbor)
+  }
+
+  fn get_parent_info(
+    client: &Client,
+    index: &Index,
+    utxos: &BTreeMap<OutPoint, Amount>,
+    parent: InscriptionId,
+  ) -> Result<ParentInfo> {
+    let location = index
+      .get_inscription_satpoint_by_id(parent)?
+      .ok_or_else(|| anyhow!("parent {parent} does not exist"))?;
+
+    let value = utxos
+      .get(&location.outpoint)
+      .ok_or_else(|| anyhow!("parent {parent} not in wallet"))?;
+
+    let script_pubkey = index
+      .get_transaction(location.outpoint.txid)?
+      .and_then(|tx| {
+        tx.output
+          .into_iter()
+          .nth(location.outpoint.vout.try_into().unwrap())
+      })
+      .map(|tx_out| tx_out.script_pubkey)
+      .ok_or_else(|| anyhow!("parent {parent} output {} not found", location.outpoint))?;
+
+    Ok(ParentInfo {
+      id: parent,
+      destination: get_change_address(client)?,
+      location,
+      tx_out: TxOut {
+        value: value.to_sat(),
+        script_pubkey,
+      },
+    })
   }
 
   fn calculate_fee(tx: &Transaction, utxos: &BTreeMap<OutPoint, Amount>) -> u64 {
@@ -148,6 +669,7 @@ impl Inscribe {
   fn create_inscription_transactions(
     satpoint: Option<SatPoint>,
     inscription: Inscription,
+    parent_info: Option<ParentInfo>,
     inscriptions: BTreeMap<SatPoint, InscriptionId>,
     network: Network,
     utxos: BTreeMap<OutPoint, Amount>,
@@ -157,7 +679,7 @@ impl Inscribe {
     commit_fee_rate: FeeRate,
     reveal_fee_rate: FeeRate,
     no_limit: bool,
-  ) -> Result<(Transaction, Transaction, TweakedKeyPair)> {
+  ) -> Result<(Transaction, RevealChain)> {
     let satpoint = if let Some(satpoint) = satpoint {
       satpoint
     } else {
@@ -189,466 +711,312 @@ impl Inscribe {
       }
     }
 
-    let secp256k1 = Secp256k1::new();
-    let key_pair = UntweakedKeyPair::new(&secp256k1, &mut rand::thread_rng());
-    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
-
-    let reveal_script = inscription.append_reveal_script(
-      script::Builder::new()
-        .push_slice(&public_key.serialize())
-        .push_opcode(opcodes::all::OP_CHECKSIG),
-    );
-
-    let taproot_spend_info = TaprootBuilder::new()
-      .add_leaf(0, reveal_script.clone())
-      .expect("adding leaf should work")
-      .finalize(&secp256k1, public_key)
-      .expect("finalizing taproot builder should work");
-
-    let control_block = taproot_spend_info
-      .control_block(&(reveal_script.clone(), LeafVersion::TapScript))
-      .expect("should compute control block");
-
-    let commit_tx_address = Address::p2tr_tweaked(taproot_spend_info.output_key(), network);
-
-    let (_, reveal_fee) = Self::build_reveal_transaction(
-      &control_block,
+    let reveal_chain = RevealChain::new(
+      &inscription,
+      destination,
+      parent_info,
       reveal_fee_rate,
-      OutPoint::null(),
-      TxOut {
-        script_pubkey: destination.script_pubkey(),
-        value: 0,
-      },
-      &reveal_script,
-    );
+      no_limit,
+    )?;
 
     let unsigned_commit_tx = TransactionBuilder::build_transaction_with_value(
       satpoint,
       inscriptions,
       utxos,
-      commit_tx_address.clone(),
+      reveal_chain.commit_address(network)?,
       dunic_utxos,
       change,
       commit_fee_rate,
-      reveal_fee + TransactionBuilder::TARGET_POSTAGE,
+      Amount::from_sat(reveal_chain.value(0)),
     )?;
 
-    let (vout, output) = unsigned_commit_tx
-      .output
-      .iter()
-      .enumerate()
-      .find(|(_vout, output)| output.script_pubkey == commit_tx_address.script_pubkey())
-      .expect("should find sat commit/inscription output");
-
-    let (mut reveal_tx, fee) = Self::build_reveal_transaction(
-      &control_block,
-      reveal_fee_rate,
-      OutPoint {
-        txid: unsigned_commit_tx.txid(),
-        vout: vout.try_into().unwrap(),
-      },
-      TxOut {
-        script_pubkey: destination.script_pubkey(),
-        value: output.value,
-      },
-      &reveal_script,
-    );
-
-    reveal_tx.output[0].value = reveal_tx.output[0]
-      .value
-      .checked_sub(fee.to_sat())
-      .context("commit transaction output value insufficient to pay transaction fee")?;
-
-    if reveal_tx.output[0].value < reveal_tx.output[0].script_pubkey.dust_value().to_sat() {
-      bail!("commit transaction output would be dust");
-    }
-
-    let mut sighash_cache = SighashCache::new(&mut reveal_tx);
-
-    let signature_hash = sighash_cache
-      .taproot_script_spend_signature_hash(
-        0,
-        &Prevouts::All(&[output]),
-        TapLeafHash::from_script(&reveal_script, LeafVersion::TapScript),
-        SchnorrSighashType::Default,
-      )
-      .expect("signature hash should compute");
-
-    let signature = secp256k1.sign_schnorr(
-      &secp256k1::Message::from_slice(signature_hash.as_inner())
-        .expect("should be cryptographically secure hash"),
-      &key_pair,
-    );
-
-    let witness = sighash_cache
-      .witness_mut(0)
-      .expect("getting mutable witness reference should work");
-    witness.push(signature.as_ref());
-    witness.push(reveal_script);
-    witness.push(&control_block.serialize());
-
-    let recovery_key_pair = key_pair.tap_tweak(&secp256k1, taproot_spend_info.merkle_root());
-
-    let (x_only_pub_key, _parity) = recovery_key_pair.to_inner().x_only_public_key();
-    assert_eq!(
-      Address::p2tr_tweaked(
-        TweakedPublicKey::dangerous_assume_tweaked(x_only_pub_key),
-        network,
-      ),
-      commit_tx_address
-    );
-
-    let reveal_weight = reveal_tx.weight();
-
-    if !no_limit && reveal_weight > MAX_STANDARD_TX_WEIGHT.try_into().unwrap() {
-      bail!(
-        "reveal transaction weight greater than {MAX_STANDARD_TX_WEIGHT} (MAX_STANDARD_TX_WEIGHT): {reveal_weight}"
-      );
-    }
-
-    Ok((unsigned_commit_tx, reveal_tx, recovery_key_pair))
+    Ok((unsigned_commit_tx, reveal_chain))
   }
 
-  fn backup_recovery_key(
-    client: &Client,
-    recovery_key_pair: TweakedKeyPair,
-    network: Network,
-  ) -> Result {
-    let recovery_private_key = PrivateKey::new(recovery_key_pair.to_inner().secret_key(), network);
-
-    let info = client.get_descriptor_info(&format!("rawtr({})", recovery_private_key.to_wif()))?;
+  fn backup_recovery_key(client: &Client, recovery_private_key: PrivateKey) -> Result {
+    let info = client.get_descriptor_info(&format!("pkh({})", recovery_private_key.to_wif()))?;
 
     let response = client.import_descriptors(ImportDescriptors {
-      descriptor: format!("rawtr({})#{}", recovery_private_key.to_wif(), info.checksum),
+      descriptor: format!("pkh({})#{}", recovery_private_key.to_wif(), info.checksum),
       timestamp: Timestamp::Now,
       active: Some(false),
       range: None,
       next_index: None,
       internal: Some(false),
-      label: Some("commit tx recovery key".to_string()),
+      label: Some("inscription recovery key".to_string()),
     })?;
 
     for result in response {
       if !result.success {
-        return Err(anyhow!("commit tx recovery key import failed"));
+        return Err(anyhow!("inscription recovery key import failed"));
       }
     }
 
     Ok(())
   }
-
-  fn build_reveal_transaction(
-    control_block: &ControlBlock,
-    fee_rate: FeeRate,
-    input: OutPoint,
-    output: TxOut,
-    script: &Script,
-  ) -> (Transaction, Amount) {
-    let reveal_tx = Transaction {
-      input: vec![TxIn {
-        previous_output: input,
-        script_sig: script::Builder::new().into_script(),
-        witness: Witness::new(),
-        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
-      }],
-      output: vec![output],
-      lock_time: PackedLockTime::ZERO,
-      version: 1,
-    };
-
-    let fee = {
-      let mut reveal_tx = reveal_tx.clone();
-
-      reveal_tx.input[0].witness.push(
-        Signature::from_slice(&[0; SCHNORR_SIGNATURE_SIZE])
-          .unwrap()
-          .as_ref(),
-      );
-      reveal_tx.input[0].witness.push(script);
-      reveal_tx.input[0].witness.push(&control_block.serialize());
-
-      fee_rate.fee(reveal_tx.vsize())
-    };
-
-    (reveal_tx, fee)
-  }
 }
 
 #[cfg(test)]
 mod tests {
-  use bitcoin::blockdata::constants::COIN_VALUE;
   use super::*;
 
-  #[test]
-  fn reveal_transaction_pays_fee() {
-    let utxos = vec![(outpoint(1), Amount::from_sat(20000))];
-    let inscription = inscription("text/plain", "ord");
-    let commit_address = change(0);
-    let reveal_address = recipient();
-
-    let (commit_tx, reveal_tx, _private_key) = Inscribe::create_inscription_transactions(
+  fn create(
+    inscription: Inscription,
+    parent_info: Option<ParentInfo>,
+    utxos: Vec<(OutPoint, Amount)>,
+    fee_rate: f64,
+    no_limit: bool,
+  ) -> Result<(Transaction, RevealChain)> {
+    Inscribe::create_inscription_transactions(
       Some(satpoint(1, 0)),
       inscription,
+      parent_info,
       BTreeMap::new(),
       Network::Bitcoin,
       utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
-      FeeRate::try_from(1.0).unwrap(),
-      FeeRate::try_from(1.0).unwrap(),
-      false,
+      BTreeSet::new(),
+      [change(0), change(1)],
+      recipient(),
+      FeeRate::try_from(fee_rate).unwrap(),
+      FeeRate::try_from(fee_rate).unwrap(),
+      no_limit,
     )
-    .unwrap();
+  }
+
+  fn commit_outpoint(commit_tx: &Transaction, reveal_chain: &RevealChain) -> OutPoint {
+    Inscribe::commit_outpoint(
+      commit_tx,
+      &reveal_chain.commit_address(Network::Bitcoin).unwrap(),
+    )
+  }
+
+  #[test]
+  fn json_metadata_is_converted_to_cbor() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("metadata.json");
+    fs::write(&path, r#"{"trait": "woof"}"#).unwrap();
 
-    #[allow(clippy::cast_possible_truncation)]
-    #[allow(clippy::cast_sign_loss)]
-    let fee = Amount::from_sat((1.0 * (reveal_tx.vsize() as f64)).ceil() as u64);
+    let cbor = Inscribe::parse_metadata(&path).unwrap();
 
     assert_eq!(
-      reveal_tx.output[0].value,
-      20000 - fee.to_sat() - (20000 - commit_tx.output[0].value),
+      ciborium::from_reader::<serde_json::Value, _>(cbor.as_slice()).unwrap(),
+      serde_json::json!({"trait": "woof"}),
     );
   }
 
   #[test]
-  fn inscript_tansactions_opt_in_to_rbf() {
-    let utxos = vec![(outpoint(1), Amount::from_sat(20000))];
-    let inscription = inscription("text/plain", "ord");
-    let commit_address = change(0);
-    let reveal_address = recipient();
+  fn invalid_cbor_metadata_is_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("metadata.cbor");
+    fs::write(&path, [0xff]).unwrap();
 
-    let (commit_tx, reveal_tx, _) = Inscribe::create_inscription_transactions(
-      Some(satpoint(1, 0)),
-      inscription,
-      BTreeMap::new(),
-      Network::Bitcoin,
-      utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
-      FeeRate::try_from(1.0).unwrap(),
-      FeeRate::try_from(1.0).unwrap(),
+    assert_eq!(
+      Inscribe::parse_metadata(&path).unwrap_err().to_string(),
+      format!("{} is not valid CBOR", path.display()),
+    );
+  }
+
+  #[test]
+  fn small_inscription_is_revealed_in_one_transaction() {
+    let (commit_tx, reveal_chain) = create(
+      inscription("text/plain", "ord"),
+      None,
+      vec![(outpoint(1), Amount::from_sat(100_000))],
+      1.0,
       false,
     )
     .unwrap();
 
-    assert!(commit_tx.is_explicitly_rbf());
-    assert!(reveal_tx.is_explicitly_rbf());
-  }
+    let commit = commit_outpoint(&commit_tx, &reveal_chain);
 
-  #[test]
-  fn inscribe_with_no_satpoint_and_no_cardinal_utxos() {
-    let utxos = vec![(outpoint(1), Amount::from_sat(1000))];
-    let mut inscriptions = BTreeMap::new();
-    inscriptions.insert(
-      SatPoint {
-        outpoint: outpoint(1),
-        offset: 0,
-      },
-      inscription_id(1),
-    );
+    let transactions = reveal_chain.transactions(commit);
 
-    let inscription = inscription("text/plain", "ord");
-    let satpoint = None;
-    let commit_address = change(0);
-    let reveal_address = recipient();
+    assert_eq!(transactions.len(), 1);
 
-    let error = Inscribe::create_inscription_transactions(
-      satpoint,
-      inscription,
-      inscriptions,
-      Network::Bitcoin,
-      utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
-      FeeRate::try_from(1.0).unwrap(),
-      FeeRate::try_from(1.0).unwrap(),
-      false,
-    )
-    .unwrap_err()
-    .to_string();
+    let reveal_tx = &transactions[0];
 
-    assert!(
-      error.contains("wallet contains no cardinal utxos"),
-      "{}",
-      error
+    assert_eq!(reveal_tx.input[0].previous_output, commit);
+    assert_eq!(
+      reveal_tx.output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(
+      reveal_tx.output[0].value,
+      TransactionBuilder::TARGET_POSTAGE.to_sat()
+    );
+    assert_eq!(
+      commit_tx.output[commit.vout as usize].value,
+      TransactionBuilder::TARGET_POSTAGE.to_sat() + reveal_chain.fees(),
+    );
+    assert_eq!(
+      Inscription::from_transactions(transactions),
+      ParsedInscription::Complete(inscription("text/plain", "ord")),
     );
   }
 
   #[test]
-  fn inscribe_with_no_satpoint_and_enough_cardinal_utxos() {
-    let utxos = vec![
-      (outpoint(1), Amount::from_sat(20_000)),
-      (outpoint(2), Amount::from_sat(20_000)),
-    ];
-    let mut inscriptions = BTreeMap::new();
-    inscriptions.insert(
-      SatPoint {
-        outpoint: outpoint(1),
-        offset: 0,
-      },
-      inscription_id(1),
-    );
-
-    let inscription = inscription("text/plain", "ord");
-    let satpoint = None;
-    let commit_address = change(0);
-    let reveal_address = recipient();
-
-    assert!(Inscribe::create_inscription_transactions(
-      satpoint,
-      inscription,
-      inscriptions,
-      Network::Bitcoin,
-      utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
-      FeeRate::try_from(1.0).unwrap(),
-      FeeRate::try_from(1.0).unwrap(),
+  fn large_inscription_is_chained_across_transactions() {
+    let inscription = inscription("text/plain", [7; 10_000]);
+
+    let (commit_tx, reveal_chain) = create(
+      inscription.clone(),
+      None,
+      vec![(outpoint(1), Amount::from_sat(1_000_000))],
+      1.0,
       false,
     )
-    .is_ok())
-  }
+    .unwrap();
 
-  #[test]
-  fn inscribe_with_custom_fee_rate() {
-    let utxos = vec![
-      (outpoint(1), Amount::from_sat(10_000)),
-      (outpoint(2), Amount::from_sat(20_000)),
-    ];
-    let mut inscriptions = BTreeMap::new();
-    inscriptions.insert(
-      SatPoint {
-        outpoint: outpoint(1),
-        offset: 0,
-      },
-      inscription_id(1),
-    );
+    let commit = commit_outpoint(&commit_tx, &reveal_chain);
 
-    let inscription = inscription("text/plain", "ord");
-    let satpoint = None;
-    let commit_address = change(0);
-    let reveal_address = recipient();
-    let fee_rate = 3.3;
+    let transactions = reveal_chain.transactions(commit);
 
-    let (commit_tx, reveal_tx, _private_key) = Inscribe::create_inscription_transactions(
-      satpoint,
-      inscription,
-      inscriptions,
-      bitcoin::Network::Signet,
-      utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
-      FeeRate::try_from(fee_rate).unwrap(),
-      FeeRate::try_from(fee_rate).unwrap(),
-      false,
-    )
-    .unwrap();
+    assert!(transactions.len() > 1);
 
-    let sig_vbytes = 17;
-    let fee = FeeRate::try_from(fee_rate)
-      .unwrap()
-      .fee(commit_tx.vsize() + sig_vbytes)
-      .to_sat();
+    let mut previous_output = commit;
+    let mut value = commit_tx.output[commit.vout as usize].value;
 
-    let reveal_value = commit_tx
-      .output
-      .iter()
-      .map(|o| o.value)
-      .reduce(|acc, i| acc + i)
-      .unwrap();
+    for (transaction, fee) in transactions.iter().zip(&reveal_chain.fees) {
+      assert_eq!(transaction.input[0].previous_output, previous_output);
+      assert!(transaction.input[0].script_sig.len() <= RevealChain::MAX_STANDARD_SCRIPT_SIG_SIZE);
+      assert_eq!(transaction.output[0].value, value - fee);
 
-    assert_eq!(reveal_value, 20_000 - fee);
+      previous_output = OutPoint {
+        txid: transaction.txid(),
+        vout: 0,
+      };
+      value = transaction.output[0].value;
+    }
 
-    let fee = FeeRate::try_from(fee_rate)
-      .unwrap()
-      .fee(reveal_tx.vsize())
-      .to_sat();
+    assert_eq!(value, TransactionBuilder::TARGET_POSTAGE.to_sat());
 
     assert_eq!(
-      reveal_tx.output[0].value,
-      20_000 - fee - (20_000 - commit_tx.output[0].value),
+      Inscription::from_transactions(transactions),
+      ParsedInscription::Complete(inscription),
     );
   }
 
   #[test]
-  fn inscribe_with_commit_fee_rate() {
-    let utxos = vec![
-      (outpoint(1), Amount::from_sat(10_000)),
-      (outpoint(2), Amount::from_sat(20_000)),
-    ];
-    let mut inscriptions = BTreeMap::new();
-    inscriptions.insert(
-      SatPoint {
-        outpoint: outpoint(1),
-        offset: 0,
-      },
-      inscription_id(1),
-    );
-
-    let inscription = inscription("text/plain", "ord");
-    let satpoint = None;
-    let commit_address = change(0);
-    let reveal_address = recipient();
-    let commit_fee_rate = 3.3;
-    let fee_rate = 1.0;
-
-    let (commit_tx, reveal_tx, _private_key) = Inscribe::create_inscription_transactions(
-      satpoint,
-      inscription,
-      inscriptions,
-      bitcoin::Network::Signet,
-      utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
-      FeeRate::try_from(commit_fee_rate).unwrap(),
-      FeeRate::try_from(fee_rate).unwrap(),
+  fn reveal_transactions_pay_fee_rate() {
+    let (commit_tx, reveal_chain) = create(
+      inscription("text/plain", [7; 5_000]),
+      None,
+      vec![(outpoint(1), Amount::from_sat(1_000_000))],
+      3.3,
       false,
     )
     .unwrap();
 
-    let sig_vbytes = 17;
-    let fee = FeeRate::try_from(commit_fee_rate)
-      .unwrap()
-      .fee(commit_tx.vsize() + sig_vbytes)
-      .to_sat();
+    let fee_rate = FeeRate::try_from(3.3).unwrap();
 
-    let reveal_value = commit_tx
-      .output
-      .iter()
-      .map(|o| o.value)
-      .reduce(|acc, i| acc + i)
-      .unwrap();
+    let transactions = reveal_chain.transactions(commit_outpoint(&commit_tx, &reveal_chain));
 
-    assert_eq!(reveal_value, 20_000 - fee);
+    for (transaction, fee) in transactions.iter().zip(&reveal_chain.fees) {
+      assert!(*fee >= fee_rate.fee(transaction.vsize()).to_sat());
+    }
+  }
 
-    let fee = FeeRate::try_from(fee_rate)
-      .unwrap()
-      .fee(reveal_tx.vsize())
-      .to_sat();
+  #[test]
+  fn reveal_transaction_returns_parent() {
+    let parent_info = ParentInfo {
+      id: inscription_id(2),
+      destination: change(2),
+      location: satpoint(2, 0),
+      tx_out: TxOut {
+        value: 10_000,
+        script_pubkey: change(2).script_pubkey(),
+      },
+    };
+
+    let (commit_tx, reveal_chain) = create(
+      inscription("text/plain", "ord"),
+      Some(parent_info.clone()),
+      vec![(outpoint(1), Amount::from_sat(100_000))],
+      1.0,
+      false,
+    )
+    .unwrap();
+
+    let transactions = reveal_chain.transactions(commit_outpoint(&commit_tx, &reveal_chain));
+    let reveal_tx = transactions.last().unwrap();
 
+    assert_eq!(reveal_tx.input.len(), 2);
+    assert_eq!(reveal_tx.input[1].previous_output, outpoint(2));
+    assert_eq!(reveal_tx.output[1].value, 10_000);
     assert_eq!(
-      reveal_tx.output[0].value,
-      20_000 - fee - (20_000 - commit_tx.output[0].value),
+      reveal_tx.output[1].script_pubkey,
+      parent_info.destination.script_pubkey()
     );
   }
 
   #[test]
-  fn inscribe_over_max_standard_tx_weight() {
-    let utxos = vec![(outpoint(1), Amount::from_sat(50 * COIN_VALUE))];
+  fn oversized_fields_are_rejected() {
+    let mut inscription = inscription("text/plain", "ord");
+    inscription.metadata = Some(vec![0; 2000]);
+
+    assert!(create(
+      inscription.clone(),
+      None,
+      vec![(outpoint(1), Amount::from_sat(100_000))],
+      1.0,
+      false,
+    )
+    .unwrap_err()
+    .to_string()
+    .starts_with(
+      "reveal transaction script_sig size greater than 1650 (MAX_STANDARD_SCRIPT_SIG_SIZE)"
+    ));
+
+    assert!(create(
+      inscription,
+      None,
+      vec![(outpoint(1), Amount::from_sat(100_000))],
+      1.0,
+      true,
+    )
+    .is_ok());
+  }
 
-    let inscription = inscription("text/plain", [0; MAX_STANDARD_TX_WEIGHT as usize]);
-    let satpoint = None;
-    let commit_address = change(0);
-    let reveal_address = recipient();
+  #[test]
+  fn inscription_transactions_opt_in_to_rbf() {
+    let (commit_tx, reveal_chain) = create(
+      inscription("text/plain", "ord"),
+      None,
+      vec![(outpoint(1), Amount::from_sat(100_000))],
+      1.0,
+      false,
+    )
+    .unwrap();
+
+    assert!(commit_tx.is_explicitly_rbf());
+
+    for transaction in reveal_chain.transactions(commit_outpoint(&commit_tx, &reveal_chain)) {
+      assert!(transaction.is_explicitly_rbf());
+    }
+  }
+
+  #[test]
+  fn inscribe_with_no_satpoint_and_no_cardinal_utxos() {
+    let utxos = vec![(outpoint(1), Amount::from_sat(1000))];
+    let mut inscriptions = BTreeMap::new();
+    inscriptions.insert(
+      SatPoint {
+        outpoint: outpoint(1),
+        offset: 0,
+      },
+      inscription_id(1),
+    );
 
     let error = Inscribe::create_inscription_transactions(
-      satpoint,
-      inscription,
-      BTreeMap::new(),
+      None,
+      inscription("text/plain", "ord"),
+      None,
+      inscriptions,
       Network::Bitcoin,
       utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
+      BTreeSet::new(),
+      [change(0), change(1)],
+      recipient(),
       FeeRate::try_from(1.0).unwrap(),
       FeeRate::try_from(1.0).unwrap(),
       false,
@@ -657,35 +1025,44 @@ mod tests {
     .to_string();
 
     assert!(
-      error.contains(&format!("reveal transaction weight greater than {MAX_STANDARD_TX_WEIGHT} (MAX_STANDARD_TX_WEIGHT): 402799")),
+      error.contains("wallet contains no cardinal utxos"),
       "{}",
       error
     );
   }
 
   #[test]
-  fn inscribe_with_no_max_standard_tx_weight() {
-    let utxos = vec![(outpoint(1), Amount::from_sat(50 * COIN_VALUE))];
-
-    let inscription = inscription("text/plain", [0; MAX_STANDARD_TX_WEIGHT as usize]);
-    let satpoint = None;
-    let commit_address = change(0);
-    let reveal_address = recipient();
-
-    let (_commit_tx, reveal_tx, _private_key) = Inscribe::create_inscription_transactions(
-      satpoint,
-      inscription,
-      BTreeMap::new(),
-      Network::Bitcoin,
-      utxos.into_iter().collect(),
-      [commit_address, change(1)],
-      reveal_address,
-      FeeRate::try_from(1.0).unwrap(),
-      FeeRate::try_from(1.0).unwrap(),
-      true,
+  fn state_round_trips_through_file() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("inscribe").join("state.json");
+
+    let (commit_tx, reveal_chain) = create(
+      inscription("text/plain", "ord"),
+      None,
+      vec![(outpoint(1), Amount::from_sat(100_000))],
+      1.0,
+      false,
     )
     .unwrap();
 
-    assert!(reveal_tx.size() >= MAX_STANDARD_TX_WEIGHT as usize);
+    let mut transactions = vec![commit_tx.clone()];
+    transactions.extend(reveal_chain.transactions(commit_outpoint(&commit_tx, &reveal_chain)));
+
+    let state = State::new(
+      Output::new(&transactions, None, 1000),
+      Vec::new(),
+      &transactions,
+    );
+
+    state.save(&path).unwrap();
+
+    let loaded = State::load(&path).unwrap();
+
+    assert_eq!(loaded, state);
+    assert_eq!(loaded.transactions().unwrap(), transactions);
+    assert_eq!(
+      loaded.output.inscription,
+      InscriptionId::from(transactions[1].txid())
+    );
   }
 }
@@ -0,0 +1,460 @@
+use {super::*, bitcoincore_rpc::json::FundRawTransactionOptions, std::collections::btree_map};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Output {
+  pub commit: Txid,
+  pub inscriptions: Vec<InscriptionInfo>,
+  pub fees: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct InscriptionInfo {
+  pub id: InscriptionId,
+  pub destination: Address,
+  pub parent: Option<InscriptionId>,
+  pub reveal: Txid,
+  pub transactions: Vec<Txid>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Batchfile {
+  pub(crate) inscriptions: Vec<BatchEntry>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct BatchEntry {
+  pub(crate) file: PathBuf,
+  pub(crate) destination: Option<Address>,
+  pub(crate) metadata: Option<serde_yaml::Value>,
+  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) delegate: Option<InscriptionId>,
+}
+
+impl Batchfile {
+  /// Loads a batch file, resolving inscription files relative to it.
+  pub(crate) fn load(path: &Path) -> Result<Self> {
+    let mut batchfile: Self = serde_yaml::from_reader(
+      File::open(path).with_context(|| format!("io error reading {}", path.display()))?,
+    )
+    .with_context(|| format!("{} is not a valid batch file", path.display()))?;
+
+    if batchfile.inscriptions.is_empty() {
+      bail!("batch file {} contains no inscriptions", path.display());
+    }
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+
+    for entry in &mut batchfile.inscriptions {
+      entry.file = dir.join(&entry.file);
+    }
+
+    Ok(batchfile)
+  }
+
+  pub(crate) fn inscribe(
+    self,
+    inscribe: &Inscribe,
+    options: &Options,
+    index: &Index,
+  ) -> Result<Output> {
+    let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
+
+    let utxos = index.get_unspent_outputs(Wallet::load(options)?)?;
+
+    let reveal_chains = self.reveal_chains(inscribe, options, index, &client, &utxos)?;
+
+    let mut locked = index
+      .get_inscriptions(None)?
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .filter(|outpoint| utxos.contains_key(outpoint))
+      .collect::<BTreeSet<OutPoint>>();
+
+    locked.extend(index.get_dunic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?);
+
+    let locked = locked.into_iter().collect::<Vec<OutPoint>>();
+
+    if !client.lock_unspent(&locked)? {
+      bail!("failed to lock UTXOs");
+    }
+
+    // the locks only need to outlive funding, so don't leave them behind when
+    // nothing was sent
+    match Self::commit_and_reveal(inscribe, options, &client, reveal_chains) {
+      Ok(output) if inscribe.dry_run => {
+        client.unlock_unspent(&locked)?;
+        Ok(output)
+      }
+      Ok(output) => Ok(output),
+      Err(err) => {
+        if let Err(unlock_err) = client.unlock_unspent(&locked) {
+          log::warn!("failed to unlock UTXOs: {unlock_err}");
+        }
+        Err(err)
+      }
+    }
+  }
+
+  /// Funds the commit transaction, then either returns the plan, for a dry
+  /// run, or signs and broadcasts every transaction.
+  fn commit_and_reveal(
+    inscribe: &Inscribe,
+    options: &Options,
+    client: &Client,
+    mut reveal_chains: Vec<RevealChain>,
+  ) -> Result<Output> {
+    let network = options.chain().network();
+
+    let unfunded_commit_tx = Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input: Vec::new(),
+      output: reveal_chains
+        .iter()
+        .map(|reveal_chain| {
+          Ok(TxOut {
+            script_pubkey: reveal_chain.commit_address(network)?.script_pubkey(),
+            value: reveal_chain.value(0),
+          })
+        })
+        .collect::<Result<Vec<TxOut>>>()?,
+    };
+
+    let fee_rate = inscribe.commit_fee_rate.unwrap_or(inscribe.fee_rate);
+
+    // keep the commit outputs first, in batch order, so chain `i` spends output `i`
+    let funded_commit_tx = client.fund_raw_transaction(
+      &unfunded_commit_tx,
+      Some(&FundRawTransactionOptions {
+        change_position: Some(reveal_chains.len().try_into().unwrap()),
+        fee_rate: Some(Amount::from_sat((fee_rate.n() * 1000.0).ceil() as u64)),
+        ..Default::default()
+      }),
+      Some(false),
+    )?;
+
+    let fees =
+      funded_commit_tx.fee.to_sat() + reveal_chains.iter().map(RevealChain::fees).sum::<u64>();
+
+    let unsigned_commit_tx: Transaction = consensus::encode::deserialize(&funded_commit_tx.hex)?;
+
+    eprintln!(
+      "Inscribing {} inscriptions in {} transactions for {fees} sats in fees",
+      reveal_chains.len(),
+      1 + reveal_chains
+        .iter()
+        .map(|reveal_chain| reveal_chain.partials.len())
+        .sum::<usize>(),
+    );
+
+    if inscribe.dry_run {
+      let chains = Self::transactions(&unsigned_commit_tx, &mut reveal_chains, None)?;
+
+      return Ok(Self::output(
+        &unsigned_commit_tx,
+        &reveal_chains,
+        &chains,
+        fees,
+      ));
+    }
+
+    let mut recovery_keys = Vec::new();
+
+    if !inscribe.no_backup {
+      for reveal_chain in &reveal_chains {
+        let recovery_key = reveal_chain.recovery_key(network);
+        Inscribe::backup_recovery_key(client, recovery_key)?;
+        recovery_keys.push(recovery_key.to_wif());
+      }
+    }
+
+    let commit_tx: Transaction = consensus::encode::deserialize(
+      &client
+        .sign_raw_transaction_with_wallet(&unsigned_commit_tx, None, None)?
+        .hex,
+    )?;
+
+    let chains = Self::transactions(&commit_tx, &mut reveal_chains, Some(client))?;
+
+    let output = Self::output(&commit_tx, &reveal_chains, &chains, fees);
+
+    let mut transactions = vec![commit_tx];
+    transactions.extend(chains.into_iter().flatten());
+
+    let path = State::<Output>::path(options, output.commit)?;
+
+    State::new(output, recovery_keys, &transactions).broadcast(client, &path)
+  }
+
+  /// Plans the chain of every inscription. A parent shared by several
+  /// inscriptions is passed from one reveal transaction to the next, moving
+  /// further into its output by each reveal fee.
+  fn reveal_chains(
+    self,
+    inscribe: &Inscribe,
+    options: &Options,
+    index: &Index,
+    client: &Client,
+    utxos: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<Vec<RevealChain>> {
+    let mut parent_infos = BTreeMap::<InscriptionId, ParentInfo>::new();
+    let mut reveal_chains = Vec::new();
+
+    for entry in self.inscriptions {
+      let mut inscription = Inscription::from_file(options.chain(), &entry.file)?;
+
+      if inscribe.compress {
+        inscription.compress()?;
+      }
+
+      if let Some(metadata) = &entry.metadata {
+        let mut cbor = Vec::new();
+        ciborium::into_writer(metadata, &mut cbor)?;
+        inscription.metadata = Some(cbor);
+      }
+
+      inscription.delegate = entry.delegate.map(InscriptionId::value);
+
+      let parent_info = match entry.parent {
+        Some(parent) => {
+          inscription.parent = Some(parent.value());
+
+          let parent_info = match parent_infos.entry(parent) {
+            btree_map::Entry::Occupied(entry) => entry.into_mut(),
+            btree_map::Entry::Vacant(entry) => {
+              entry.insert(Inscribe::get_parent_info(client, index, utxos, parent)?)
+            }
+          };
+
+          Some(parent_info.clone())
+        }
+        None => None,
+      };
+
+      let destination = match entry.destination {
+        Some(destination) => destination,
+        None => get_change_address(client)?,
+      };
+
+      let reveal_chain = RevealChain::new(
+        &inscription,
+        destination,
+        parent_info,
+        inscribe.fee_rate,
+        inscribe.no_limit,
+      )
+      .with_context(|| format!("failed to inscribe {}", entry.file.display()))?;
+
+      if let Some(parent) = entry.parent {
+        let parent_info = parent_infos.get_mut(&parent).unwrap();
+        parent_info.location.offset += reveal_chain.reveal_fee();
+        parent_info.tx_out.script_pubkey = parent_info.destination.script_pubkey();
+      }
+
+      reveal_chains.push(reveal_chain);
+    }
+
+    Ok(reveal_chains)
+  }
+
+  /// Signs every chain on top of the commit transaction. Parents passed
+  /// between reveal transactions are signed by the wallet, if there is one.
+  fn transactions(
+    commit_tx: &Transaction,
+    reveal_chains: &mut [RevealChain],
+    client: Option<&Client>,
+  ) -> Result<Vec<Vec<Transaction>>> {
+    let mut parent_outpoints = BTreeMap::<InscriptionId, OutPoint>::new();
+    let mut chains = Vec::new();
+
+    for (vout, reveal_chain) in reveal_chains.iter_mut().enumerate() {
+      if let Some(parent_info) = &mut reveal_chain.parent_info {
+        if let Some(outpoint) = parent_outpoints.get(&parent_info.id) {
+          parent_info.location.outpoint = *outpoint;
+        }
+      }
+
+      let mut transactions = reveal_chain.transactions(OutPoint {
+        txid: commit_tx.txid(),
+        vout: vout.try_into().unwrap(),
+      });
+
+      if let Some(parent_info) = &reveal_chain.parent_info {
+        let reveal_tx = transactions.last_mut().unwrap();
+
+        if let Some(client) = client {
+          Inscribe::sign_parent_input(client, reveal_tx, parent_info)?;
+        }
+
+        parent_outpoints.insert(
+          parent_info.id,
+          OutPoint {
+            txid: reveal_tx.txid(),
+            vout: 1,
+          },
+        );
+      }
+
+      chains.push(transactions);
+    }
+
+    Ok(chains)
+  }
+
+  fn output(
+    commit_tx: &Transaction,
+    reveal_chains: &[RevealChain],
+    chains: &[Vec<Transaction>],
+    fees: u64,
+  ) -> Output {
+    Output {
+      commit: commit_tx.txid(),
+      inscriptions: reveal_chains
+        .iter()
+        .zip(chains)
+        .map(|(reveal_chain, transactions)| InscriptionInfo {
+          id: transactions[0].txid().into(),
+          destination: reveal_chain.destination.clone(),
+          parent: reveal_chain
+            .parent_info
+            .as_ref()
+            .map(|parent_info| parent_info.id),
+          reveal: transactions[transactions.len() - 1].txid(),
+          transactions: transactions.iter().map(Transaction::txid).collect(),
+        })
+        .collect(),
+      fees,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn batchfile_is_loaded_relative_to_its_directory() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("batch.yaml");
+
+    fs::write(
+      &path,
+      format!(
+        "inscriptions:
+- file: a.txt
+  metadata:
+    trait: woof
+  parent: {}
+- file: /b.txt
+  destination: {}
+  delegate: {}
+",
+        inscription_id(1),
+        recipient(),
+        inscription_id(2),
+      ),
+    )
+    .unwrap();
+
+    assert_eq!(
+      Batchfile::load(&path).unwrap(),
+      Batchfile {
+        inscriptions: vec![
+          BatchEntry {
+            file: tempdir.path().join("a.txt"),
+            destination: None,
+            metadata: Some(serde_yaml::from_str("trait: woof").unwrap()),
+            parent: Some(inscription_id(1)),
+            delegate: None,
+          },
+          BatchEntry {
+            file: "/b.txt".into(),
+            destination: Some(recipient()),
+            metadata: None,
+            parent: None,
+            delegate: Some(inscription_id(2)),
+          },
+        ],
+      }
+    );
+  }
+
+  #[test]
+  fn batchfile_with_unknown_field_is_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("batch.yaml");
+
+    fs::write(&path, "inscriptions:\n- file: a.txt\n  postage: 1\n").unwrap();
+
+    assert_eq!(
+      Batchfile::load(&path).unwrap_err().to_string(),
+      format!("{} is not a valid batch file", path.display()),
+    );
+  }
+
+  #[test]
+  fn empty_batchfile_is_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("batch.yaml");
+
+    fs::write(&path, "inscriptions: []\n").unwrap();
+
+    assert_eq!(
+      Batchfile::load(&path).unwrap_err().to_string(),
+      format!("batch file {} contains no inscriptions", path.display()),
+    );
+  }
+
+  #[test]
+  fn shared_parent_is_passed_between_reveal_transactions() {
+    let parent_info = ParentInfo {
+      id: inscription_id(1),
+      destination: change(2),
+      location: satpoint(2, 0),
+      tx_out: TxOut {
+        value: 100_000,
+        script_pubkey: change(2).script_pubkey(),
+      },
+    };
+
+    let mut reveal_chains = (0..2)
+      .map(|_| {
+        RevealChain::new(
+          &inscription("text/plain", "ord"),
+          recipient(),
+          Some(parent_info.clone()),
+          FeeRate::try_from(1.0).unwrap(),
+          false,
+        )
+        .unwrap()
+      })
+      .collect::<Vec<RevealChain>>();
+
+    let commit_tx = Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    let chains = Batchfile::transactions(&commit_tx, &mut reveal_chains, None).unwrap();
+
+    assert_eq!(chains[0][0].input[1].previous_output, outpoint(2));
+    assert_eq!(
+      chains[1][0].input[0].previous_output,
+      OutPoint {
+        txid: commit_tx.txid(),
+        vout: 1,
+      }
+    );
+    assert_eq!(
+      chains[1][0].input[1].previous_output,
+      OutPoint {
+        txid: chains[0][0].txid(),
+        vout: 1,
+      }
+    );
+  }
+}
@@ -0,0 +1,105 @@
+use {super::*, crate::dunes::CLAIM_BIT, bitcoin::PackedLockTime};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Mint {
+  #[clap(long, help = "Use <FEE_RATE> sats/vbyte for mint transaction.")]
+  fee_rate: FeeRate,
+  #[clap(long, help = "Mint <DUNE>. May contain `.` or `•`as spacers.")]
+  dune: SpacedDune,
+  #[clap(long, help = "Send minted dunes to <DESTINATION>.")]
+  destination: Option<Address>,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct Output {
+  pub(crate) dune: SpacedDune,
+  pub(crate) pile: Pile,
+  pub(crate) mint: Txid,
+}
+
+impl Mint {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    ensure!(
+      index.has_dune_index(),
+      "`ord wallet mint` requires index created with `--index-dunes` flag",
+    );
+
+    index.update()?;
+
+    let dune = self.dune.dune;
+
+    let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
+
+    let count = client.get_block_count()?;
+
+    let (id, entry) = index
+      .dune(dune)?
+      .with_context(|| format!("dune `{}` has not been etched", self.dune))?;
+
+    let limit = entry
+      .mintable(count + 1)
+      .map_err(|err| anyhow!("dune `{}` {err}", self.dune))?;
+
+    ensure!(limit > 0, "dune `{}` has a mint limit of zero", self.dune);
+
+    let destination = match self.destination {
+      Some(destination) => destination,
+      None => get_change_address(&client)?,
+    };
+
+    let dunestone = Dunestone {
+      edicts: vec![Edict {
+        amount: limit,
+        id: u128::from(id) | CLAIM_BIT,
+        output: 1,
+      }],
+      ..Default::default()
+    };
+
+    let unfunded_transaction = Transaction {
+      version: 1,
+      lock_time: PackedLockTime::ZERO,
+      input: Vec::new(),
+      output: vec![
+        TxOut {
+          script_pubkey: dunestone.encipher(),
+          value: 0,
+        },
+        TxOut {
+          script_pubkey: destination.script_pubkey(),
+          value: TARGET_POSTAGE.to_sat(),
+        },
+      ],
+    };
+
+    let utxos = index.get_unspent_outputs(Wallet::load(&options)?)?;
+
+    let mut locked = index
+      .get_inscriptions(None)?
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<Vec<OutPoint>>();
+
+    locked.extend(index.get_dunic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?);
+
+    if !client.lock_unspent(&locked)? {
+      bail!("failed to lock UTXOs");
+    }
+
+    let unsigned_transaction = fund_raw_transaction(&client, self.fee_rate, &unfunded_transaction)?;
+
+    let signed_transaction = client
+      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+      .hex;
+
+    let mint = client.send_raw_transaction(&signed_transaction)?;
+
+    Ok(Box::new(Output {
+      dune: self.dune,
+      pile: entry.pile(limit),
+      mint,
+    }))
+  }
+}
@@ -0,0 +1,17 @@
+use {super::*, inscribe::State};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Resume {
+  #[clap(help = "Broadcast the remaining transactions of the inscription saved in <STATE>")]
+  state: PathBuf,
+}
+
+impl Resume {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
+
+    let output = State::<serde_json::Value>::load(&self.state)?.broadcast(&client, &self.state)?;
+
+    Ok(Box::new(output))
+  }
+}
@@ -3,15 +3,10 @@ use super::*;
 #[derive(Copy, Clone)]
 #[repr(u8)]
 pub(crate) enum Tag {
-  #[allow(unused)]
   Parent = 3,
-  #[allow(unused)]
   Metadata = 5,
-  #[allow(unused)]
   Metaprotocol = 7,
-  #[allow(unused)]
   ContentEncoding = 9,
-  #[allow(unused)]
   Delegate = 11,
   #[allow(unused)]
   Note = 15,
@@ -1,20 +1,28 @@
 use {boilerplate::Boilerplate, super::*};
 pub(crate) use {
+  address::AddressHtml,
   block::BlockHashAndConfirmations,
   block::BlockHtml,
+  block::BlockInfoJson,
   block::BlockJson,
-  bit20::BIT20,
+  bit20::{Dev20InscriptionReceiptsJson, Dev20ReceiptJson, Dev20TransactionReceiptsJson, BIT20},
   dune::{
-    DuneAddressJson, DuneBalance, DuneEntryJson, DuneHtml, DuneJson, DuneOutput, DuneOutputJson,
+    DuneAddressJson, DuneBalance, DuneEntryJson, DuneEventsJson, DuneHolderJson,
+    DuneHoldersJson, DuneHtml, DuneJson, DuneOutput, DuneOutputJson,
   },
   dune_balances::DuneBalancesHtml,
   dunes::DunesHtml,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
-  inscription::{InscriptionByAddressJson, InscriptionHtml, InscriptionJson, ShibescriptionJson},
+  inscription::{
+    InscriptionByAddressJson, InscriptionHistoryJson, InscriptionHtml, InscriptionIdsJson,
+    InscriptionJson, InscriptionRecursiveJson, InscriptionsBySha256Json,
+    MetaprotocolInscriptionsJson, ShibescriptionJson,
+  },
   inscriptions::InscriptionsHtml,
   output::AddressOutputJson,
+  output::AddressTransactionsJson,
   output::OutputHtml,
   output::OutputJson,
   page_config::PageConfig,
@@ -25,10 +33,12 @@ pub(crate) use {
   range::RangeHtml,
   rare::RareTxt,
   sat::SatHtml,
+  search::{SearchHtml, SearchJson},
   transaction::TransactionHtml,
   utxo::Utxo,
 };
 
+mod address;
 mod block;
 mod bit20;
 mod dune;
@@ -44,6 +54,7 @@ mod preview;
 mod range;
 mod rare;
 mod sat;
+mod search;
 mod transaction;
 mod utxo;
 
@@ -0,0 +1,43 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHtml {
+  pub(crate) address: Address,
+  pub(crate) outputs: Vec<OutPoint>,
+}
+
+impl PageContent for AddressHtml {
+  fn title(&self) -> String {
+    format!("Address {}", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display_outputs() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        outputs: vec![outpoint(1), outpoint(2)],
+      },
+      "
+        <h1>Address <span class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</span></h1>
+        <dl>
+          <dt>outputs</dt>
+          <dd>
+            <ul class=monospace>
+              <li><a href=/output/1{64}:1>1{64}:1</a></li>
+              <li><a href=/output/2{64}:2>2{64}:2</a></li>
+            </ul>
+          </dd>
+          <dt>dunes</dt>
+          <dd><a href=/dunes/balance/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4>balances</a></dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
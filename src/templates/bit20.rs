@@ -1,4 +1,7 @@
-use super::*;
+use {
+  super::*,
+  crate::bit20::{Event, OperationType, Receipt},
+};
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -49,3 +52,79 @@ impl BIT20 {
       && self.clone().op.is_some_and(|op| op != Operation::Unknown)
   }
 }
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub(crate) struct Dev20ReceiptJson {
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) inscription_number: i64,
+  pub(crate) old_satpoint: SatPoint,
+  pub(crate) new_satpoint: SatPoint,
+  pub(crate) op: OperationType,
+  pub(crate) from: String,
+  pub(crate) to: String,
+  pub(crate) valid: bool,
+  pub(crate) event: Option<Event>,
+  pub(crate) error: Option<String>,
+}
+
+impl From<Receipt> for Dev20ReceiptJson {
+  fn from(receipt: Receipt) -> Self {
+    let (event, error) = match receipt.result {
+      Ok(event) => (Some(event), None),
+      Err(error) => (None, Some(error.to_string())),
+    };
+
+    Self {
+      inscription_id: receipt.inscription_id,
+      inscription_number: receipt.inscription_number,
+      old_satpoint: receipt.old_satpoint,
+      new_satpoint: receipt.new_satpoint,
+      op: receipt.op,
+      from: receipt.from.to_string(),
+      to: receipt.to.to_string(),
+      valid: error.is_none(),
+      event,
+      error,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub(crate) struct Dev20TransactionReceiptsJson {
+  pub(crate) txid: Txid,
+  pub(crate) receipts: Vec<Dev20ReceiptJson>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub(crate) struct Dev20InscriptionReceiptsJson {
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) receipts: Vec<Dev20ReceiptJson>,
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::bit20::{script_key::ScriptKey, BIT20Error},
+  };
+
+  #[test]
+  fn failed_receipt_reports_error() {
+    let script_key = ScriptKey::from_script(&Script::new(), Network::Bitcoin);
+
+    let json = Dev20ReceiptJson::from(Receipt {
+      inscription_id: inscription_id(1),
+      inscription_number: 0,
+      old_satpoint: satpoint(1, 0),
+      new_satpoint: satpoint(2, 0),
+      op: OperationType::Mint,
+      from: script_key.clone(),
+      to: script_key,
+      result: Err(BIT20Error::TickNotFound("doge".into())),
+    });
+
+    assert!(!json.valid);
+    assert_eq!(json.event, None);
+    assert_eq!(json.error, Some("tick: doge not found".into()));
+  }
+}
@@ -1,4 +1,6 @@
 use super::*;
+use bitcoin::TxMerkleNode;
+use bitcoincore_rpc::json::GetBlockHeaderResult;
 use std::collections::HashMap;
 
 impl Serialize for Height {
@@ -62,6 +64,43 @@ impl BlockJson {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockInfoJson {
+  pub bits: String,
+  pub confirmations: i32,
+  pub difficulty: f64,
+  pub hash: BlockHash,
+  pub height: usize,
+  pub merkle_root: TxMerkleNode,
+  pub median_time: Option<usize>,
+  pub next_block_hash: Option<BlockHash>,
+  pub nonce: u32,
+  pub previous_block_hash: Option<BlockHash>,
+  pub timestamp: usize,
+  pub transaction_count: usize,
+  pub version: i32,
+}
+
+impl From<GetBlockHeaderResult> for BlockInfoJson {
+  fn from(info: GetBlockHeaderResult) -> Self {
+    Self {
+      bits: info.bits,
+      confirmations: info.confirmations,
+      difficulty: info.difficulty,
+      hash: info.hash,
+      height: info.height,
+      merkle_root: info.merkle_root,
+      median_time: info.median_time,
+      next_block_hash: info.next_block_hash,
+      nonce: info.nonce,
+      previous_block_hash: info.previous_block_hash,
+      timestamp: info.time,
+      transaction_count: info.n_tx,
+      version: info.version,
+    }
+  }
+}
+
 #[derive(Boilerplate)]
 pub(crate) struct BlockHtml {
   hash: BlockHash,
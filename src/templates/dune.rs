@@ -3,6 +3,8 @@ use super::*;
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct DuneHtml {
   pub(crate) entry: DuneEntry,
+  pub(crate) events: Vec<DuneEvent>,
+  pub(crate) holders: u64,
   pub(crate) id: DuneId,
   pub(crate) mintable: bool,
   pub(crate) inscription: Option<InscriptionId>,
@@ -25,11 +27,38 @@ pub(crate) struct DuneEntryJson {
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct DuneJson {
   pub(crate) entry: DuneEntryJson,
+  pub(crate) holders: u64,
   pub(crate) id: DuneId,
   pub(crate) mintable: bool,
   pub(crate) inscription: Option<InscriptionId>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DuneEventsJson {
+  pub(crate) dune: SpacedDune,
+  pub(crate) events: Vec<DuneEvent>,
+  pub(crate) id: DuneId,
+  pub(crate) more: bool,
+  pub(crate) page: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DuneHolderJson {
+  pub(crate) address: Option<String>,
+  pub(crate) balance: u128,
+  pub(crate) script_pubkey: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DuneHoldersJson {
+  pub(crate) dune: SpacedDune,
+  pub(crate) holders: Vec<DuneHolderJson>,
+  pub(crate) id: DuneId,
+  pub(crate) more: bool,
+  pub(crate) page: usize,
+  pub(crate) total: u64,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct DuneOutputJson {
   pub(crate) dune: SpacedDune,
@@ -5,16 +5,23 @@ use super::*;
 #[derive(Boilerplate, Default)]
 pub(crate) struct InscriptionHtml {
   pub(crate) chain: Chain,
+  pub(crate) children: Vec<InscriptionId>,
   pub(crate) genesis_fee: u64,
   pub(crate) genesis_height: u32,
+  pub(crate) history: Vec<InscriptionTransfer>,
   pub(crate) inscription: Inscription,
   pub(crate) inscription_id: InscriptionId,
   pub(crate) inscription_number: u64,
+  pub(crate) metadata: Option<serde_json::Value>,
+  pub(crate) metaprotocol: Option<String>,
   pub(crate) next: Option<InscriptionId>,
+  pub(crate) original: Option<InscriptionId>,
   pub(crate) output: TxOut,
+  pub(crate) parent: Option<InscriptionId>,
   pub(crate) previous: Option<InscriptionId>,
   pub(crate) dune: Option<SpacedDune>,
   pub(crate) sat: Option<Sat>,
+  pub(crate) sha256: Option<sha256::Hash>,
   pub(crate) satpoint: SatPoint,
   pub(crate) timestamp: DateTime<Utc>,
 }
@@ -22,21 +29,41 @@ pub(crate) struct InscriptionHtml {
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct ShibescriptionJson {
   pub(crate) chain: Chain,
+  pub(crate) children: Vec<InscriptionId>,
   pub(crate) genesis_fee: u64,
   pub(crate) genesis_height: u32,
   pub(crate) inscription: Inscription,
   pub(crate) inscription_id: InscriptionId,
   pub(crate) inscription_number: u64,
+  pub(crate) metadata: Option<serde_json::Value>,
+  pub(crate) metaprotocol: Option<String>,
   pub(crate) next: Option<InscriptionId>,
+  pub(crate) original: Option<InscriptionId>,
   pub(crate) output: TxOut,
   pub(crate) address: Option<String>,
+  pub(crate) parent: Option<InscriptionId>,
   pub(crate) previous: Option<InscriptionId>,
   pub(crate) dune: Option<SpacedDune>,
   pub(crate) sat: Option<Sat>,
+  pub(crate) sha256: Option<sha256::Hash>,
   pub(crate) satpoint: SatPoint,
   pub(crate) timestamp: DateTime<Utc>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionsBySha256Json {
+  pub ids: Vec<InscriptionId>,
+  pub sha256: sha256::Hash,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct InscriptionHistoryJson {
+  pub(crate) history: Vec<InscriptionTransfer>,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) more: bool,
+  pub(crate) page: usize,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InscriptionJson {
   pub tx_id: String,
@@ -47,6 +74,8 @@ pub struct InscriptionJson {
   pub genesis_height: u32,
   pub inscription_id: InscriptionId,
   pub inscription_number: u64,
+  pub metaprotocol: Option<String>,
+  pub parent: Option<InscriptionId>,
   //pub dune: Option<SpacedDune>,
   pub timestamp: u32,
 }
@@ -65,6 +94,36 @@ pub struct InscriptionByAddressJson {
   pub offset: u64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionRecursiveJson {
+  pub content_length: Option<usize>,
+  pub content_type: Option<String>,
+  pub fee: u64,
+  pub height: u32,
+  pub id: InscriptionId,
+  pub number: u64,
+  pub output: OutPoint,
+  pub sat: Option<Sat>,
+  pub satpoint: SatPoint,
+  pub timestamp: u32,
+  pub value: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionIdsJson {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetaprotocolInscriptionsJson {
+  pub ids: Vec<InscriptionId>,
+  pub metaprotocol: String,
+  pub more: bool,
+  pub page: usize,
+}
+
 impl PageContent for InscriptionHtml {
   fn title(&self) -> String {
     format!("Inscription {}", self.inscription_number)
@@ -188,6 +247,228 @@ mod tests {
     );
   }
 
+  #[test]
+  fn with_parent_and_children() {
+    assert_regex_match!(
+      InscriptionHtml {
+        children: vec![inscription_id(2), inscription_id(3)],
+        genesis_fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        output: tx_out(1, address()),
+        parent: Some(inscription_id(4)),
+        satpoint: satpoint(1, 0),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        .*
+        <dl>
+          .*
+          <dt>parent</dt>
+          <dd><a class=monospace href=/inscription/4{64}i4>4{64}i4</a></dd>
+          <dt>children</dt>
+          <dd>
+            <a class=monospace href=/inscription/2{64}i2>2{64}i2</a>
+            <a class=monospace href=/inscription/3{64}i3>3{64}i3</a>
+            <a href=/r/children/1{64}i1>all</a>
+          </dd>
+          <dt>preview</dt>
+          .*
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_history() {
+    assert_regex_match!(
+      InscriptionHtml {
+        genesis_fee: 1,
+        history: vec![
+          InscriptionTransfer {
+            height: 2,
+            sequence: 0,
+            txid: txid(2),
+            old_satpoint: Some(satpoint(1, 0)),
+            new_satpoint: satpoint(2, 0),
+            owner: Some(address().to_string()),
+          },
+          InscriptionTransfer {
+            height: 1,
+            sequence: 0,
+            txid: txid(1),
+            old_satpoint: None,
+            new_satpoint: satpoint(1, 0),
+            owner: None,
+          },
+        ],
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        output: tx_out(1, address()),
+        satpoint: satpoint(2, 0),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        .*
+        </dl>
+        <h2>History</h2>
+        <table>
+          .*
+          <tr>
+            <td><a href=/block/2>2</a></td>
+            <td class=monospace>1{64}:1:0</td>
+            <td><a class=monospace href=/output/2{64}:2>2{64}:2:0</a></td>
+            <td class=monospace>[[:alnum:]]+</td>
+            <td><a class=monospace href=/tx/2{64}>2{64}</a></td>
+          </tr>
+          <tr>
+            <td><a href=/block/1>1</a></td>
+            <td>inscribed</td>
+            <td><a class=monospace href=/output/1{64}:1>1{64}:1:0</a></td>
+            <td class=monospace></td>
+            <td><a class=monospace href=/tx/1{64}>1{64}</a></td>
+          </tr>
+        </table>
+        <a href=/inscription/1{64}i1/history>all</a>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_sha256() {
+    assert_regex_match!(
+      InscriptionHtml {
+        genesis_fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        original: Some(inscription_id(1)),
+        output: tx_out(1, address()),
+        satpoint: satpoint(1, 0),
+        sha256: Some(sha256::Hash::hash(b"HELLOWORLD")),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        .*
+          <dt>sha256</dt>
+          <dd><a class=monospace href=/inscriptions/by-hash/[[:xdigit:]]{64}>[[:xdigit:]]{64}</a></dd>
+          <dt>original</dt>
+          <dd>yes</dd>
+        .*
+      "
+      .unindent()
+    );
+
+    assert_regex_match!(
+      InscriptionHtml {
+        genesis_fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(2),
+        inscription_number: 2,
+        original: Some(inscription_id(1)),
+        output: tx_out(1, address()),
+        satpoint: satpoint(1, 0),
+        sha256: Some(sha256::Hash::hash(b"HELLOWORLD")),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 2</h1>
+        .*
+          <dt>duplicate of</dt>
+          <dd><a class=monospace href=/inscription/1{64}i1>1{64}i1</a></dd>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_metadata() {
+    assert_regex_match!(
+      InscriptionHtml {
+        genesis_fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        metadata: Some(serde_json::json!({"name": "<b>"})),
+        output: tx_out(1, address()),
+        satpoint: satpoint(1, 0),
+        ..Default::default()
+      },
+      r#"
+        <h1>Inscription 1</h1>
+        .*
+          <dt>content type</dt>
+          <dd>text/plain;charset=utf-8</dd>
+          <dt>metadata</dt>
+          <dd><pre>\{
+          &quot;name&quot;: &quot;&lt;b&gt;&quot;
+        \}</pre></dd>
+          <dt>timestamp</dt>
+        .*
+      "#
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_metaprotocol() {
+    assert_regex_match!(
+      InscriptionHtml {
+        genesis_fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        metaprotocol: Some("dev-20".into()),
+        output: tx_out(1, address()),
+        satpoint: satpoint(1, 0),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        .*
+          <dt>content type</dt>
+          <dd>text/plain;charset=utf-8</dd>
+          <dt>metaprotocol</dt>
+          <dd><a href=\"/inscriptions\\?metaprotocol=dev-20&amp;page=0\">dev-20</a></dd>
+          <dt>timestamp</dt>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn metaprotocol_link_is_encoded() {
+    assert_regex_match!(
+      InscriptionHtml {
+        genesis_fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        inscription_id: inscription_id(1),
+        inscription_number: 1,
+        metaprotocol: Some("x onmouseover=alert(1)".into()),
+        output: tx_out(1, address()),
+        satpoint: satpoint(1, 0),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        .*
+          <dt>metaprotocol</dt>
+          <dd><a href=\"/inscriptions\\?metaprotocol=x\\+onmouseover%3Dalert%281%29&amp;page=0\">x onmouseover=alert\\(1\\)</a></dd>
+        .*
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn with_dune() {
     assert_regex_match!(
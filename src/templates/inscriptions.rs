@@ -3,6 +3,7 @@ use super::*;
 #[derive(Boilerplate)]
 pub(crate) struct InscriptionsHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) metaprotocol: Option<String>,
   pub(crate) prev: Option<u64>,
   pub(crate) next: Option<u64>,
 }
@@ -12,6 +13,27 @@ pub struct InscriptionsJson {
   pub inscriptions: Vec<InscriptionId>,
 }
 
+impl InscriptionsHtml {
+  pub(crate) fn metaprotocol_url(metaprotocol: &str, page: u64) -> String {
+    format!(
+      "/inscriptions?{}",
+      url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("metaprotocol", metaprotocol)
+        .append_pair("page", &page.to_string())
+        .finish()
+    )
+  }
+
+  // Unfiltered pages are addressed by inscription number, and pages filtered
+  // by metaprotocol by page index.
+  fn page_url(&self, n: u64) -> String {
+    match &self.metaprotocol {
+      Some(metaprotocol) => Self::metaprotocol_url(metaprotocol, n),
+      None => format!("/inscriptions/{n}"),
+    }
+  }
+}
+
 impl PageContent for InscriptionsHtml {
   fn title(&self) -> String {
     "Inscription".into()
@@ -27,6 +49,7 @@ mod tests {
     assert_regex_match!(
       InscriptionsHtml {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
+        metaprotocol: None,
         prev: None,
         next: None,
       },
@@ -50,6 +73,7 @@ mod tests {
     assert_regex_match!(
       InscriptionsHtml {
         inscriptions: vec![inscription_id(1), inscription_id(2)],
+        metaprotocol: None,
         prev: Some(1),
         next: Some(2),
       },
@@ -67,4 +91,27 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn with_metaprotocol() {
+    assert_regex_match!(
+      InscriptionsHtml {
+        inscriptions: vec![inscription_id(1)],
+        metaprotocol: Some("x onmouseover=alert(1)".into()),
+        prev: Some(0),
+        next: Some(2),
+      },
+      "
+        <h1>Inscriptions: x onmouseover=alert\\(1\\)</h1>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+        </div>
+        .*
+        <a class=prev href=/inscriptions\\?metaprotocol=x\\+onmouseover%3Dalert%281%29&amp;page=0>prev</a>
+        <a class=next href=/inscriptions\\?metaprotocol=x\\+onmouseover%3Dalert%281%29&amp;page=2>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
 }
@@ -25,6 +25,14 @@ impl AddressOutputJson {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AddressTransactionsJson {
+  pub(crate) address: String,
+  pub(crate) more: bool,
+  pub(crate) page: usize,
+  pub(crate) transactions: Vec<AddressActivity>,
+}
+
 impl PageContent for OutputHtml {
   fn title(&self) -> String {
     format!("Output {}", self.outpoint)
@@ -0,0 +1,85 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct SearchHtml {
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) more: bool,
+  pub(crate) page: usize,
+  pub(crate) query: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchJson {
+  pub inscriptions: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+  pub query: String,
+}
+
+impl SearchHtml {
+  fn page_url(&self, page: usize) -> String {
+    format!(
+      "/search?{}",
+      url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("q", &self.query)
+        .append_pair("page", &page.to_string())
+        .finish()
+    )
+  }
+}
+
+impl PageContent for SearchHtml {
+  fn title(&self) -> String {
+    format!("Search: {}", self.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_results() {
+    assert_regex_match!(
+      SearchHtml {
+        inscriptions: Vec::new(),
+        more: false,
+        page: 0,
+        query: "such <wow>".into(),
+      },
+      "
+        <h1>Search: such &lt;wow&gt;</h1>
+        <p>No inscriptions found.</p>
+        <div class=center>
+        prev
+        next
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_results_and_pages() {
+    assert_regex_match!(
+      SearchHtml {
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        more: true,
+        page: 1,
+        query: "\"such wow\"".into(),
+      },
+      "
+        <h1>Search: &quot;such wow&quot;</h1>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        <div class=center>
+        <a class=prev href=/search\\?q=%22such\\+wow%22&amp;page=0>prev</a>
+        <a class=next href=/search\\?q=%22such\\+wow%22&amp;page=2>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
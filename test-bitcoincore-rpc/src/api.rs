@@ -40,6 +40,14 @@ pub trait Api {
     replaceable: Option<bool>,
   ) -> Result<String, jsonrpc_core::Error>;
 
+  #[rpc(name = "fundrawtransaction")]
+  fn fund_raw_transaction(
+    &self,
+    tx: String,
+    options: Option<Value>,
+    is_witness: Option<bool>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
   #[rpc(name = "createwallet")]
   fn create_wallet(
     &self,
@@ -81,6 +89,9 @@ pub trait Api {
     include_watchonly: Option<bool>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
+  #[rpc(name = "getrawmempool")]
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error>;
+
   #[rpc(name = "getrawtransaction")]
   fn get_raw_transaction(
     &self,
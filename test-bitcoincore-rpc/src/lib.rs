@@ -117,6 +117,8 @@ pub struct TransactionTemplate<'a> {
   pub inputs: &'a [(usize, usize, usize)],
   pub output_values: &'a [u64],
   pub outputs: usize,
+  pub recipient: Option<Address>,
+  pub script_sig: Script,
   pub witness: Witness,
 }
 
@@ -149,6 +151,8 @@ impl<'a> Default for TransactionTemplate<'a> {
       inputs: &[],
       output_values: &[],
       outputs: 1,
+      recipient: None,
+      script_sig: Script::new(),
       witness: Witness::default(),
     }
   }
@@ -188,6 +192,10 @@ impl Handle {
     self.state().broadcast_tx(template)
   }
 
+  pub fn fail_get_block(&self, fail_get_block: bool) {
+    self.state().fail_get_block = fail_get_block;
+  }
+
   pub fn invalidate_tip(&self) -> BlockHash {
     self.state().pop_block()
   }
@@ -221,6 +229,10 @@ impl Handle {
     self.state().locked.insert(output);
   }
 
+  pub fn locked(&self) -> BTreeSet<OutPoint> {
+    self.state().locked.clone()
+  }
+
   pub fn network(&self) -> String {
     match self.state().network {
       Network::Bitcoin => "mainnet".to_string(),
@@ -143,7 +143,11 @@ impl Api for Server {
 
   fn get_block(&self, block_hash: BlockHash, verbose: bool) -> Result<String, jsonrpc_core::Error> {
     assert_eq!(verbose, false, "Verbosity level {verbose} is unsupported");
-    match self.state().blocks.get(&block_hash) {
+    let state = self.state();
+    if state.fail_get_block {
+      return Err(jsonrpc_core::Error::internal_error());
+    }
+    match state.blocks.get(&block_hash) {
       Some(block) => Ok(hex::encode(serialize(block))),
       None => Err(Self::not_found()),
     }
@@ -219,6 +223,78 @@ impl Api for Server {
     Ok(hex::encode(serialize(&tx)))
   }
 
+  fn fund_raw_transaction(
+    &self,
+    tx: String,
+    options: Option<Value>,
+    _is_witness: Option<bool>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    let options = options.unwrap_or_default();
+
+    let mut transaction = Transaction::deserialize(&hex::decode(tx).unwrap()).unwrap();
+
+    let change_position = options["changePosition"]
+      .as_u64()
+      .map(|position| usize::try_from(position).unwrap())
+      .unwrap_or(transaction.output.len());
+
+    assert!(change_position <= transaction.output.len());
+
+    let fee_rate = options["feeRate"]
+      .as_f64()
+      .map(|fee_rate| Amount::from_btc(fee_rate).unwrap().to_sat())
+      .unwrap_or(100_000);
+
+    let state = self.state();
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|output| output.value)
+      .sum::<u64>();
+
+    let mut input_value = 0;
+
+    for (outpoint, amount) in &state.utxos {
+      if input_value > output_value {
+        break;
+      }
+
+      if state.locked.contains(outpoint) {
+        continue;
+      }
+
+      transaction.input.push(TxIn {
+        previous_output: *outpoint,
+        script_sig: Script::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      });
+
+      input_value += amount.to_sat();
+    }
+
+    transaction.output.insert(
+      change_position,
+      TxOut {
+        value: 0,
+        script_pubkey: Script::new(),
+      },
+    );
+
+    let fee = fee_rate * transaction.vsize() as u64 / 1000;
+
+    assert!(input_value >= output_value + fee, "insufficient funds");
+
+    transaction.output[change_position].value = input_value - output_value - fee;
+
+    Ok(serde_json::json!({
+      "hex": hex::encode(serialize(&transaction)),
+      "fee": Amount::from_sat(fee).to_btc(),
+      "changepos": change_position,
+    }))
+  }
+
   fn create_wallet(
     &self,
     name: String,
@@ -332,6 +408,10 @@ impl Api for Server {
     }
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(self.state().mempool.iter().map(|tx| tx.txid()).collect())
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -363,7 +443,12 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match self.state().transactions.get(&txid) {
+      let state = self.state();
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -524,8 +609,6 @@ impl Api for Server {
     unlock: bool,
     outputs: Vec<JsonOutPoint>,
   ) -> Result<bool, jsonrpc_core::Error> {
-    assert!(!unlock);
-
     let mut state = self.state();
 
     if state.fail_lock_unspent {
@@ -538,7 +621,11 @@ impl Api for Server {
         txid: output.txid,
       };
       assert!(state.utxos.contains_key(&output));
-      state.locked.insert(output);
+      if unlock {
+        state.locked.remove(&output);
+      } else {
+        state.locked.insert(output);
+      }
     }
 
     Ok(true)
@@ -3,6 +3,7 @@ use super::*;
 pub(crate) struct State {
   pub(crate) blocks: BTreeMap<BlockHash, Block>,
   pub(crate) descriptors: Vec<String>,
+  pub(crate) fail_get_block: bool,
   pub(crate) fail_lock_unspent: bool,
   pub(crate) hashes: Vec<BlockHash>,
   pub(crate) locked: BTreeSet<OutPoint>,
@@ -38,6 +39,7 @@ impl State {
     Self {
       blocks,
       descriptors: Vec::new(),
+      fail_get_block: false,
       fail_lock_unspent,
       hashes,
       locked: BTreeSet::new(),
@@ -144,7 +146,11 @@ impl State {
       total_value += tx.output[*vout].value;
       input.push(TxIn {
         previous_output: OutPoint::new(tx.txid(), *vout as u32),
-        script_sig: Script::new(),
+        script_sig: if i == 0 {
+          template.script_sig.clone()
+        } else {
+          Script::new()
+        },
         sequence: Sequence::MAX,
         witness: if i == 0 {
           template.witness.clone()
@@ -171,7 +177,11 @@ impl State {
             .get(i)
             .cloned()
             .unwrap_or(value_per_output),
-          script_pubkey: script::Builder::new().into_script(),
+          script_pubkey: template
+            .recipient
+            .as_ref()
+            .map(Address::script_pubkey)
+            .unwrap_or_default(),
         })
         .collect(),
     };
@@ -0,0 +1,82 @@
+use {
+  super::*,
+  bitcoin::{
+    blockdata::script,
+    hashes::{sha256, Hash},
+    Script,
+  },
+  ord::subcommand::find_duplicates::Output,
+  std::io::Write,
+  test_bitcoincore_rpc::TransactionTemplate,
+};
+
+fn inscription_script_sig(body: &[u8], content_encoding: Option<&str>) -> Script {
+  let mut builder = script::Builder::new()
+    .push_slice(b"ord")
+    .push_int(1)
+    .push_slice(b"text/plain;charset=utf-8")
+    .push_int(0)
+    .push_slice(body);
+
+  if let Some(content_encoding) = content_encoding {
+    builder = builder.push_int(9).push_slice(content_encoding.as_bytes());
+  }
+
+  builder.into_script()
+}
+
+#[test]
+fn no_duplicates_without_inscriptions() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  assert_eq!(
+    CommandBuilder::new("find-duplicates")
+      .rpc_server(&rpc_server)
+      .output::<Vec<Output>>(),
+    Vec::new()
+  );
+}
+
+#[test]
+fn duplicates_are_found_by_decoded_content() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+  gzip.write_all(b"woof").unwrap();
+  let gzip = gzip.finish().unwrap();
+
+  let original = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0)],
+    script_sig: inscription_script_sig(b"woof", None),
+    ..Default::default()
+  });
+  rpc_server.mine_blocks(1);
+
+  let duplicate = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(2, 0, 0)],
+    script_sig: inscription_script_sig(&gzip, Some("gzip")),
+    ..Default::default()
+  });
+  rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(3, 0, 0)],
+    script_sig: inscription_script_sig(b"meow", None),
+    ..Default::default()
+  });
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("find-duplicates")
+    .rpc_server(&rpc_server)
+    .output::<Vec<Output>>();
+
+  assert_eq!(output.len(), 1);
+  assert_eq!(output[0].sha256, sha256::Hash::hash(b"woof"));
+  assert_eq!(output[0].original.to_string(), format!("{original}i0"));
+  assert_eq!(
+    output[0]
+      .duplicates
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<String>>(),
+    [format!("{duplicate}i0")]
+  );
+}
@@ -74,6 +74,7 @@ mod core;
 mod epochs;
 mod expected;
 mod find;
+mod find_duplicates;
 mod index;
 mod info;
 mod list;
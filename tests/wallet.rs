@@ -2,8 +2,10 @@ use super::*;
 
 mod balance;
 mod create;
+mod dev20;
 mod inscribe;
 mod inscriptions;
+mod mint;
 mod outputs;
 mod receive;
 mod restore;
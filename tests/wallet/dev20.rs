@@ -0,0 +1,213 @@
+use {super::*, bitcoin::Address};
+
+#[derive(Deserialize)]
+struct Dev20 {
+  commit: Txid,
+  inscription: String,
+  reveal: Txid,
+  send: Option<Txid>,
+}
+
+fn dev20(rpc_server: &test_bitcoincore_rpc::Handle, args: &str) -> Dev20 {
+  let output = CommandBuilder::new(format!("--index-dunes wallet dev20 {args}"))
+    .rpc_server(rpc_server)
+    .output::<Dev20>();
+
+  rpc_server.mine_blocks(1);
+
+  output
+}
+
+#[test]
+fn dev20_requires_bit20_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new("wallet dev20 mint --tick doge --amount 1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: `ord wallet dev20` requires index created with `--index-dunes` flag\n")
+    .run();
+}
+
+#[test]
+fn mint_requires_deployed_token() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-dunes wallet dev20 mint --tick doge --amount 1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: dev-20 token `doge` has not been deployed\n")
+    .run();
+}
+
+#[test]
+fn transfer_requires_deployed_token() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-dunes wallet dev20 transfer --tick doge --amount 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: dev-20 token `doge` has not been deployed\n")
+  .run();
+}
+
+#[test]
+fn deploy_rejects_zero_supply() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-dunes wallet dev20 deploy --tick doge --max 0")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: max must be greater than zero\n")
+    .run();
+}
+
+#[test]
+fn deploy_inscribes_deploy_operation() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("--index-dunes wallet dev20 deploy --tick doge --max 1000 --limit 100")
+      .rpc_server(&rpc_server)
+      .output::<Dev20>();
+
+  assert_eq!(output.send, None);
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 2);
+  assert_eq!(mempool[0].txid(), output.commit);
+  assert_eq!(mempool[1].txid(), output.reveal);
+  assert_eq!(mempool[1].input[0].previous_output.txid, output.commit);
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &["--index-dunes"]);
+
+  assert_eq!(
+    server
+      .request(format!("/content/{}", output.inscription))
+      .text()
+      .unwrap(),
+    r#"{"p":"dev-20","op":"deploy","tick":"doge","max":"1000","lim":"100"}"#
+  );
+
+  server.assert_response_regex(
+    "/dev20/tick/doge",
+    r#".*"supply":1000,"minted":0,"limit_per_mint":100,.*"#,
+  );
+}
+
+#[test]
+fn mint_inscribes_mint_operation() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  dev20(&rpc_server, "deploy --tick doge --max 1000 --limit 100");
+
+  let output = CommandBuilder::new("--index-dunes wallet dev20 mint --tick doge --amount 100")
+    .rpc_server(&rpc_server)
+    .output::<Dev20>();
+
+  assert_eq!(output.send, None);
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 2);
+  assert_eq!(mempool[0].txid(), output.commit);
+  assert_eq!(mempool[1].txid(), output.reveal);
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &["--index-dunes"]);
+
+  assert_eq!(
+    server
+      .request(format!("/content/{}", output.inscription))
+      .text()
+      .unwrap(),
+    r#"{"p":"dev-20","op":"mint","tick":"doge","amt":"100"}"#
+  );
+
+  server.assert_response_regex(
+    "/dev20/tick/doge",
+    r#".*"supply":1000,"minted":100,"limit_per_mint":100,.*"#,
+  );
+}
+
+#[test]
+fn transfer_inscribes_to_holder_and_sends_reveal_output_to_recipient() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  dev20(&rpc_server, "deploy --tick doge --max 1000 --limit 100");
+
+  let mint = CommandBuilder::new("--index-dunes wallet dev20 mint --tick doge --amount 100")
+    .rpc_server(&rpc_server)
+    .output::<Dev20>();
+
+  let holder = rpc_server
+    .mempool()
+    .into_iter()
+    .find(|tx| tx.txid() == mint.reveal)
+    .unwrap()
+    .output[0]
+    .script_pubkey
+    .clone();
+
+  rpc_server.mine_blocks(1);
+
+  let recipient = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+  let output = CommandBuilder::new(format!(
+    "--index-dunes wallet dev20 transfer --tick doge --amount 40 {recipient}"
+  ))
+  .rpc_server(&rpc_server)
+  .output::<Dev20>();
+
+  let mempool = rpc_server.mempool();
+
+  let reveal = mempool
+    .iter()
+    .find(|tx| tx.txid() == output.reveal)
+    .unwrap();
+
+  let body = br#"{"p":"dev-20","op":"transfer","tick":"doge","amt":"40"}"#;
+
+  assert_eq!(reveal.output[0].script_pubkey, holder);
+  assert!(reveal.input[0]
+    .script_sig
+    .as_bytes()
+    .windows(body.len())
+    .any(|window| window == body));
+
+  // the reveal is still unconfirmed when it is spent
+  let send = mempool
+    .iter()
+    .find(|tx| Some(tx.txid()) == output.send)
+    .unwrap();
+
+  assert_eq!(
+    send.input[0].previous_output,
+    OutPoint {
+      txid: output.reveal,
+      vout: 0,
+    }
+  );
+  assert_eq!(
+    send.output[0].script_pubkey,
+    Address::from_str(recipient).unwrap().script_pubkey()
+  );
+  assert_eq!(send.output[0].value, reveal.output[0].value);
+}
@@ -396,3 +396,85 @@ fn inscribe_with_no_limit() {
     .write("degenerate.png", four_megger)
     .rpc_server(&rpc_server);
 }
+
+#[derive(Deserialize)]
+struct Batch {
+  commit: Txid,
+  inscriptions: Vec<BatchInscription>,
+  fees: u64,
+}
+
+#[derive(Deserialize)]
+struct BatchInscription {
+  reveal: Txid,
+}
+
+#[test]
+fn batch_inscribe_funds_commit_outputs_in_batch_order() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --batch batch.yaml --fee-rate 2")
+    .write(
+      "batch.yaml",
+      "inscriptions:\n- file: a.txt\n- file: b.txt\n",
+    )
+    .write("a.txt", "A")
+    .write("b.txt", [0; 1000])
+    .rpc_server(&rpc_server)
+    .output::<Batch>();
+
+  let mempool = rpc_server.mempool();
+
+  assert_eq!(mempool.len(), 1 + output.inscriptions.len());
+
+  let commit = &mempool[0];
+  assert_eq!(commit.txid(), output.commit);
+  assert_eq!(commit.output.len(), 3);
+
+  let mut fees = commit
+    .input
+    .iter()
+    .map(|input| {
+      rpc_server
+        .get_utxo_amount(&input.previous_output)
+        .unwrap()
+        .to_sat()
+    })
+    .sum::<u64>()
+    - commit.output.iter().map(|output| output.value).sum::<u64>();
+
+  for (vout, (inscription, reveal)) in output.inscriptions.iter().zip(&mempool[1..]).enumerate() {
+    assert_eq!(reveal.txid(), inscription.reveal);
+    assert_eq!(
+      reveal.input[0].previous_output,
+      OutPoint {
+        txid: output.commit,
+        vout: vout.try_into().unwrap(),
+      }
+    );
+
+    fees +=
+      commit.output[vout].value - reveal.output.iter().map(|output| output.value).sum::<u64>();
+  }
+
+  assert!(commit.output[0].value < commit.output[1].value);
+  assert_eq!(output.fees, fees);
+}
+
+#[test]
+fn batch_inscribe_dry_run_leaves_no_outputs_locked() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  inscribe(&rpc_server);
+
+  CommandBuilder::new("wallet inscribe --batch batch.yaml --dry-run")
+    .write("batch.yaml", "inscriptions:\n- file: a.txt\n")
+    .write("a.txt", "A")
+    .rpc_server(&rpc_server)
+    .output::<Batch>();
+
+  assert!(rpc_server.mempool().is_empty());
+  assert!(rpc_server.locked().is_empty());
+}
@@ -0,0 +1,84 @@
+use {
+  super::*,
+  ord::{DuneId, Dunestone, Edict},
+};
+
+#[test]
+fn minting_requires_dune_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new("wallet mint --fee-rate 1 --dune AAAAAAAAAAAAA")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: `ord wallet mint` requires index created with `--index-dunes` flag\n")
+    .run();
+}
+
+#[test]
+fn minting_unetched_dune_fails() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-dunes wallet mint --fee-rate 1 --dune AAAAAAAAAAAAA")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: dune `AAAAAAAAAAAAA` has not been etched\n")
+    .run();
+}
+
+#[test]
+fn minting_claims_limit_to_destination_output() {
+  #[derive(Deserialize)]
+  struct Etch {
+    transaction: Txid,
+  }
+
+  #[derive(Deserialize)]
+  struct Mint {
+    mint: Txid,
+  }
+
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let etch = CommandBuilder::new(
+    "--index-dunes wallet etch --divisibility 0 --fee-rate 1 --dune AAAAAAAAAAAAAA --supply 1000 --symbol x --cap 10 --limit 100",
+  )
+  .rpc_server(&rpc_server)
+  .output::<Etch>();
+
+  assert_eq!(
+    rpc_server.mine_blocks(1)[0].txdata[1].txid(),
+    etch.transaction
+  );
+
+  let output = CommandBuilder::new("--index-dunes wallet mint --fee-rate 1 --dune AAAAAAAAAAAAAA")
+    .rpc_server(&rpc_server)
+    .output::<Mint>();
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 1);
+
+  let tx = &mempool[0];
+  assert_eq!(tx.txid(), output.mint);
+
+  let dunestone = Dunestone::from_transaction(tx).unwrap();
+
+  assert_eq!(
+    dunestone.edicts,
+    [Edict {
+      id: u128::from(DuneId {
+        height: 2,
+        index: 1,
+      }) | (1 << 48),
+      amount: 100,
+      output: 1,
+    }]
+  );
+  assert!(dunestone.etching.is_none());
+  assert!(!dunestone.cenotaph);
+  assert_eq!(tx.output[1].value, 10_000);
+}